@@ -1,13 +1,195 @@
+use crate::date;
+use crate::model::export::ExportFormat;
+use crate::model::filter::{self, Filter, Selection, TaskReference};
+use crate::model::import::ImportFormat;
+use crate::model::import::csv::ColumnMapping;
+use crate::model::milestone::Milestone;
+use crate::model::project;
+use crate::model::report::ReportFormat;
+use crate::model::search::{self, SearchTerm};
+use crate::model::sort::{ListOptions, SortKey};
+use crate::model::token::Scope;
+use crate::model::view::{Column, View};
+use crate::model::{Priority, TaskState};
+
 use std::env;
 
+// The commands, for the completion of the shell.
+pub const COMMAND_NAMES: &[&str] = &[
+    "list",
+    "board",
+    "view",
+    "search",
+    "add",
+    "show",
+    "update",
+    "delete",
+    "mark",
+    "priority",
+    "tag",
+    "untag",
+    "plan",
+    "unplan",
+    "milestone",
+    "roadmap",
+    "note",
+    "trash",
+    "restore",
+    "purge",
+    "archive",
+    "unarchive",
+    "stats",
+    "project",
+    "report",
+    "tui",
+    "shell",
+    "serve",
+    "token",
+    "rpc",
+    "import",
+    "export",
+    "sync",
+    "undo",
+    "redo",
+];
+
+pub const HELP_TEXT: &str = r"A simple CLI app for tracking tasks.
+    Usage: task-tracker <command> [command-arguments]
+    Commands:
+    - list [filter]                                             List all the tasks if filter not provided, or list only the tasks matching the filter (e.g.: task-tracker list done).
+                                                                Filters combine 'state:<state>', 'tag:<tag>', 'title~<text>', 'priority<op><priority>' and 'created<op><duration>'
+                                                                with 'and', 'or', 'not' and parentheses, where <op> is one of ':', '<', '<=', '>', '>=' ('created=7d' compares whole days)
+                                                                (e.g.: task-tracker list 'state:in-progress or (priority>=high and created>7d)').
+    - list @<view>                                              List the tasks of a saved view (e.g.: task-tracker list @standup).
+    - list [--sort <fields>] [--limit <n>] [--offset <n>]       Sort by fields in turn, each ascending or with ':desc', and show a page of the tasks. Combines with a filter or a view
+                                                                (e.g.: task-tracker list --sort priority:desc,created --limit 10 --offset 20). Long output goes through $PAGER.
+    - board [--project <name>] [filter]                         Show the tasks as a board with a column per state and the WIP counts. WIP limits are set in
+                                                                ~/tasks.config.json (e.g.: {'wip_limits': {'in-progress': 3}}) and 'mark' warns when one is exceeded.
+    - view save <name> [filter] [--columns <columns>]           Save a filter, the columns to show and the order as a view. Columns are id, state, priority, tags, created and title
+               [--sort <fields>]                                (e.g.: task-tracker view save standup 'state:in-progress tag:team' --columns id,priority,title --sort priority:desc).
+    - view list                                                 List the saved views (e.g.: task-tracker view list).
+    - view delete <name>                                        Delete a saved view (e.g.: task-tracker view delete standup).
+    - list --archived                                           List the archived tasks (e.g.: task-tracker list --archived).
+    - search <terms>                                            Search the tasks, notes and tags, best matches first. Double quote phrases and end prefixes with * (e.g.: task-tracker search login auth*).
+    - add <task> [--milestone <milestone>] [--project <name>]   Add one task, optionally planned for a milestone or in a project (e.g.: task-tracker add 'Buy 3 eggs.' --milestone v1.2).
+    - project add <name>                                        Add a project. Its tasks are also numbered within the project, and the key can be given where
+                                                                a <task_id> is expected (e.g.: task-tracker project add infra, then task-tracker mark INFRA-12 done).
+    - project list                                              List the projects with their task counts (e.g.: task-tracker project list).
+    - list --project <name> [filter]                            List the tasks of a project (e.g.: task-tracker list --project infra state:in-progress).
+    - show <task_id>                                            Show task (e.g.: task-tracker show 1).
+    - update <task_id> <updated_task>                           Update task (e.g.: task-tracker update 1 'Buy 3 eggs and 1 milk.').
+                                                                Where a <task_id> is expected, a part of the task title can be given instead (e.g.: task-tracker mark 'login bug' done).
+                                                                Other commands than delete and mark also take the starts of its words or its letters in order (e.g.: task-tracker show lgnbg).
+    - delete <task_ids>                                         Delete tasks (e.g.: task-tracker delete 1 or task-tracker delete 4-9).
+    - mark <task_ids> ['not-started', 'in-progress', 'done']    Mark tasks as 'not-started', 'in-progress' or 'done' (e.g.: task-tracker mark 3,5,7-12 done).
+    - mark --where <filter> [--dry-run] <state>                 Mark every task matching the filter (e.g.: task-tracker mark --where 'state:in-progress tag:sprint-12' done).
+    - delete --where <filter> [--dry-run]                       Delete every task matching the filter (e.g.: task-tracker delete --where 'state:done' --dry-run).
+    - priority <task_ids> ['low', 'medium', 'high']             Set the priority of tasks (e.g.: task-tracker priority 3,5 high).
+    - tag <task_ids> <tag>                                      Tag tasks (e.g.: task-tracker tag 3,5 sprint-12).
+    - untag <task_ids> <tag>                                    Remove a tag from tasks (e.g.: task-tracker untag 3,5 sprint-12).
+    - note <task_id> <note>                                     Add a note to a task (e.g.: task-tracker note 1 'Free range only.').
+    - milestone add <name> <target_date> [description]          Add a milestone, or change an existing one (e.g.: task-tracker milestone add v1.2 2026-12-01 'Sync and sharing').
+    - milestone delete <name>                                   Delete a milestone, its tasks are kept unplanned (e.g.: task-tracker milestone delete v1.2).
+    - plan <task_ids> <milestone>                               Plan tasks for a milestone (e.g.: task-tracker plan 3,5 v1.2).
+    - unplan <task_ids>                                         Remove tasks from their milestone (e.g.: task-tracker unplan 3,5).
+    - roadmap                                                   List the milestones by target date with their progress and open tasks (e.g.: task-tracker roadmap).
+    - trash                                                     List the deleted tasks (e.g.: task-tracker trash).
+    - restore <task_id>                                         Restore a deleted task with its original id (e.g.: task-tracker restore 1).
+    - purge [--older-than <duration>]                           Permanently remove deleted tasks, optionally only the older ones (e.g.: task-tracker purge --older-than 30d).
+    - archive [--older-than <duration>]                         Move the done tasks to the archive, optionally only the ones done earlier (e.g.: task-tracker archive --older-than 14d).
+    - unarchive <task_id>                                       Move an archived task back to the tasks (e.g.: task-tracker unarchive 1).
+    - stats [--json] [--project <name>]                         Count the tasks per state, priority and tag, including the archived ones, with the weekly completion rate,
+                                                                the average time in each state and the oldest open tasks (e.g.: task-tracker stats --json).
+    - report burndown [--since <date>] [--format <format>]      Chart the open and done tasks per day from the task history, by default over the last 30 days.
+                                                                Formats are text, svg and csv (e.g.: task-tracker report burndown --since 2026-10-01 --format svg > burndown.svg).
+    - tui                                                       Browse and edit the tasks in a full-screen interface, with a live filter and a board view.
+                                                                Keys: j/k move, m mark, a add, e edit, d delete, / filter, b board, u undo, r redo, q quit (e.g.: task-tracker tui).
+    - shell                                                     Type commands one after the other in a shell keeping the tasks open, with line editing,
+                                                                history and completion; 'help' lists the commands and 'exit' leaves (e.g.: task-tracker shell).
+    - serve [--bind <address>]                                  Serve the tasks as a JSON API over HTTP, by default on 127.0.0.1:8080 (e.g.: task-tracker serve --bind 127.0.0.1:9000).
+                                                                Open the address in a browser for a page listing the tasks, with a board and forms to add, edit and mark them.
+                                                                Endpoints: GET and POST /tasks (with the 'filter', 'sort', 'limit' and 'offset' query parameters),
+                                                                GET, PUT, PATCH and DELETE /tasks/<task_id>, and PUT /tasks/<task_id>/state.
+                                                                GET /events streams the task-created, task-updated, task-deleted and task-state-changed events as server-sent events,
+                                                                for the changes made through the server and by other commands alike.
+                                                                Once a token exists, requests need one as 'Authorization: Bearer <token>' or the 'access_token' query parameter.
+    - token create <owner> [--scope <scope>]                    Create the API token of a user for the server, printed once. Scopes are read (the default) and read-write
+                                                                (e.g.: task-tracker token create alice --scope read-write).
+    - token list                                                List the owners of API tokens with their scope (e.g.: task-tracker token list).
+    - token revoke <owner>                                      Revoke the API token of a user (e.g.: task-tracker token revoke alice).
+                                                                Every change of the tasks is recorded in tasks.audit.jsonl with the owner of the token that made it, or 'local'.
+    - rpc --stdio                                               Answer JSON-RPC 2.0 requests on stdin, one per line, for editor integrations (e.g.: task-tracker rpc --stdio).
+                                                                Methods are list, search, show, add, update, note, delete, mark, priority, tag, untag, plan, unplan, trash, restore, purge,
+                                                                archived, archive, unarchive, milestones, projects, views, undo and redo,
+                                                                and changes are sent as task-created, task-updated, task-deleted and task-state-changed notifications,
+                                                                including the ones other commands made since the last request.
+    - import <format> <file> [--map <mapping>] [--dry-run]      Import tasks from todotxt, taskwarrior (the output of 'task export') or csv files, skipping the ones with the title
+                                                                of an existing task. CSV columns are found by header, title, state, priority, tags, project and notes by default,
+                                                                or mapped as <field>=<header> pairs (e.g.: task-tracker import csv issues.csv --map title=Summary,state=Status --dry-run).
+    - export --to <format> [filter]                             Print the tasks, or the ones matching the filter, as a markdown checklist grouped by state, a todotxt file,
+                                                                a csv file or an ics calendar of to-dos due on the target date of their milestone
+                                                                (e.g.: task-tracker export --to ics 'state:in-progress' > roadmap.ics).
+    - sync markdown <path>                                      Sync the checkbox items of a Markdown file with the tasks, both ways: new items become tasks, checking an item
+                                                                marks its task done, and tasks marked or renamed update their item. Items are linked to their task by a hidden
+                                                                comment, and deleting either side deletes the other while archived tasks keep their item; the rest of the file is kept as it is
+                                                                (e.g.: task-tracker sync markdown TODO.md).
+    - undo                                                      Revert the last add, update, delete or mark (e.g.: task-tracker undo).
+    - redo                                                      Reapply the last undone operation (e.g.: task-tracker redo).
+    ";
+
 #[derive(PartialEq, Debug)]
 pub enum Command {
-    List(String),
-    Add(String),
-    Show(u8),
-    Update((u8, String)),
-    Delete(u8),
-    Mark((u8, String)),
+    List((Option<Filter>, ListOptions)),
+    ListArchived(ListOptions),
+    ListView((String, ListOptions)),
+    Board(Option<Filter>),
+    ViewSave(View),
+    ViewList,
+    ViewDelete(String),
+    Search(Vec<SearchTerm>),
+    // The task, the milestone it is planned for and its project
+    Add((String, Option<String>, Option<String>)),
+    Show(TaskReference),
+    Update((TaskReference, String)),
+    Delete(Selection),
+    Mark((Selection, TaskState)),
+    Priority((Selection, Priority)),
+    Tag((Selection, String)),
+    Untag((Selection, String)),
+    Note((TaskReference, String)),
+    Plan((Selection, String)),
+    Unplan(Selection),
+    MilestoneAdd(Milestone),
+    MilestoneDelete(String),
+    Roadmap,
+    Trash,
+    Restore(u8),
+    Purge(Option<u64>),
+    Archive(Option<u64>),
+    Unarchive(u8),
+    // Whether to print the statistics as JSON, and the project to report on
+    Stats((bool, Option<String>)),
+    ProjectAdd(String),
+    ProjectList,
+    // The start of the report, if given, and its format
+    ReportBurndown((Option<u64>, ReportFormat)),
+    Tui,
+    Shell,
+    // The address to listen on
+    Serve(String),
+    // The owner of the token and what it allows
+    TokenCreate((String, Scope)),
+    TokenList,
+    TokenRevoke(String),
+    Rpc,
+    // The format, the file, the CSV columns and whether it is a dry run
+    Import((ImportFormat, String, ColumnMapping, bool)),
+    // The format and the tasks to export
+    Export((ExportFormat, Option<Filter>)),
+    // The path of the Markdown file
+    SyncMarkdown(String),
+    Undo,
+    Redo,
     Unknown,
 }
 
@@ -16,7 +198,7 @@ impl Command {
         let arguments: Vec<String> = env::args().collect();
         Self::parse_from(&arguments)
     }
-    fn parse_from(arguments: &Vec<String>) -> Self {
+    pub fn parse_from(arguments: &[String]) -> Self {
         // The first argument in arguments is the path of the executable (index: 0).
 
         // The second argument in arguments is the 'command' (index: 1).
@@ -27,91 +209,428 @@ impl Command {
 
         // The user have to provide at least one command
         let argument_count: usize = 2;
-        validate_argument_count(argument_count, &arguments);
+        validate_argument_count(argument_count, arguments);
 
         let command: &str = &arguments[COMMAND_INDEX];
         match command {
             "list" => {
-                // User have to provide one command argument <state_filter>
-                let argument_count: usize = 2;
-                validate_argument_count(argument_count, &arguments);
-
-                const STATE_FILTER_ARGUMENT_INDEX: usize = 2;
-                let state_filter: Option<&String> = arguments.get(STATE_FILTER_ARGUMENT_INDEX);
-                match state_filter {
-                    Some(filter) => Command::List(filter.clone()),
-                    None => Command::List(String::from("None")),
+                // User can provide a filter query, optionally split over several arguments,
+                // and the '--sort', '--limit', '--offset' and '--project' flags anywhere after the command
+                const FILTER_ARGUMENT_INDEX: usize = 2;
+                let (project, arguments) = take_flag("--project", arguments);
+                let (options, arguments) = parse_list_options(&arguments[FILTER_ARGUMENT_INDEX..]);
+                if project.is_some() {
+                    // A project scopes a filter query; views and the archive are not scoped.
+                    return Command::List((parse_scoped_filter(project, &arguments), options));
+                }
+                match arguments.first() {
+                    Some(flag) if flag == "--archived" => Command::ListArchived(options),
+                    Some(view) if view.starts_with('@') => {
+                        Command::ListView((view[1..].to_owned(), options))
+                    }
+                    Some(_) => {
+                        let query: String = arguments.join(" ");
+                        let filter: Filter = filter::query::parse_filter(&query)
+                            .unwrap_or_else(|error| panic!("{error}"));
+                        Command::List((Some(filter), options))
+                    }
+                    None => Command::List((None, options)),
+                }
+            }
+            "board" => {
+                // User can provide a filter query, optionally split over several arguments,
+                // and the '--project' flag anywhere after the command
+                const FILTER_ARGUMENT_INDEX: usize = 2;
+                let (project, arguments) = take_flag("--project", arguments);
+                Command::Board(parse_scoped_filter(
+                    project,
+                    &arguments[FILTER_ARGUMENT_INDEX..],
+                ))
+            }
+            "view" => {
+                // User have to provide a subcommand: save, list or delete
+                let argument_count: usize = 3;
+                validate_argument_count(argument_count, arguments);
+
+                const SUBCOMMAND_INDEX: usize = 2;
+                const VIEW_NAME_INDEX: usize = 3;
+                match arguments[SUBCOMMAND_INDEX].as_str() {
+                    "save" => {
+                        // User have to provide the <name>, optionally a <query> and '--columns <columns>'
+                        let argument_count: usize = 4;
+                        validate_argument_count(argument_count, arguments);
+
+                        let name: String = arguments[VIEW_NAME_INDEX].clone();
+                        Command::ViewSave(parse_view(name, &arguments[VIEW_NAME_INDEX + 1..]))
+                    }
+                    "list" => Command::ViewList,
+                    "delete" => {
+                        // User have to provide the <name>
+                        let argument_count: usize = 4;
+                        validate_argument_count(argument_count, arguments);
+
+                        Command::ViewDelete(arguments[VIEW_NAME_INDEX].clone())
+                    }
+                    _ => Command::Unknown,
                 }
             }
+            "search" => {
+                // User have to provide at least one search term, optionally over several arguments
+                let argument_count: usize = 3;
+                validate_argument_count(argument_count, arguments);
+
+                const SEARCH_ARGUMENT_INDEX: usize = 2;
+                let query: String = arguments[SEARCH_ARGUMENT_INDEX..].join(" ");
+                let terms: Vec<SearchTerm> =
+                    search::query::parse_search(&query).unwrap_or_else(|error| panic!("{error}"));
+
+                Command::Search(terms)
+            }
             "add" => {
-                // User have to provide one command argument <task>
+                // User have to provide one command argument <task>, optionally with
+                // '--milestone <milestone>' and '--project <project>'
+                let (milestone, arguments) = take_flag("--milestone", arguments);
+                let (project, arguments) = take_flag("--project", &arguments);
                 let argument_count: usize = 3;
                 validate_argument_count(argument_count, &arguments);
 
                 const ADD_TASK_ARGUMENT_INDEX: usize = 2;
                 let add_task: String = arguments[ADD_TASK_ARGUMENT_INDEX].clone();
 
-                Command::Add(add_task)
+                Command::Add((
+                    add_task,
+                    milestone,
+                    project.map(|project| project.to_lowercase()),
+                ))
             }
             "show" => {
                 // User have to provide one command argument <task_id>
                 let argument_count: usize = 3;
-                validate_argument_count(argument_count, &arguments);
+                validate_argument_count(argument_count, arguments);
 
-                let id: u8 = parse_id(&arguments[TASK_ID_ARGUMENT_INDEX]);
+                let reference: TaskReference = parse_reference(&arguments[TASK_ID_ARGUMENT_INDEX]);
 
-                Command::Show(id)
+                Command::Show(reference)
             }
             "update" => {
                 // User have to provide two command arguments <task_id> <updated_task>
                 let argument_count: usize = 4;
-                validate_argument_count(argument_count, &arguments);
+                validate_argument_count(argument_count, arguments);
 
-                let id: u8 = parse_id(&arguments[TASK_ID_ARGUMENT_INDEX]);
+                let reference: TaskReference = parse_reference(&arguments[TASK_ID_ARGUMENT_INDEX]);
 
                 const TASK_UPDATE_ARGUMENT_INDEX: usize = 3;
                 let task_update: String = arguments[TASK_UPDATE_ARGUMENT_INDEX].clone();
 
-                Command::Update((id, task_update))
+                Command::Update((reference, task_update))
             }
             "delete" => {
+                // User have to provide one command argument <task_ids>
+                let argument_count: usize = 3;
+                let (selection, _) = parse_selection(argument_count, arguments);
+
+                Command::Delete(selection)
+            }
+            "mark" => {
+                // User have to provide two command arguments <task_ids> <state>
+                let argument_count: usize = 4;
+                let (selection, arguments) = parse_selection(argument_count, arguments);
+
+                const STATE_ARGUMENT_INDEX: usize = 3;
+                let state: &String = &arguments[STATE_ARGUMENT_INDEX];
+                let state: TaskState = TaskState::try_from(state.to_owned())
+                    .unwrap_or_else(|_| panic!("Unknown task state '{state}'"));
+
+                Command::Mark((selection, state))
+            }
+            "priority" => {
+                // User have to provide two command arguments <task_ids> <priority>
+                let argument_count: usize = 4;
+                let (selection, arguments) = parse_selection(argument_count, arguments);
+
+                const PRIORITY_ARGUMENT_INDEX: usize = 3;
+                let priority: Priority =
+                    Priority::try_from(arguments[PRIORITY_ARGUMENT_INDEX].to_owned())
+                        .unwrap_or_else(|error| panic!("{error}"));
+
+                Command::Priority((selection, priority))
+            }
+            "tag" | "untag" => {
+                // User have to provide two command arguments <task_ids> <tag>
+                let argument_count: usize = 4;
+                let (selection, arguments) = parse_selection(argument_count, arguments);
+
+                const TAG_ARGUMENT_INDEX: usize = 3;
+                let tag: String = arguments[TAG_ARGUMENT_INDEX].to_owned();
+
+                match command {
+                    "tag" => Command::Tag((selection, tag)),
+                    _ => Command::Untag((selection, tag)),
+                }
+            }
+            "plan" => {
+                // User have to provide two command arguments <task_ids> <milestone>
+                let argument_count: usize = 4;
+                let (selection, arguments) = parse_selection(argument_count, arguments);
+
+                const MILESTONE_ARGUMENT_INDEX: usize = 3;
+                let milestone: String = arguments[MILESTONE_ARGUMENT_INDEX].to_owned();
+
+                Command::Plan((selection, milestone))
+            }
+            "unplan" => {
+                // User have to provide one command argument <task_ids>
+                let argument_count: usize = 3;
+                let (selection, _) = parse_selection(argument_count, arguments);
+
+                Command::Unplan(selection)
+            }
+            "milestone" => {
+                // User have to provide a subcommand: add or delete, and the <name>
+                let argument_count: usize = 4;
+                validate_argument_count(argument_count, arguments);
+
+                const SUBCOMMAND_INDEX: usize = 2;
+                const MILESTONE_NAME_INDEX: usize = 3;
+                let name: String = arguments[MILESTONE_NAME_INDEX].clone();
+                match arguments[SUBCOMMAND_INDEX].as_str() {
+                    "add" => {
+                        // User have to provide the <target_date>, optionally a <description>
+                        let argument_count: usize = 5;
+                        validate_argument_count(argument_count, arguments);
+
+                        const TARGET_DATE_INDEX: usize = 4;
+                        let target_date: u64 = date::parse_date(&arguments[TARGET_DATE_INDEX])
+                            .unwrap_or_else(|error| panic!("{error}"));
+                        const DESCRIPTION_INDEX: usize = 5;
+                        let description: String = arguments
+                            .get(DESCRIPTION_INDEX)
+                            .cloned()
+                            .unwrap_or_default();
+
+                        Command::MilestoneAdd(Milestone::new(name, target_date, description))
+                    }
+                    "delete" => Command::MilestoneDelete(name),
+                    _ => Command::Unknown,
+                }
+            }
+            "roadmap" => Command::Roadmap,
+            "tui" => Command::Tui,
+            "shell" => Command::Shell,
+            "serve" => {
+                // User can choose the address with the '--bind <address>' flag
+                const DEFAULT_BIND: &str = "127.0.0.1:8080";
+                let (bind, arguments) = take_flag("--bind", arguments);
+                const EXTRA_ARGUMENT_INDEX: usize = 2;
+                match arguments.get(EXTRA_ARGUMENT_INDEX) {
+                    Some(_) => Command::Unknown,
+                    None => Command::Serve(bind.unwrap_or_else(|| DEFAULT_BIND.to_owned())),
+                }
+            }
+            "note" => {
+                // User have to provide two command arguments <task_id> <note>
+                let argument_count: usize = 4;
+                validate_argument_count(argument_count, arguments);
+
+                let reference: TaskReference = parse_reference(&arguments[TASK_ID_ARGUMENT_INDEX]);
+
+                const NOTE_ARGUMENT_INDEX: usize = 3;
+                let note: String = arguments[NOTE_ARGUMENT_INDEX].clone();
+
+                Command::Note((reference, note))
+            }
+            "trash" => Command::Trash,
+            "restore" => {
                 // User have to provide one command argument <task_id>
                 let argument_count: usize = 3;
-                validate_argument_count(argument_count, &arguments);
+                validate_argument_count(argument_count, arguments);
 
                 let id: u8 = parse_id(&arguments[TASK_ID_ARGUMENT_INDEX]);
 
-                Command::Delete(id)
+                Command::Restore(id)
             }
-            "mark" => {
-                // User have to provide two command arguments <task_id> <state>
-                let argument_count: usize = 4;
-                validate_argument_count(argument_count, &arguments);
+            "purge" => match parse_older_than(arguments) {
+                Some(older_than) => Command::Purge(older_than),
+                None => Command::Unknown,
+            },
+            "archive" => match parse_older_than(arguments) {
+                Some(older_than) => Command::Archive(older_than),
+                None => Command::Unknown,
+            },
+            "unarchive" => {
+                // User have to provide one command argument <task_id>
+                let argument_count: usize = 3;
+                validate_argument_count(argument_count, arguments);
 
                 let id: u8 = parse_id(&arguments[TASK_ID_ARGUMENT_INDEX]);
 
-                const TASK_ARGUMENT_INDEX: usize = 3;
-                let state: String = arguments[TASK_ARGUMENT_INDEX].to_owned();
+                Command::Unarchive(id)
+            }
+            "stats" => {
+                // User can ask for JSON output with the '--json' flag, and scope the
+                // statistics with '--project <project>'
+                let (project, arguments) = take_flag("--project", arguments);
+                const FORMAT_ARGUMENT_INDEX: usize = 2;
+                match arguments.get(FORMAT_ARGUMENT_INDEX).map(String::as_str) {
+                    Some("--json") => Command::Stats((true, project)),
+                    Some(_) => Command::Unknown,
+                    None => Command::Stats((false, project)),
+                }
+            }
+            "project" => {
+                // User have to provide a subcommand: add or list
+                let argument_count: usize = 3;
+                validate_argument_count(argument_count, arguments);
+
+                const SUBCOMMAND_INDEX: usize = 2;
+                match arguments[SUBCOMMAND_INDEX].as_str() {
+                    "add" => {
+                        // User have to provide the <name>
+                        let argument_count: usize = 4;
+                        validate_argument_count(argument_count, arguments);
+
+                        const PROJECT_NAME_INDEX: usize = 3;
+                        let name: String = arguments[PROJECT_NAME_INDEX].clone();
+                        project::Project::new(name.clone())
+                            .unwrap_or_else(|error| panic!("{error}"));
+
+                        Command::ProjectAdd(name.to_lowercase())
+                    }
+                    "list" => Command::ProjectList,
+                    _ => Command::Unknown,
+                }
+            }
+            "rpc" => {
+                // User have to choose the transport, only '--stdio' for now
+                const TRANSPORT_ARGUMENT_INDEX: usize = 2;
+                match arguments.get(TRANSPORT_ARGUMENT_INDEX).map(String::as_str) {
+                    Some("--stdio") => Command::Rpc,
+                    _ => Command::Unknown,
+                }
+            }
+            "import" => {
+                // User have to provide two command arguments <format> <file>, and can
+                // map the CSV columns with '--map <mapping>' and preview with '--dry-run'
+                let dry_run: bool = arguments.iter().any(|argument| argument == "--dry-run");
+                let arguments: Vec<String> = arguments
+                    .iter()
+                    .filter(|argument| *argument != "--dry-run")
+                    .cloned()
+                    .collect();
+                let (mapping, arguments) = take_flag("--map", &arguments);
+                let argument_count: usize = 4;
+                validate_argument_count(argument_count, &arguments);
+
+                const FORMAT_ARGUMENT_INDEX: usize = 2;
+                const FILE_ARGUMENT_INDEX: usize = 3;
+                let format: ImportFormat =
+                    ImportFormat::try_from(arguments[FORMAT_ARGUMENT_INDEX].clone())
+                        .unwrap_or_else(|error| panic!("{error}"));
+                let mapping: ColumnMapping = match mapping {
+                    Some(_) if format != ImportFormat::Csv => {
+                        panic!("The '--map' flag can only be used with the csv format.")
+                    }
+                    Some(mapping) => {
+                        ColumnMapping::try_from(mapping).unwrap_or_else(|error| panic!("{error}"))
+                    }
+                    None => ColumnMapping::default(),
+                };
+                Command::Import((
+                    format,
+                    arguments[FILE_ARGUMENT_INDEX].clone(),
+                    mapping,
+                    dry_run,
+                ))
+            }
+            "export" => {
+                // User have to choose the format with '--to <format>', and can give a
+                // filter
+                let (format, arguments) = take_flag("--to", arguments);
+                let format: ExportFormat = match format {
+                    Some(format) => {
+                        ExportFormat::try_from(format).unwrap_or_else(|error| panic!("{error}"))
+                    }
+                    None => panic!(
+                        "The '--to <format>' flag is required, formats are markdown, todotxt, csv and ics."
+                    ),
+                };
+                const FILTER_ARGUMENT_INDEX: usize = 2;
+                match &arguments[FILTER_ARGUMENT_INDEX..] {
+                    [] => Command::Export((format, None)),
+                    [filter] => {
+                        let filter: Filter = filter::query::parse_filter(filter)
+                            .unwrap_or_else(|error| panic!("{error}"));
+                        Command::Export((format, Some(filter)))
+                    }
+                    _ => Command::Unknown,
+                }
+            }
+            "sync" => {
+                // User have to provide the kind of file and its <path>: markdown
+                let argument_count: usize = 4;
+                validate_argument_count(argument_count, arguments);
 
-                Command::Mark((id, state))
+                const KIND_ARGUMENT_INDEX: usize = 2;
+                const PATH_ARGUMENT_INDEX: usize = 3;
+                match arguments[KIND_ARGUMENT_INDEX].as_str() {
+                    "markdown" => Command::SyncMarkdown(arguments[PATH_ARGUMENT_INDEX].clone()),
+                    _ => Command::Unknown,
+                }
+            }
+            "token" => {
+                // User have to provide a subcommand: create, list or revoke
+                let argument_count: usize = 3;
+                validate_argument_count(argument_count, arguments);
+
+                // User can choose the scope of a new token with '--scope <scope>'
+                let (scope, arguments) = take_flag("--scope", arguments);
+                const SUBCOMMAND_INDEX: usize = 2;
+                const OWNER_ARGUMENT_INDEX: usize = 3;
+                match arguments[SUBCOMMAND_INDEX].as_str() {
+                    "create" => {
+                        // User have to provide the <owner>
+                        let argument_count: usize = 4;
+                        validate_argument_count(argument_count, &arguments);
+
+                        let scope: Scope = scope
+                            .map(|scope| {
+                                Scope::try_from(scope).unwrap_or_else(|error| panic!("{error}"))
+                            })
+                            .unwrap_or_default();
+                        Command::TokenCreate((arguments[OWNER_ARGUMENT_INDEX].clone(), scope))
+                    }
+                    "list" => Command::TokenList,
+                    "revoke" => {
+                        // User have to provide the <owner>
+                        let argument_count: usize = 4;
+                        validate_argument_count(argument_count, &arguments);
+
+                        Command::TokenRevoke(arguments[OWNER_ARGUMENT_INDEX].clone())
+                    }
+                    _ => Command::Unknown,
+                }
+            }
+            "report" => {
+                // User have to provide the report: burndown
+                let argument_count: usize = 3;
+                validate_argument_count(argument_count, arguments);
+
+                const REPORT_ARGUMENT_INDEX: usize = 2;
+                match arguments[REPORT_ARGUMENT_INDEX].as_str() {
+                    "burndown" => Command::ReportBurndown(parse_report(
+                        &arguments[REPORT_ARGUMENT_INDEX + 1..],
+                    )),
+                    _ => Command::Unknown,
+                }
             }
+            "undo" => Command::Undo,
+            "redo" => Command::Redo,
             _ => Command::Unknown,
         }
     }
 }
 
-fn validate_argument_count(argument_count: usize, arguments: &Vec<String>) {
-    const HELP_TEXT: &str = r"A simple CLI app for tracking tasks.
-    Usage: task-tracker <command> [command-arguments]
-    Commands:
-    - list <state_filter>                                       List all the tasks if state filter not provided, or list only the tasks matching the state filter (e.g.: task-tracker list done').
-    - add <task>                                                Add one task (e.g.: task-tracker add 'Buy 3 eggs.').
-    - show <task_id>                                            Show task (e.g.: task-tracker show 1).
-    - update <task_id> <updated_task>                           Update task (e.g.: task-tracker update 1 'Buy 3 eggs and 1 milk.').
-    - delete <task_id>                                          Delete task (e.g.: task-tracker delete 1).
-    - mark <task_id> ['not-started', 'in-progress', 'done']     Mark task as 'not-started', 'in-progress' or 'done' (e.g.: task-tracker mark 1 done).
-    ";
-
+fn validate_argument_count(argument_count: usize, arguments: &[String]) {
     if arguments.len() < argument_count {
         panic!("{HELP_TEXT}")
     }
@@ -123,6 +642,243 @@ fn parse_id(argument: &str) -> u8 {
         .expect("The 'id' must be a numerical value between 1-256.")
 }
 
+// Parses the optional '--older-than <duration>' flag following the command.
+// Returns None when something else than the flag was provided.
+fn parse_older_than(arguments: &[String]) -> Option<Option<u64>> {
+    const OLDER_THAN_FLAG_INDEX: usize = 2;
+    const OLDER_THAN_ARGUMENT_INDEX: usize = 3;
+    match arguments.get(OLDER_THAN_FLAG_INDEX).map(String::as_str) {
+        Some("--older-than") => {
+            let argument_count: usize = 4;
+            validate_argument_count(argument_count, arguments);
+
+            let older_than: u64 = parse_duration(&arguments[OLDER_THAN_ARGUMENT_INDEX]);
+            Some(Some(older_than))
+        }
+        Some(_) => None,
+        None => Some(None),
+    }
+}
+
+fn parse_duration(argument: &str) -> u64 {
+    date::parse_duration(argument).unwrap_or_else(|error| panic!("{error}"))
+}
+
+// Parses the tasks a bulk command applies to, given either as <task_ids>, a
+// task title or as '--where <filter>' with an optional '--dry-run' flag. The flags are
+// removed from the returned arguments, so the remaining command arguments
+// keep their usual index.
+fn parse_selection(argument_count: usize, arguments: &[String]) -> (Selection, Vec<String>) {
+    const SELECTION_ARGUMENT_INDEX: usize = 2;
+
+    let dry_run: bool = arguments.iter().any(|argument| argument == "--dry-run");
+    let mut arguments: Vec<String> = arguments
+        .iter()
+        .filter(|argument| *argument != "--dry-run")
+        .cloned()
+        .collect();
+    let by_filter: bool = arguments
+        .get(SELECTION_ARGUMENT_INDEX)
+        .is_some_and(|argument| argument == "--where");
+    if by_filter {
+        arguments.remove(SELECTION_ARGUMENT_INDEX);
+    }
+    validate_argument_count(argument_count, &arguments);
+
+    let argument: &str = &arguments[SELECTION_ARGUMENT_INDEX];
+    let selection: Selection = if by_filter {
+        let filter: Filter =
+            filter::query::parse_filter(argument).unwrap_or_else(|error| panic!("{error}"));
+        Selection::Where { filter, dry_run }
+    } else if dry_run {
+        panic!("The '--dry-run' flag can only be used together with '--where <filter>'.")
+    } else if is_id_list(argument) {
+        Selection::Ids(parse_ids(argument))
+    } else if let Some((project, number)) = project::parse_key(argument) {
+        Selection::Key(project, number)
+    } else {
+        Selection::Title(argument.to_owned())
+    };
+    (selection, arguments)
+}
+
+// Removes an optional '<flag> <value>' pair from the arguments, so the
+// remaining command arguments keep their usual index.
+fn take_flag(flag: &str, arguments: &[String]) -> (Option<String>, Vec<String>) {
+    let mut arguments: Vec<String> = arguments.to_vec();
+    let Some(index) = arguments.iter().position(|argument| argument == flag) else {
+        return (None, arguments);
+    };
+    if index + 1 >= arguments.len() {
+        panic!("The '{flag}' flag needs a value.");
+    }
+    let value: String = arguments.remove(index + 1);
+    arguments.remove(index);
+    (Some(value), arguments)
+}
+
+// Parses the filter query of the arguments, if any, restricted to the tasks
+// of the project when one is given.
+fn parse_scoped_filter(project: Option<String>, arguments: &[String]) -> Option<Filter> {
+    let filter: Option<Filter> = match arguments.is_empty() {
+        true => None,
+        false => Some(
+            filter::query::parse_filter(&arguments.join(" "))
+                .unwrap_or_else(|error| panic!("{error}")),
+        ),
+    };
+    match (project.map(Filter::Project), filter) {
+        (Some(project), Some(filter)) => Some(Filter::And(Box::new(project), Box::new(filter))),
+        (project, filter) => project.or(filter),
+    }
+}
+
+// Parses the '--sort <keys>', '--limit <count>' and '--offset <count>' flags
+// of a list. The flags are removed from the returned arguments.
+fn parse_list_options(arguments: &[String]) -> (ListOptions, Vec<String>) {
+    let mut sort: Vec<SortKey> = vec![];
+    let mut limit: Option<usize> = None;
+    let mut offset: usize = 0;
+    let mut remaining: Vec<String> = vec![];
+    let mut arguments = arguments.iter();
+    while let Some(argument) = arguments.next() {
+        match argument.as_str() {
+            "--sort" => {
+                let value: &String = arguments
+                    .next()
+                    .expect("The '--sort' flag needs a list of fields (e.g.: priority:desc,id).");
+                sort = parse_sort(value);
+            }
+            "--limit" | "--offset" => {
+                let count: usize = arguments
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or_else(|| panic!("The '{argument}' flag needs a number of tasks."));
+                match argument.as_str() {
+                    "--limit" => limit = Some(count),
+                    _ => offset = count,
+                }
+            }
+            _ => remaining.push(argument.clone()),
+        }
+    }
+    (ListOptions::new(sort, limit, offset), remaining)
+}
+
+// Parses comma separated sort keys (e.g.: 'priority:desc,created').
+fn parse_sort(argument: &str) -> Vec<SortKey> {
+    argument
+        .split(',')
+        .map(|key| SortKey::try_from(key.to_owned()))
+        .collect::<Result<Vec<SortKey>, String>>()
+        .unwrap_or_else(|error| panic!("{error}"))
+}
+
+// Parses the '--since <date>' and '--format <format>' flags of a report.
+fn parse_report(arguments: &[String]) -> (Option<u64>, ReportFormat) {
+    let mut since: Option<u64> = None;
+    let mut format: ReportFormat = ReportFormat::default();
+    let mut arguments = arguments.iter();
+    while let Some(argument) = arguments.next() {
+        let value: &String = arguments
+            .next()
+            .unwrap_or_else(|| panic!("The '{argument}' flag needs a value."));
+        match argument.as_str() {
+            "--since" => {
+                since = Some(date::parse_date(value).unwrap_or_else(|error| panic!("{error}")));
+            }
+            "--format" => {
+                format =
+                    ReportFormat::try_from(value.clone()).unwrap_or_else(|error| panic!("{error}"));
+            }
+            _ => panic!("Unknown report flag '{argument}'."),
+        }
+    }
+    (since, format)
+}
+
+// Parses the query and the optional '--columns <columns>' and '--sort <keys>'
+// flags of a view. The query is validated now, so a saved view always runs.
+fn parse_view(name: String, arguments: &[String]) -> View {
+    let mut query_parts: Vec<String> = vec![];
+    let mut columns: Vec<Column> = vec![];
+    let mut sort: Vec<SortKey> = vec![];
+    let mut arguments = arguments.iter();
+    while let Some(argument) = arguments.next() {
+        if argument == "--columns" {
+            let value: &String = arguments
+                .next()
+                .expect("The '--columns' flag needs a list of columns (e.g.: id,state,title).");
+            columns = value
+                .split(',')
+                .map(|column| Column::try_from(column.to_owned()))
+                .collect::<Result<Vec<Column>, String>>()
+                .unwrap_or_else(|error| panic!("{error}"));
+        } else if argument == "--sort" {
+            let value: &String = arguments
+                .next()
+                .expect("The '--sort' flag needs a list of fields (e.g.: priority:desc,id).");
+            sort = parse_sort(value);
+        } else {
+            query_parts.push(argument.clone());
+        }
+    }
+    let query: Option<String> = match query_parts.is_empty() {
+        true => None,
+        false => {
+            let query: String = query_parts.join(" ");
+            filter::query::parse_filter(&query).unwrap_or_else(|error| panic!("{error}"));
+            Some(query)
+        }
+    };
+    View::new(name, query, columns, sort)
+}
+
+// A task is referenced by its id, by its project key (e.g.: 'INFRA-12'), or
+// by its title otherwise.
+fn parse_reference(argument: &str) -> TaskReference {
+    match argument.parse() {
+        Ok(id) => TaskReference::Id(id),
+        Err(_) if is_id_list(argument) => TaskReference::Id(parse_id(argument)),
+        Err(_) => match project::parse_key(argument) {
+            Some((project, number)) => TaskReference::Key(project, number),
+            None => TaskReference::Title(argument.to_owned()),
+        },
+    }
+}
+
+fn is_id_list(argument: &str) -> bool {
+    !argument.is_empty()
+        && argument
+            .chars()
+            .all(|character| character.is_ascii_digit() || character == ',' || character == '-')
+}
+
+// Parses a comma separated list of ids and id ranges (e.g.: '3,5,7-12').
+// Ids are returned in the given order, without duplicates.
+fn parse_ids(argument: &str) -> Vec<u8> {
+    let mut ids: Vec<u8> = vec![];
+    for part in argument.split(',') {
+        let range: Vec<u8> = match part.split_once('-') {
+            Some((start, end)) => {
+                let start: u8 = parse_id(start);
+                let end: u8 = parse_id(end);
+                if start > end {
+                    panic!("The id range '{part}' must start with the lower id.");
+                }
+                (start..=end).collect()
+            }
+            None => vec![parse_id(part)],
+        };
+        for id in range {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+    }
+    ids
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -159,6 +915,55 @@ mod tests {
         parse_id("invalid");
     }
 
+    // parse_reference tests //
+    #[test]
+    fn test_parse_reference_id() {
+        assert_eq!(parse_reference("42"), TaskReference::Id(42));
+    }
+    #[test]
+    fn test_parse_reference_title() {
+        assert_eq!(
+            parse_reference("login bug"),
+            TaskReference::Title("login bug".to_string())
+        );
+    }
+    #[test]
+    fn test_parse_reference_project_key() {
+        assert_eq!(
+            parse_reference("INFRA-12"),
+            TaskReference::Key("infra".to_string(), 12)
+        );
+    }
+    #[test]
+    #[should_panic]
+    fn test_parse_reference_out_of_range_id() {
+        parse_reference("300");
+    }
+
+    // parse_ids tests //
+    #[test]
+    fn test_parse_ids_single() {
+        assert_eq!(parse_ids("42"), vec![42]);
+    }
+    #[test]
+    fn test_parse_ids_list_and_ranges() {
+        assert_eq!(parse_ids("3,5,7-9"), vec![3, 5, 7, 8, 9]);
+    }
+    #[test]
+    fn test_parse_ids_removes_duplicates() {
+        assert_eq!(parse_ids("3-5,4"), vec![3, 4, 5]);
+    }
+    #[test]
+    #[should_panic]
+    fn test_parse_ids_reversed_range() {
+        parse_ids("9-4");
+    }
+    #[test]
+    #[should_panic]
+    fn test_parse_ids_invalid() {
+        parse_ids("3,x");
+    }
+
     // Command::parse_from tests
     #[test]
     #[should_panic]
@@ -183,151 +988,1251 @@ mod tests {
     }
 
     // List
-    // #[test]
-    // fn test_command_parse_from_with_list_command_returns_list_variant() {
-    //     let path_argument: String = "some/path".to_string();
-    //     let command_argument: String = "list".to_string();
-
-    //     let arguments: Vec<String> = vec![path_argument, command_argument];
+    #[test]
+    fn test_command_parse_from_with_list_command_returns_list_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "list".to_string();
 
-    //     let command: Command = Command::parse_from(&arguments);
-    //     assert_eq!(command, Command::List)
-    // }
+        let arguments: Vec<String> = vec![path_argument, command_argument];
 
-    // Add
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(command, Command::List((None, ListOptions::default())))
+    }
     #[test]
-    fn test_command_parse_from_with_add_command_returns_add_variant() {
+    fn test_command_parse_from_with_list_command_and_filter_returns_list_variant() {
         let path_argument: String = "some/path".to_string();
-        let command_argument: String = "add".to_string();
-        let task_argument: String = "task".to_string();
+        let command_argument: String = "list".to_string();
+        let filter_arguments: Vec<String> = vec![
+            "state:in-progress".to_string(),
+            "or".to_string(),
+            "state:not-started".to_string(),
+        ];
 
-        let arguments: Vec<String> = vec![path_argument, command_argument, task_argument.clone()];
+        let mut arguments: Vec<String> = vec![path_argument, command_argument];
+        arguments.extend(filter_arguments.clone());
 
         let command: Command = Command::parse_from(&arguments);
-        assert_eq!(command, Command::Add(task_argument))
+        let filter: Filter = filter::query::parse_filter(&filter_arguments.join(" ")).unwrap();
+        assert_eq!(
+            command,
+            Command::List((Some(filter), ListOptions::default()))
+        )
     }
     #[test]
     #[should_panic]
-    fn test_command_parse_from_with_add_command_without_and_invalid_argument_count_should_panic() {
+    fn test_command_parse_from_with_list_command_and_invalid_filter_should_panic() {
         let path_argument: String = "some/path".to_string();
-        let command_argument: String = "add".to_string();
+        let command_argument: String = "list".to_string();
+        let filter_argument: String = "state:finished".to_string();
 
-        let arguments: Vec<String> = vec![path_argument, command_argument];
+        let arguments: Vec<String> = vec![path_argument, command_argument, filter_argument];
 
         Command::parse_from(&arguments);
     }
 
-    // Show
     #[test]
-    fn test_command_parse_from_with_show_command_returns_show_variant() {
+    fn test_command_parse_from_with_list_command_and_archived_flag_returns_list_archived_variant() {
         let path_argument: String = "some/path".to_string();
-        let command_argument: String = "show".to_string();
-        let task_id_argument: String = "1".to_string();
+        let command_argument: String = "list".to_string();
+        let flag_argument: String = "--archived".to_string();
 
-        let arguments: Vec<String> =
-            vec![path_argument, command_argument, task_id_argument.clone()];
+        let arguments: Vec<String> = vec![path_argument, command_argument, flag_argument];
 
         let command: Command = Command::parse_from(&arguments);
-        assert_eq!(command, Command::Show(parse_id(&task_id_argument)))
+        assert_eq!(command, Command::ListArchived(ListOptions::default()))
     }
+
     #[test]
-    #[should_panic]
-    fn test_command_parse_from_with_show_command_and_invalid_argument_count_should_panic() {
+    fn test_command_parse_from_with_list_command_and_view_returns_list_view_variant() {
         let path_argument: String = "some/path".to_string();
-        let command_argument: String = "show".to_string();
+        let command_argument: String = "list".to_string();
+        let view_argument: String = "@standup".to_string();
 
-        let arguments: Vec<String> = vec![path_argument, command_argument];
+        let arguments: Vec<String> = vec![path_argument, command_argument, view_argument];
 
-        Command::parse_from(&arguments);
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(
+            command,
+            Command::ListView(("standup".to_string(), ListOptions::default()))
+        )
     }
-
-    // Update
     #[test]
-    fn test_command_parse_from_with_update_command_returns_update_variant() {
+    fn test_command_parse_from_with_list_command_and_sort_and_page_flags_returns_list_variant() {
         let path_argument: String = "some/path".to_string();
-        let command_argument: String = "update".to_string();
-        let task_id_argument: String = "1".to_string();
-        let updated_task_argument: String = "updated task".to_string();
+        let command_argument: String = "list".to_string();
+        let sort_flag_argument: String = "--sort".to_string();
+        let sort_argument: String = "priority:desc,created".to_string();
+        let filter_argument: String = "tag:team".to_string();
+        let limit_flag_argument: String = "--limit".to_string();
+        let limit_argument: String = "10".to_string();
+        let offset_flag_argument: String = "--offset".to_string();
+        let offset_argument: String = "20".to_string();
 
         let arguments: Vec<String> = vec![
             path_argument,
             command_argument,
-            task_id_argument.clone(),
-            updated_task_argument.clone(),
+            sort_flag_argument,
+            sort_argument,
+            filter_argument,
+            limit_flag_argument,
+            limit_argument,
+            offset_flag_argument,
+            offset_argument,
         ];
 
         let command: Command = Command::parse_from(&arguments);
+        let options: ListOptions = ListOptions::new(
+            vec![
+                SortKey::new(Column::Priority, true),
+                SortKey::new(Column::Created, false),
+            ],
+            Some(10),
+            20,
+        );
         assert_eq!(
             command,
-            Command::Update((parse_id(&task_id_argument), updated_task_argument))
+            Command::List((Some(Filter::Tag("team".to_string())), options))
         )
     }
     #[test]
     #[should_panic]
-    fn test_command_parse_from_with_update_command_and_invalid_argument_count_should_panic() {
+    fn test_command_parse_from_with_list_command_and_invalid_limit_should_panic() {
         let path_argument: String = "some/path".to_string();
-        let command_argument: String = "update".to_string();
-        let updated_task_argument: String = "updated task".to_string();
+        let command_argument: String = "list".to_string();
+        let limit_flag_argument: String = "--limit".to_string();
+        let limit_argument: String = "many".to_string();
 
-        let arguments: Vec<String> = vec![path_argument, command_argument, updated_task_argument];
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            limit_flag_argument,
+            limit_argument,
+        ];
 
         Command::parse_from(&arguments);
     }
 
-    // Delete
+    // Board
     #[test]
-    fn test_command_parse_from_with_delete_command_returns_delete_variant() {
+    fn test_command_parse_from_with_board_command_returns_board_variant() {
         let path_argument: String = "some/path".to_string();
-        let command_argument: String = "delete".to_string();
-        let task_id_argument: String = "1".to_string();
+        let command_argument: String = "board".to_string();
 
-        let arguments: Vec<String> =
-            vec![path_argument, command_argument, task_id_argument.clone()];
+        let arguments: Vec<String> = vec![path_argument, command_argument];
 
         let command: Command = Command::parse_from(&arguments);
-        assert_eq!(command, Command::Delete(parse_id(&task_id_argument)))
+        assert_eq!(command, Command::Board(None))
     }
     #[test]
-    #[should_panic]
-    fn test_command_parse_from_with_delete_command_and_invalid_argument_count_should_panic() {
+    fn test_command_parse_from_with_board_command_and_project_returns_board_variant() {
         let path_argument: String = "some/path".to_string();
-        let command_argument: String = "delete".to_string();
+        let command_argument: String = "board".to_string();
+        let flag_argument: String = "--project".to_string();
+        let project_argument: String = "infra".to_string();
 
-        let arguments: Vec<String> = vec![path_argument, command_argument];
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            flag_argument,
+            project_argument.clone(),
+        ];
 
-        Command::parse_from(&arguments);
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(
+            command,
+            Command::Board(Some(Filter::Project(project_argument)))
+        )
     }
 
-    // Mark
+    // View
     #[test]
-    fn test_command_parse_from_with_mark_command_returns_mark_variant() {
+    fn test_command_parse_from_with_view_save_command_returns_view_save_variant() {
         let path_argument: String = "some/path".to_string();
-        let command_argument: String = "mark".to_string();
-        let task_id_argument: String = "1".to_string();
-        let state_argument: String = "done".to_string();
+        let command_argument: String = "view".to_string();
+        let subcommand_argument: String = "save".to_string();
+        let name_argument: String = "standup".to_string();
+        let query_argument: String = "state:in-progress tag:team".to_string();
+        let columns_flag_argument: String = "--columns".to_string();
+        let columns_argument: String = "id,title".to_string();
 
         let arguments: Vec<String> = vec![
             path_argument,
             command_argument,
-            task_id_argument.clone(),
-            state_argument.clone(),
+            subcommand_argument,
+            name_argument.clone(),
+            query_argument.clone(),
+            columns_flag_argument,
+            columns_argument,
         ];
 
         let command: Command = Command::parse_from(&arguments);
         assert_eq!(
             command,
-            Command::Mark((parse_id(&task_id_argument), state_argument))
+            Command::ViewSave(View::new(
+                name_argument,
+                Some(query_argument),
+                vec![Column::Id, Column::Title],
+                vec![]
+            ))
         )
     }
     #[test]
     #[should_panic]
-    fn test_command_parse_from_with_mark_command_and_invalid_argument_count_should_panic() {
+    fn test_command_parse_from_with_view_save_command_and_invalid_query_should_panic() {
         let path_argument: String = "some/path".to_string();
-        let command_argument: String = "mark".to_string();
-        let state_argument: String = "done".to_string();
+        let command_argument: String = "view".to_string();
+        let subcommand_argument: String = "save".to_string();
+        let name_argument: String = "standup".to_string();
+        let query_argument: String = "state:bogus".to_string();
 
-        let arguments: Vec<String> = vec![path_argument, command_argument, state_argument];
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            subcommand_argument,
+            name_argument,
+            query_argument,
+        ];
 
         Command::parse_from(&arguments);
     }
+    #[test]
+    fn test_command_parse_from_with_view_list_command_returns_view_list_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "view".to_string();
+        let subcommand_argument: String = "list".to_string();
+
+        let arguments: Vec<String> = vec![path_argument, command_argument, subcommand_argument];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(command, Command::ViewList)
+    }
+
+    // Search
+    #[test]
+    fn test_command_parse_from_with_search_command_returns_search_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "search".to_string();
+        let first_term_argument: String = "login".to_string();
+        let second_term_argument: String = "bug*".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            first_term_argument,
+            second_term_argument,
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(
+            command,
+            Command::Search(vec![
+                SearchTerm::Word("login".to_string()),
+                SearchTerm::Prefix("bug".to_string())
+            ])
+        )
+    }
+    #[test]
+    #[should_panic]
+    fn test_command_parse_from_with_search_command_and_invalid_argument_count_should_panic() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "search".to_string();
+
+        let arguments: Vec<String> = vec![path_argument, command_argument];
+
+        Command::parse_from(&arguments);
+    }
+
+    // Add
+    #[test]
+    fn test_command_parse_from_with_add_command_returns_add_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "add".to_string();
+        let task_argument: String = "task".to_string();
+
+        let arguments: Vec<String> = vec![path_argument, command_argument, task_argument.clone()];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(command, Command::Add((task_argument, None, None)))
+    }
+    #[test]
+    fn test_command_parse_from_with_add_command_and_milestone_returns_add_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "add".to_string();
+        let flag_argument: String = "--milestone".to_string();
+        let milestone_argument: String = "v1.2".to_string();
+        let task_argument: String = "task".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            flag_argument,
+            milestone_argument.clone(),
+            task_argument.clone(),
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(
+            command,
+            Command::Add((task_argument, Some(milestone_argument), None))
+        )
+    }
+    #[test]
+    #[should_panic]
+    fn test_command_parse_from_with_add_command_without_and_invalid_argument_count_should_panic() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "add".to_string();
+
+        let arguments: Vec<String> = vec![path_argument, command_argument];
+
+        Command::parse_from(&arguments);
+    }
+
+    // Show
+    #[test]
+    fn test_command_parse_from_with_show_command_returns_show_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "show".to_string();
+        let task_id_argument: String = "1".to_string();
+
+        let arguments: Vec<String> =
+            vec![path_argument, command_argument, task_id_argument.clone()];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(command, Command::Show(parse_reference(&task_id_argument)))
+    }
+    #[test]
+    #[should_panic]
+    fn test_command_parse_from_with_show_command_and_invalid_argument_count_should_panic() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "show".to_string();
+
+        let arguments: Vec<String> = vec![path_argument, command_argument];
+
+        Command::parse_from(&arguments);
+    }
+
+    // Update
+    #[test]
+    fn test_command_parse_from_with_update_command_returns_update_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "update".to_string();
+        let task_id_argument: String = "1".to_string();
+        let updated_task_argument: String = "updated task".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            task_id_argument.clone(),
+            updated_task_argument.clone(),
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(
+            command,
+            Command::Update((parse_reference(&task_id_argument), updated_task_argument))
+        )
+    }
+    #[test]
+    #[should_panic]
+    fn test_command_parse_from_with_update_command_and_invalid_argument_count_should_panic() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "update".to_string();
+        let updated_task_argument: String = "updated task".to_string();
+
+        let arguments: Vec<String> = vec![path_argument, command_argument, updated_task_argument];
+
+        Command::parse_from(&arguments);
+    }
+
+    // Delete
+    #[test]
+    fn test_command_parse_from_with_delete_command_returns_delete_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "delete".to_string();
+        let task_id_argument: String = "1".to_string();
+
+        let arguments: Vec<String> =
+            vec![path_argument, command_argument, task_id_argument.clone()];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(
+            command,
+            Command::Delete(Selection::Ids(parse_ids(&task_id_argument)))
+        )
+    }
+    #[test]
+    fn test_command_parse_from_with_delete_command_and_where_filter_dry_run_returns_delete_variant()
+    {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "delete".to_string();
+        let where_argument: String = "--where".to_string();
+        let filter_argument: String = "state:done".to_string();
+        let dry_run_argument: String = "--dry-run".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            where_argument,
+            filter_argument.clone(),
+            dry_run_argument,
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        let filter: Filter = filter::query::parse_filter(&filter_argument).unwrap();
+        assert_eq!(
+            command,
+            Command::Delete(Selection::Where {
+                filter,
+                dry_run: true
+            })
+        )
+    }
+    #[test]
+    #[should_panic]
+    fn test_command_parse_from_with_delete_command_and_invalid_argument_count_should_panic() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "delete".to_string();
+
+        let arguments: Vec<String> = vec![path_argument, command_argument];
+
+        Command::parse_from(&arguments);
+    }
+
+    // Mark
+    #[test]
+    fn test_command_parse_from_with_mark_command_returns_mark_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "mark".to_string();
+        let task_id_argument: String = "1".to_string();
+        let state_argument: String = "done".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            task_id_argument.clone(),
+            state_argument.clone(),
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(
+            command,
+            Command::Mark((
+                Selection::Ids(parse_ids(&task_id_argument)),
+                TaskState::Done
+            ))
+        )
+    }
+    #[test]
+    #[should_panic]
+    fn test_command_parse_from_with_mark_command_and_invalid_argument_count_should_panic() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "mark".to_string();
+        let state_argument: String = "done".to_string();
+
+        let arguments: Vec<String> = vec![path_argument, command_argument, state_argument];
+
+        Command::parse_from(&arguments);
+    }
+    #[test]
+    #[should_panic(expected = "Unknown task state 'finished'")]
+    fn test_command_parse_from_with_mark_command_and_invalid_state_should_panic() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "mark".to_string();
+        let task_id_argument: String = "1-5".to_string();
+        let state_argument: String = "finished".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            task_id_argument,
+            state_argument,
+        ];
+
+        Command::parse_from(&arguments);
+    }
+
+    // Undo
+    #[test]
+    fn test_command_parse_from_with_undo_command_returns_undo_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "undo".to_string();
+
+        let arguments: Vec<String> = vec![path_argument, command_argument];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(command, Command::Undo)
+    }
+
+    // Redo
+    #[test]
+    fn test_command_parse_from_with_redo_command_returns_redo_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "redo".to_string();
+
+        let arguments: Vec<String> = vec![path_argument, command_argument];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(command, Command::Redo)
+    }
+
+    #[test]
+    fn test_command_parse_from_with_mark_command_and_where_filter_returns_mark_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "mark".to_string();
+        let where_argument: String = "--where".to_string();
+        let filter_argument: String = "state:in-progress tag:sprint-12".to_string();
+        let state_argument: String = "done".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            where_argument,
+            filter_argument.clone(),
+            state_argument.clone(),
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        let filter: Filter = filter::query::parse_filter(&filter_argument).unwrap();
+        assert_eq!(
+            command,
+            Command::Mark((
+                Selection::Where {
+                    filter,
+                    dry_run: false
+                },
+                TaskState::Done
+            ))
+        )
+    }
+    #[test]
+    #[should_panic]
+    fn test_command_parse_from_with_mark_command_and_dry_run_without_where_should_panic() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "mark".to_string();
+        let task_id_argument: String = "1".to_string();
+        let dry_run_argument: String = "--dry-run".to_string();
+        let state_argument: String = "done".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            task_id_argument,
+            dry_run_argument,
+            state_argument,
+        ];
+
+        Command::parse_from(&arguments);
+    }
+
+    // Priority
+    #[test]
+    fn test_command_parse_from_with_priority_command_returns_priority_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "priority".to_string();
+        let task_id_argument: String = "1,2".to_string();
+        let priority_argument: String = "high".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            task_id_argument.clone(),
+            priority_argument,
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(
+            command,
+            Command::Priority((Selection::Ids(parse_ids(&task_id_argument)), Priority::High))
+        )
+    }
+    #[test]
+    #[should_panic]
+    fn test_command_parse_from_with_priority_command_and_invalid_priority_should_panic() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "priority".to_string();
+        let task_id_argument: String = "1".to_string();
+        let priority_argument: String = "urgent".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            task_id_argument,
+            priority_argument,
+        ];
+
+        Command::parse_from(&arguments);
+    }
+
+    #[test]
+    fn test_command_parse_from_with_mark_command_and_title_returns_mark_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "mark".to_string();
+        let title_argument: String = "login bug".to_string();
+        let state_argument: String = "done".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            title_argument.clone(),
+            state_argument.clone(),
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(
+            command,
+            Command::Mark((Selection::Title(title_argument), TaskState::Done))
+        )
+    }
+
+    // Tag
+    #[test]
+    fn test_command_parse_from_with_tag_command_returns_tag_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "tag".to_string();
+        let task_id_argument: String = "1-3".to_string();
+        let tag_argument: String = "sprint-12".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            task_id_argument.clone(),
+            tag_argument.clone(),
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(
+            command,
+            Command::Tag((Selection::Ids(parse_ids(&task_id_argument)), tag_argument))
+        )
+    }
+    #[test]
+    #[should_panic]
+    fn test_command_parse_from_with_tag_command_and_invalid_argument_count_should_panic() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "tag".to_string();
+        let tag_argument: String = "sprint-12".to_string();
+
+        let arguments: Vec<String> = vec![path_argument, command_argument, tag_argument];
+
+        Command::parse_from(&arguments);
+    }
+
+    // Untag
+    #[test]
+    fn test_command_parse_from_with_untag_command_returns_untag_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "untag".to_string();
+        let task_id_argument: String = "1".to_string();
+        let tag_argument: String = "sprint-12".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            task_id_argument.clone(),
+            tag_argument.clone(),
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(
+            command,
+            Command::Untag((Selection::Ids(parse_ids(&task_id_argument)), tag_argument))
+        )
+    }
+
+    // Note
+    #[test]
+    fn test_command_parse_from_with_note_command_returns_note_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "note".to_string();
+        let task_id_argument: String = "1".to_string();
+        let note_argument: String = "a note".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            task_id_argument.clone(),
+            note_argument.clone(),
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(
+            command,
+            Command::Note((parse_reference(&task_id_argument), note_argument))
+        )
+    }
+
+    // Trash
+    #[test]
+    fn test_command_parse_from_with_trash_command_returns_trash_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "trash".to_string();
+
+        let arguments: Vec<String> = vec![path_argument, command_argument];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(command, Command::Trash)
+    }
+
+    // Milestone
+    #[test]
+    fn test_command_parse_from_with_milestone_add_command_returns_milestone_add_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "milestone".to_string();
+        let subcommand_argument: String = "add".to_string();
+        let name_argument: String = "v1.2".to_string();
+        let date_argument: String = "2026-12-01".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            subcommand_argument,
+            name_argument.clone(),
+            date_argument,
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        let target_date: u64 = date::parse_date("2026-12-01").unwrap();
+        assert_eq!(
+            command,
+            Command::MilestoneAdd(Milestone::new(name_argument, target_date, String::new()))
+        )
+    }
+    #[test]
+    fn test_command_parse_from_with_plan_command_returns_plan_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "plan".to_string();
+        let task_ids_argument: String = "3,5".to_string();
+        let milestone_argument: String = "v1.2".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            task_ids_argument,
+            milestone_argument.clone(),
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(
+            command,
+            Command::Plan((Selection::Ids(vec![3, 5]), milestone_argument))
+        )
+    }
+    #[test]
+    fn test_command_parse_from_with_roadmap_command_returns_roadmap_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "roadmap".to_string();
+
+        let arguments: Vec<String> = vec![path_argument, command_argument];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(command, Command::Roadmap)
+    }
+
+    // Tui
+    #[test]
+    fn test_command_parse_from_with_tui_command_returns_tui_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "tui".to_string();
+
+        let arguments: Vec<String> = vec![path_argument, command_argument];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(command, Command::Tui)
+    }
+
+    // Shell
+    #[test]
+    fn test_command_parse_from_with_shell_command_returns_shell_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "shell".to_string();
+
+        let arguments: Vec<String> = vec![path_argument, command_argument];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(command, Command::Shell)
+    }
+
+    // Token
+    #[test]
+    fn test_command_parse_from_with_token_create_command_returns_token_create_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "token".to_string();
+        let subcommand_argument: String = "create".to_string();
+        let owner_argument: String = "alice".to_string();
+        let flag_argument: String = "--scope".to_string();
+        let scope_argument: String = "read-write".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            subcommand_argument,
+            owner_argument,
+            flag_argument,
+            scope_argument,
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(
+            command,
+            Command::TokenCreate(("alice".to_string(), Scope::ReadWrite))
+        )
+    }
+    #[test]
+    fn test_command_parse_from_with_token_create_command_defaults_to_read_scope() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "token".to_string();
+        let subcommand_argument: String = "create".to_string();
+        let owner_argument: String = "bob".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            subcommand_argument,
+            owner_argument,
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(
+            command,
+            Command::TokenCreate(("bob".to_string(), Scope::Read))
+        )
+    }
+    #[test]
+    fn test_command_parse_from_with_token_revoke_command_returns_token_revoke_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "token".to_string();
+        let subcommand_argument: String = "revoke".to_string();
+        let owner_argument: String = "alice".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            subcommand_argument,
+            owner_argument,
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(command, Command::TokenRevoke("alice".to_string()))
+    }
+
+    // Import
+    #[test]
+    fn test_command_parse_from_with_import_command_returns_import_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "import".to_string();
+        let format_argument: String = "csv".to_string();
+        let file_argument: String = "issues.csv".to_string();
+        let map_flag_argument: String = "--map".to_string();
+        let map_argument: String = "title=Summary".to_string();
+        let dry_run_argument: String = "--dry-run".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            format_argument,
+            dry_run_argument,
+            file_argument.clone(),
+            map_flag_argument,
+            map_argument.clone(),
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(
+            command,
+            Command::Import((
+                ImportFormat::Csv,
+                file_argument,
+                ColumnMapping::try_from(map_argument).unwrap(),
+                true
+            ))
+        )
+    }
+    #[test]
+    #[should_panic]
+    fn test_command_parse_from_with_import_command_and_map_for_todotxt_should_panic() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "import".to_string();
+        let format_argument: String = "todotxt".to_string();
+        let file_argument: String = "todo.txt".to_string();
+        let map_flag_argument: String = "--map".to_string();
+        let map_argument: String = "title=Summary".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            format_argument,
+            file_argument,
+            map_flag_argument,
+            map_argument,
+        ];
+
+        Command::parse_from(&arguments);
+    }
+
+    // Export
+    #[test]
+    fn test_command_parse_from_with_export_command_returns_export_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "export".to_string();
+        let to_flag_argument: String = "--to".to_string();
+        let format_argument: String = "ics".to_string();
+        let filter_argument: String = "tag:team".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            filter_argument,
+            to_flag_argument,
+            format_argument,
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(
+            command,
+            Command::Export((ExportFormat::Ics, Some(Filter::Tag("team".to_string()))))
+        )
+    }
+    #[test]
+    #[should_panic]
+    fn test_command_parse_from_with_export_command_without_format_should_panic() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "export".to_string();
+
+        let arguments: Vec<String> = vec![path_argument, command_argument];
+
+        Command::parse_from(&arguments);
+    }
+
+    // Sync
+    #[test]
+    fn test_command_parse_from_with_sync_markdown_command_returns_sync_markdown_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "sync".to_string();
+        let kind_argument: String = "markdown".to_string();
+        let file_argument: String = "TODO.md".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            kind_argument,
+            file_argument.clone(),
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(command, Command::SyncMarkdown(file_argument))
+    }
+    #[test]
+    fn test_command_parse_from_with_sync_command_and_unknown_kind_returns_unknown_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "sync".to_string();
+        let kind_argument: String = "notion".to_string();
+        let file_argument: String = "TODO.md".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            kind_argument,
+            file_argument,
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(command, Command::Unknown)
+    }
+
+    // Rpc
+    #[test]
+    fn test_command_parse_from_with_rpc_stdio_command_returns_rpc_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "rpc".to_string();
+        let transport_argument: String = "--stdio".to_string();
+
+        let arguments: Vec<String> = vec![path_argument, command_argument, transport_argument];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(command, Command::Rpc)
+    }
+    #[test]
+    fn test_command_parse_from_with_rpc_command_without_transport_returns_unknown_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "rpc".to_string();
+
+        let arguments: Vec<String> = vec![path_argument, command_argument];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(command, Command::Unknown)
+    }
+
+    // Serve
+    #[test]
+    fn test_command_parse_from_with_serve_command_returns_serve_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "serve".to_string();
+
+        let arguments: Vec<String> = vec![path_argument, command_argument];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(command, Command::Serve("127.0.0.1:8080".to_string()))
+    }
+    #[test]
+    fn test_command_parse_from_with_serve_command_and_bind_returns_serve_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "serve".to_string();
+        let flag_argument: String = "--bind".to_string();
+        let bind_argument: String = "0.0.0.0:9000".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            flag_argument,
+            bind_argument,
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(command, Command::Serve("0.0.0.0:9000".to_string()))
+    }
+
+    // Project
+    #[test]
+    fn test_command_parse_from_with_project_add_command_returns_project_add_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "project".to_string();
+        let subcommand_argument: String = "add".to_string();
+        let name_argument: String = "Infra".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            subcommand_argument,
+            name_argument,
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(command, Command::ProjectAdd("infra".to_string()))
+    }
+    #[test]
+    fn test_command_parse_from_with_list_command_and_project_returns_scoped_list_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "list".to_string();
+        let flag_argument: String = "--project".to_string();
+        let project_argument: String = "infra".to_string();
+        let filter_argument: String = "done".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            flag_argument,
+            project_argument.clone(),
+            filter_argument,
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        let filter: Filter = Filter::And(
+            Box::new(Filter::Project(project_argument)),
+            Box::new(Filter::State(crate::model::TaskState::Done)),
+        );
+        assert_eq!(
+            command,
+            Command::List((Some(filter), ListOptions::default()))
+        )
+    }
+    #[test]
+    fn test_command_parse_from_with_mark_command_and_project_key_returns_mark_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "mark".to_string();
+        let key_argument: String = "INFRA-3".to_string();
+        let state_argument: String = "done".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            key_argument,
+            state_argument.clone(),
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(
+            command,
+            Command::Mark((Selection::Key("infra".to_string(), 3), TaskState::Done))
+        )
+    }
+
+    // Stats
+    #[test]
+    fn test_command_parse_from_with_stats_command_returns_stats_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "stats".to_string();
+
+        let arguments: Vec<String> = vec![path_argument, command_argument];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(command, Command::Stats((false, None)))
+    }
+    #[test]
+    fn test_command_parse_from_with_stats_command_and_json_flag_returns_stats_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "stats".to_string();
+        let flag_argument: String = "--json".to_string();
+
+        let arguments: Vec<String> = vec![path_argument, command_argument, flag_argument];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(command, Command::Stats((true, None)))
+    }
+
+    // Report
+    #[test]
+    fn test_command_parse_from_with_report_burndown_command_returns_report_burndown_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "report".to_string();
+        let report_argument: String = "burndown".to_string();
+        let since_flag_argument: String = "--since".to_string();
+        let since_argument: String = "2026-10-01".to_string();
+        let format_flag_argument: String = "--format".to_string();
+        let format_argument: String = "csv".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            report_argument,
+            since_flag_argument,
+            since_argument,
+            format_flag_argument,
+            format_argument,
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        let since: u64 = date::parse_date("2026-10-01").unwrap();
+        assert_eq!(
+            command,
+            Command::ReportBurndown((Some(since), ReportFormat::Csv))
+        )
+    }
+    #[test]
+    #[should_panic]
+    fn test_command_parse_from_with_report_burndown_command_and_invalid_date_should_panic() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "report".to_string();
+        let report_argument: String = "burndown".to_string();
+        let since_flag_argument: String = "--since".to_string();
+        let since_argument: String = "yesterday".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            report_argument,
+            since_flag_argument,
+            since_argument,
+        ];
+
+        Command::parse_from(&arguments);
+    }
+
+    // Restore
+    #[test]
+    fn test_command_parse_from_with_restore_command_returns_restore_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "restore".to_string();
+        let task_id_argument: String = "1".to_string();
+
+        let arguments: Vec<String> =
+            vec![path_argument, command_argument, task_id_argument.clone()];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(command, Command::Restore(parse_id(&task_id_argument)))
+    }
+    #[test]
+    #[should_panic]
+    fn test_command_parse_from_with_restore_command_and_invalid_argument_count_should_panic() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "restore".to_string();
+
+        let arguments: Vec<String> = vec![path_argument, command_argument];
+
+        Command::parse_from(&arguments);
+    }
+
+    // Purge
+    #[test]
+    fn test_command_parse_from_with_purge_command_returns_purge_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "purge".to_string();
+
+        let arguments: Vec<String> = vec![path_argument, command_argument];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(command, Command::Purge(None))
+    }
+    #[test]
+    fn test_command_parse_from_with_purge_command_and_older_than_returns_purge_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "purge".to_string();
+        let flag_argument: String = "--older-than".to_string();
+        let duration_argument: String = "30d".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            flag_argument,
+            duration_argument.clone(),
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(
+            command,
+            Command::Purge(Some(parse_duration(&duration_argument)))
+        )
+    }
+    #[test]
+    #[should_panic]
+    fn test_command_parse_from_with_purge_command_and_invalid_duration_should_panic() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "purge".to_string();
+        let flag_argument: String = "--older-than".to_string();
+        let duration_argument: String = "soon".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            flag_argument,
+            duration_argument,
+        ];
+
+        Command::parse_from(&arguments);
+    }
+
+    // Archive
+    #[test]
+    fn test_command_parse_from_with_archive_command_returns_archive_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "archive".to_string();
+        let flag_argument: String = "--older-than".to_string();
+        let duration_argument: String = "14d".to_string();
+
+        let arguments: Vec<String> = vec![
+            path_argument,
+            command_argument,
+            flag_argument,
+            duration_argument.clone(),
+        ];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(
+            command,
+            Command::Archive(Some(parse_duration(&duration_argument)))
+        )
+    }
+
+    // Unarchive
+    #[test]
+    fn test_command_parse_from_with_unarchive_command_returns_unarchive_variant() {
+        let path_argument: String = "some/path".to_string();
+        let command_argument: String = "unarchive".to_string();
+        let task_id_argument: String = "1".to_string();
+
+        let arguments: Vec<String> =
+            vec![path_argument, command_argument, task_id_argument.clone()];
+
+        let command: Command = Command::parse_from(&arguments);
+        assert_eq!(command, Command::Unarchive(parse_id(&task_id_argument)))
+    }
 }
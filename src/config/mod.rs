@@ -0,0 +1,117 @@
+use crate::model::TaskState;
+
+use json::JsonValue;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_UNDO_DEPTH: usize = 10;
+const DEFAULT_CONFIRM_THRESHOLD: usize = 10;
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    undo_depth: usize,
+    // Bulk commands selecting more tasks than this ask for confirmation.
+    confirm_threshold: usize,
+    // The most tasks each board column should hold, e.g. '"wip_limits":
+    // {"in-progress": 3}'. States without a limit are not limited.
+    wip_limits: Vec<(TaskState, usize)>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            undo_depth: DEFAULT_UNDO_DEPTH,
+            confirm_threshold: DEFAULT_CONFIRM_THRESHOLD,
+            wip_limits: vec![],
+        }
+    }
+}
+
+impl Config {
+    pub fn load() -> Self {
+        let file_path: PathBuf = Config::get_file_path();
+        if file_path.exists() {
+            let source: String = fs::read_to_string(file_path).unwrap();
+            let json_object: JsonValue =
+                json::parse(&source).expect("Can not parse the config file");
+            Config::from(&json_object)
+        } else {
+            Config::default()
+        }
+    }
+
+    pub fn get_undo_depth(&self) -> usize {
+        self.undo_depth
+    }
+
+    pub fn get_confirm_threshold(&self) -> usize {
+        self.confirm_threshold
+    }
+
+    pub fn get_wip_limit(&self, state: &TaskState) -> Option<usize> {
+        self.wip_limits
+            .iter()
+            .find(|(limited_state, _)| limited_state == state)
+            .map(|(_, limit)| *limit)
+    }
+
+    fn get_file_path() -> PathBuf {
+        let home: PathBuf = match env::home_dir() {
+            Some(home_path) => home_path,
+            None => panic!("Can not read the home path"),
+        };
+        let path: &Path = Path::new("tasks.config.json");
+        home.join(path)
+    }
+}
+
+impl From<&JsonValue> for Config {
+    fn from(json_object: &JsonValue) -> Self {
+        let mut config: Config = Config::default();
+        if let Some(undo_depth) = json_object["undo_depth"].as_usize() {
+            config.undo_depth = undo_depth;
+        }
+        if let Some(confirm_threshold) = json_object["confirm_threshold"].as_usize() {
+            config.confirm_threshold = confirm_threshold;
+        }
+        for (name, limit) in json_object["wip_limits"].entries() {
+            let state: TaskState = TaskState::try_from(name.to_owned())
+                .unwrap_or_else(|_| panic!("Unknown task state '{name}' in the WIP limits"));
+            let limit: usize = limit
+                .as_usize()
+                .unwrap_or_else(|| panic!("The WIP limit of '{name}' must be a number"));
+            config.wip_limits.push((state, limit));
+        }
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Parse Config from an empty object falls back to the defaults
+    #[test]
+    fn test_parse_config_from_empty_object_uses_defaults() {
+        let config: Config = Config::from(&json::object! {});
+        assert_eq!(config.get_undo_depth(), DEFAULT_UNDO_DEPTH);
+        assert_eq!(config.get_confirm_threshold(), DEFAULT_CONFIRM_THRESHOLD);
+    }
+
+    // Parse Config with undo_depth
+    #[test]
+    fn test_parse_config_with_undo_depth() {
+        let config: Config = Config::from(&json::object! { "undo_depth" => 3 });
+        assert_eq!(config.get_undo_depth(), 3);
+    }
+
+    // Parse Config with wip_limits
+    #[test]
+    fn test_parse_config_with_wip_limits() {
+        let config: Config =
+            Config::from(&json::object! { "wip_limits" => json::object! { "in-progress" => 3 } });
+        assert_eq!(config.get_wip_limit(&TaskState::InProgress), Some(3));
+        assert_eq!(config.get_wip_limit(&TaskState::Done), None);
+    }
+}
@@ -0,0 +1,97 @@
+// Reads CSV text into records of fields, as written by spreadsheets: fields
+// are separated by commas, and double quoted fields may hold commas, line
+// breaks and doubled quotes. Blank lines are skipped.
+pub fn parse_records(source: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records: Vec<Vec<String>> = vec![];
+    let mut record: Vec<String> = vec![];
+    let mut field: String = String::new();
+    let mut quoted: bool = false;
+    let mut line: usize = 1;
+    let mut characters = source.chars().peekable();
+    while let Some(character) = characters.next() {
+        match (quoted, character) {
+            (true, '"') if characters.peek() == Some(&'"') => {
+                characters.next();
+                field.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, _) => {
+                if character == '\n' {
+                    line += 1;
+                }
+                field.push(character);
+            }
+            (false, '"') if field.is_empty() => quoted = true,
+            (false, ',') => record.push(std::mem::take(&mut field)),
+            (false, '\r') if characters.peek() == Some(&'\n') => {}
+            (false, '\n') => {
+                line += 1;
+                record.push(std::mem::take(&mut field));
+                if record.iter().any(|field| !field.is_empty()) {
+                    records.push(std::mem::take(&mut record));
+                }
+                record.clear();
+            }
+            (false, _) => field.push(character),
+        }
+    }
+    if quoted {
+        return Err(format!("The CSV has an unclosed quote on line {line}"));
+    }
+    record.push(field);
+    if record.iter().any(|field| !field.is_empty()) {
+        records.push(record);
+    }
+    Ok(records)
+}
+
+// Writes a record as a CSV line, quoting the fields that need it.
+pub fn format_record(fields: &[String]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_owned()
+            }
+        })
+        .collect();
+    fields.join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Parse quoted fields with commas, quotes and line breaks
+    #[test]
+    fn test_parse_records_with_quotes() {
+        let source: &str = "title,tags\r\n\"Buy eggs, milk\",\"a \"\"b\"\"\"\n\n\"Two\nlines\",\n";
+
+        assert_eq!(
+            parse_records(source),
+            Ok(vec![
+                vec!["title".to_string(), "tags".to_string()],
+                vec!["Buy eggs, milk".to_string(), "a \"b\"".to_string()],
+                vec!["Two\nlines".to_string(), String::new()],
+            ])
+        );
+        assert!(parse_records("title\n\"Buy eggs").is_err());
+    }
+
+    // Quote the fields holding separators, and read them back
+    #[test]
+    fn test_format_record_round_trip() {
+        let record: Vec<String> = vec![
+            "Buy eggs, milk".to_string(),
+            "a \"b\"".to_string(),
+            "plain".to_string(),
+        ];
+
+        let line: String = format_record(&record);
+
+        assert_eq!(line, "\"Buy eggs, milk\",\"a \"\"b\"\"\",plain");
+        assert_eq!(parse_records(&line), Ok(vec![record]));
+    }
+}
@@ -0,0 +1,178 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+// Seconds elapsed since the unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("The system clock is set before the unix epoch")
+        .as_secs()
+}
+
+// Parses a duration like '30d', '12h', '2w' or '45m' into seconds.
+pub fn parse_duration(value: &str) -> Result<u64, String> {
+    let error = || format!("Can not parse duration '{value}' (e.g.: 30d, 12h, 2w)");
+    if value.len() < 2 {
+        return Err(error());
+    }
+    let (amount, unit) = value.split_at(value.len() - 1);
+    let amount: u64 = amount.parse().map_err(|_| error())?;
+    let unit_seconds: u64 = match unit {
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => SECONDS_PER_DAY,
+        "w" => 7 * SECONDS_PER_DAY,
+        _ => return Err(error()),
+    };
+    amount
+        .checked_mul(unit_seconds)
+        .ok_or_else(|| format!("The duration '{value}' is too long"))
+}
+
+// Formats a duration in seconds with its two largest units (e.g.: '3d 4h').
+pub fn format_duration(seconds: u64) -> String {
+    let days: u64 = seconds / SECONDS_PER_DAY;
+    let hours: u64 = seconds % SECONDS_PER_DAY / 3600;
+    let minutes: u64 = seconds % 3600 / 60;
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else {
+        format!("{minutes}m")
+    }
+}
+
+// Formats a unix timestamp as a 'YYYY-MM-DD' date in UTC.
+pub fn format_date(timestamp: u64) -> String {
+    let (year, month, day) = civil_from_days((timestamp / SECONDS_PER_DAY) as i64);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+// Parses a 'YYYY-MM-DD' date into the unix timestamp of its start in UTC.
+pub fn parse_date(value: &str) -> Result<u64, String> {
+    let error = || format!("Can not parse date '{value}' (e.g.: 2026-10-01)");
+    let parts: Vec<&str> = value.split('-').collect();
+    let [year, month, day] = parts[..] else {
+        return Err(error());
+    };
+    let year: i64 = year.parse().map_err(|_| error())?;
+    let month: u32 = month.parse().map_err(|_| error())?;
+    let day: u32 = day.parse().map_err(|_| error())?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return Err(error());
+    }
+    let days: i64 = days_from_civil(year, month, day);
+    // Rejects days past the end of the month, like 2026-02-30.
+    if civil_from_days(days) != (year, month, day) || days < 0 {
+        return Err(error());
+    }
+    Ok(days as u64 * SECONDS_PER_DAY)
+}
+
+// Converts a (year, month, day) triple to days since the unix epoch.
+// See http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year: i64 = if month <= 2 { year - 1 } else { year };
+    let era: i64 = year.div_euclid(400);
+    let year_of_era: i64 = year.rem_euclid(400);
+    let month: i64 = i64::from(month);
+    let month_index: i64 = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year: i64 = (153 * month_index + 2) / 5 + i64::from(day) - 1;
+    let day_of_era: i64 = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// Converts days since the unix epoch to a (year, month, day) triple.
+// See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z: i64 = days + 719_468;
+    let era: i64 = z.div_euclid(146_097);
+    let day_of_era: i64 = z.rem_euclid(146_097);
+    let year_of_era: i64 =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year: i64 = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index: i64 = (5 * day_of_year + 2) / 153;
+    let day: u32 = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month: u32 = if month_index < 10 {
+        (month_index + 3) as u32
+    } else {
+        (month_index - 9) as u32
+    };
+    let year: i64 = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // parse_duration tests //
+    #[test]
+    fn test_parse_duration_days() {
+        assert_eq!(parse_duration("30d"), Ok(30 * SECONDS_PER_DAY));
+    }
+
+    #[test]
+    fn test_parse_duration_hours() {
+        assert_eq!(parse_duration("12h"), Ok(12 * 60 * 60));
+    }
+
+    #[test]
+    fn test_parse_duration_invalid_unit() {
+        assert!(parse_duration("30y").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration_invalid_amount() {
+        assert!(parse_duration("d").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration_too_long() {
+        assert!(parse_duration("99999999999999w").is_err());
+    }
+
+    // format_duration tests //
+    #[test]
+    fn test_format_duration_days_and_hours() {
+        assert_eq!(
+            format_duration(3 * SECONDS_PER_DAY + 4 * 3600 + 59),
+            "3d 4h"
+        );
+    }
+
+    #[test]
+    fn test_format_duration_minutes() {
+        assert_eq!(format_duration(125), "2m");
+    }
+
+    // parse_date tests //
+    #[test]
+    fn test_parse_date_round_trip() {
+        assert_eq!(parse_date("2024-02-29"), Ok(1_709_164_800));
+        assert_eq!(format_date(parse_date("2026-10-01").unwrap()), "2026-10-01");
+    }
+
+    #[test]
+    fn test_parse_date_invalid_day() {
+        assert!(parse_date("2026-02-30").is_err());
+    }
+
+    #[test]
+    fn test_parse_date_invalid_format() {
+        assert!(parse_date("01/10/2026").is_err());
+    }
+
+    // format_date tests //
+    #[test]
+    fn test_format_date_epoch() {
+        assert_eq!(format_date(0), "1970-01-01");
+    }
+
+    #[test]
+    fn test_format_date_leap_day() {
+        assert_eq!(format_date(1_709_164_800), "2024-02-29");
+    }
+}
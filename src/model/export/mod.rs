@@ -0,0 +1,52 @@
+// The files tasks are exported to: checklists for docs, the files of other
+// tools, and calendars.
+#[derive(PartialEq, Debug, Clone)]
+pub enum ExportFormat {
+    Markdown,
+    TodoTxt,
+    Csv,
+    Ics,
+}
+
+impl From<&ExportFormat> for String {
+    fn from(value: &ExportFormat) -> Self {
+        match value {
+            ExportFormat::Markdown => String::from("markdown"),
+            ExportFormat::TodoTxt => String::from("todotxt"),
+            ExportFormat::Csv => String::from("csv"),
+            ExportFormat::Ics => String::from("ics"),
+        }
+    }
+}
+
+impl TryFrom<String> for ExportFormat {
+    type Error = String;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "markdown" => Ok(ExportFormat::Markdown),
+            "todotxt" => Ok(ExportFormat::TodoTxt),
+            "csv" => Ok(ExportFormat::Csv),
+            "ics" => Ok(ExportFormat::Ics),
+            _ => Err(format!("Unknown export format '{value}'.")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Parse ExportFormat from String and back
+    #[test]
+    fn test_export_format_string_round_trip() {
+        for format in [
+            ExportFormat::Markdown,
+            ExportFormat::TodoTxt,
+            ExportFormat::Csv,
+            ExportFormat::Ics,
+        ] {
+            assert_eq!(ExportFormat::try_from(String::from(&format)), Ok(format));
+        }
+        assert!(ExportFormat::try_from("pdf".to_string()).is_err());
+    }
+}
@@ -0,0 +1,176 @@
+pub mod query;
+
+use super::{Priority, Task, TaskState};
+use crate::date;
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    Greater,
+}
+
+impl Comparison {
+    pub fn compare<T: PartialOrd>(&self, left: &T, right: &T) -> bool {
+        match self {
+            Comparison::Less => left < right,
+            Comparison::LessOrEqual => left <= right,
+            Comparison::Equal => left == right,
+            Comparison::GreaterOrEqual => left >= right,
+            Comparison::Greater => left > right,
+        }
+    }
+}
+
+// A condition on tasks, used to select the tasks a command applies to.
+#[derive(PartialEq, Debug, Clone)]
+pub enum Filter {
+    State(TaskState),
+    Tag(String),
+    // Case insensitive substring of the task text.
+    Title(String),
+    Priority(Comparison, Priority),
+    // Compares the age of the task in seconds, so 'created > 7 days' holds
+    // for tasks created more than a week ago. Equality compares whole days,
+    // so 'created = 7 days' holds for tasks created 7 days ago.
+    Created(Comparison, u64),
+    Milestone(String),
+    Project(String),
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+    Not(Box<Filter>),
+}
+
+impl Filter {
+    pub fn matches(&self, task: &Task) -> bool {
+        match self {
+            Filter::State(state) => task.get_state() == state,
+            Filter::Tag(tag) => task.get_tags().contains(tag),
+            Filter::Title(title) => task
+                .get_task()
+                .to_lowercase()
+                .contains(&title.to_lowercase()),
+            Filter::Priority(comparison, priority) => {
+                comparison.compare(task.get_priority(), priority)
+            }
+            Filter::Created(comparison, age) => {
+                let task_age: u64 = date::now().saturating_sub(task.get_created_at());
+                match comparison {
+                    Comparison::Equal => {
+                        task_age / date::SECONDS_PER_DAY == age / date::SECONDS_PER_DAY
+                    }
+                    _ => comparison.compare(&task_age, age),
+                }
+            }
+            Filter::Milestone(milestone) => task.get_milestone() == Some(milestone),
+            Filter::Project(project) => task
+                .get_project()
+                .is_some_and(|task_project| task_project.eq_ignore_ascii_case(project)),
+            Filter::And(left, right) => left.matches(task) && right.matches(task),
+            Filter::Or(left, right) => left.matches(task) || right.matches(task),
+            Filter::Not(filter) => !filter.matches(task),
+        }
+    }
+}
+
+// The tasks a bulk command applies to: either explicit ids, a single task
+// referenced by its title or project key, or every task matching a filter.
+// A dry run only reports the matching tasks.
+#[derive(PartialEq, Debug, Clone)]
+pub enum Selection {
+    Ids(Vec<u8>),
+    Title(String),
+    Key(String, u8),
+    Where { filter: Filter, dry_run: bool },
+}
+
+// A single task, referenced by its id, by (part of) its title or by its
+// project and its number within the project.
+#[derive(PartialEq, Debug, Clone)]
+pub enum TaskReference {
+    Id(u8),
+    Title(String),
+    Key(String, u8),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Filter by state
+    #[test]
+    fn test_state_filter_matches_task_state() {
+        let task: Task = Task::new(1, "task".to_string(), TaskState::InProgress);
+
+        assert!(Filter::State(TaskState::InProgress).matches(&task));
+        assert!(!Filter::State(TaskState::Done).matches(&task));
+    }
+
+    // Filter by state and tag
+    #[test]
+    fn test_and_filter_requires_both_conditions() {
+        let mut task: Task = Task::new(1, "task".to_string(), TaskState::InProgress);
+        task.add_tag("sprint-12".to_string());
+        let filter: Filter = Filter::And(
+            Box::new(Filter::State(TaskState::InProgress)),
+            Box::new(Filter::Tag("sprint-12".to_string())),
+        );
+
+        assert!(filter.matches(&task));
+        task.remove_tag("sprint-12");
+        assert!(!filter.matches(&task));
+    }
+
+    // Filter by one state or another
+    #[test]
+    fn test_or_filter_requires_either_condition() {
+        let task: Task = Task::new(1, "task".to_string(), TaskState::NotStarted);
+        let filter: Filter = Filter::Or(
+            Box::new(Filter::State(TaskState::InProgress)),
+            Box::new(Filter::State(TaskState::NotStarted)),
+        );
+
+        assert!(filter.matches(&task));
+        assert!(!Filter::Not(Box::new(filter)).matches(&task));
+    }
+
+    // Filter by title ignores the case
+    #[test]
+    fn test_title_filter_is_case_insensitive() {
+        let task: Task = Task::new(1, "Fix the Login bug".to_string(), TaskState::NotStarted);
+
+        assert!(Filter::Title("login".to_string()).matches(&task));
+        assert!(!Filter::Title("logout".to_string()).matches(&task));
+    }
+
+    // Filter by priority
+    #[test]
+    fn test_priority_filter_compares_priorities() {
+        let mut task: Task = Task::new(1, "task".to_string(), TaskState::NotStarted);
+        task.set_priority(Priority::High);
+
+        assert!(Filter::Priority(Comparison::GreaterOrEqual, Priority::High).matches(&task));
+        assert!(!Filter::Priority(Comparison::Less, Priority::High).matches(&task));
+    }
+
+    // Filter by creation age
+    #[test]
+    fn test_created_filter_compares_task_age() {
+        let task: Task = Task::new(1, "task".to_string(), TaskState::NotStarted);
+
+        assert!(Filter::Created(Comparison::Less, date::SECONDS_PER_DAY).matches(&task));
+        assert!(!Filter::Created(Comparison::Greater, date::SECONDS_PER_DAY).matches(&task));
+    }
+
+    // Filter by creation day
+    #[test]
+    fn test_created_filter_equal_compares_days() {
+        let task: Task = Task::new(1, "task".to_string(), TaskState::NotStarted);
+
+        assert!(Filter::Created(Comparison::Equal, 0).matches(&task));
+        assert!(Filter::Created(Comparison::Equal, 3600).matches(&task));
+        assert!(!Filter::Created(Comparison::Equal, date::SECONDS_PER_DAY).matches(&task));
+    }
+}
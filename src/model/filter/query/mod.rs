@@ -0,0 +1,335 @@
+use super::{Comparison, Filter};
+use crate::date;
+use crate::model::{Priority, TaskState};
+
+// Parses a filter query. Terms are 'field<operator>value' pairs:
+// - state:<state>, tag:<tag>, milestone:<milestone>, project:<project>
+// - title~<text> (substring, double quotes allow spaces: title~"login bug")
+// - priority<operator><priority>, created<operator><duration>
+// where <operator> is one of ':', '=', '<', '<=', '>', '>='. Terms can be
+// combined with 'and', 'or', 'not' and parentheses. Terms next to each other
+// are combined with 'and', and a bare state matches that state.
+// (e.g.: 'state:in-progress or (tag:sprint-12 and not priority<high)')
+pub fn parse_filter(source: &str) -> Result<Filter, String> {
+    let tokens: Vec<Token> = tokenize(source)?;
+    let mut parser: Parser = Parser {
+        tokens,
+        position: 0,
+    };
+    let filter: Filter = parser.parse_or()?;
+    match parser.peek() {
+        None => Ok(filter),
+        Some(token) => Err(format!("Unexpected '{token}' in the filter.")),
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+enum Token {
+    OpenParenthesis,
+    CloseParenthesis,
+    And,
+    Or,
+    Not,
+    Term(String),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Token::OpenParenthesis => write!(formatter, "("),
+            Token::CloseParenthesis => write!(formatter, ")"),
+            Token::And => write!(formatter, "and"),
+            Token::Or => write!(formatter, "or"),
+            Token::Not => write!(formatter, "not"),
+            Token::Term(term) => write!(formatter, "{term}"),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens: Vec<Token> = vec![];
+    let mut word: String = String::new();
+    let mut quoted: bool = false;
+    let mut in_quotes: bool = false;
+    for character in source.chars() {
+        match character {
+            '"' => {
+                in_quotes = !in_quotes;
+                quoted = true;
+            }
+            _ if in_quotes => word.push(character),
+            '(' | ')' => {
+                push_word(&mut tokens, &mut word, &mut quoted);
+                if character == '(' {
+                    tokens.push(Token::OpenParenthesis);
+                } else {
+                    tokens.push(Token::CloseParenthesis);
+                }
+            }
+            _ if character.is_whitespace() => push_word(&mut tokens, &mut word, &mut quoted),
+            _ => word.push(character),
+        }
+    }
+    if in_quotes {
+        return Err("Unterminated quote in the filter.".to_owned());
+    }
+    push_word(&mut tokens, &mut word, &mut quoted);
+    Ok(tokens)
+}
+
+fn push_word(tokens: &mut Vec<Token>, word: &mut String, quoted: &mut bool) {
+    if word.is_empty() {
+        return;
+    }
+    let token: Token = match word.to_lowercase().as_str() {
+        "and" if !*quoted => Token::And,
+        "or" if !*quoted => Token::Or,
+        "not" if !*quoted => Token::Not,
+        _ => Token::Term(word.clone()),
+    };
+    tokens.push(token);
+    word.clear();
+    *quoted = false;
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token: Option<Token> = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<Filter, String> {
+        let mut filter: Filter = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            let right: Filter = self.parse_and()?;
+            filter = Filter::Or(Box::new(filter), Box::new(right));
+        }
+        Ok(filter)
+    }
+
+    fn parse_and(&mut self) -> Result<Filter, String> {
+        let mut filter: Filter = self.parse_not()?;
+        loop {
+            match self.peek() {
+                Some(Token::And) => {
+                    self.next();
+                }
+                Some(Token::Term(_)) | Some(Token::Not) | Some(Token::OpenParenthesis) => {}
+                _ => return Ok(filter),
+            }
+            let right: Filter = self.parse_not()?;
+            filter = Filter::And(Box::new(filter), Box::new(right));
+        }
+    }
+
+    fn parse_not(&mut self) -> Result<Filter, String> {
+        match self.next() {
+            Some(Token::Not) => Ok(Filter::Not(Box::new(self.parse_not()?))),
+            Some(Token::OpenParenthesis) => {
+                let filter: Filter = self.parse_or()?;
+                match self.next() {
+                    Some(Token::CloseParenthesis) => Ok(filter),
+                    _ => Err("Missing ')' in the filter.".to_owned()),
+                }
+            }
+            Some(Token::Term(term)) => parse_term(&term),
+            Some(token) => Err(format!("Unexpected '{token}' in the filter.")),
+            None => Err("The filter is incomplete.".to_owned()),
+        }
+    }
+}
+
+fn parse_term(term: &str) -> Result<Filter, String> {
+    let field_length: usize = term
+        .find(|character: char| !character.is_ascii_alphabetic())
+        .unwrap_or(term.len());
+    let (field, rest) = term.split_at(field_length);
+    let operator_length: usize = if rest.starts_with(">=") || rest.starts_with("<=") {
+        2
+    } else if rest.starts_with([':', '~', '=', '<', '>']) {
+        1
+    } else {
+        0
+    };
+    let (operator, value) = rest.split_at(operator_length);
+
+    // A bare state, as accepted by 'list' before filters existed.
+    if operator.is_empty() {
+        return TaskState::try_from(term.to_owned())
+            .map(Filter::State)
+            .map_err(|_| format!("Unknown filter term '{term}'."));
+    }
+    if value.is_empty() {
+        return Err(format!("Missing value in filter term '{term}'."));
+    }
+    match (field, operator) {
+        ("state", ":" | "=") => Ok(Filter::State(TaskState::try_from(value.to_owned())?)),
+        ("tag", ":" | "=") => Ok(Filter::Tag(value.to_owned())),
+        ("milestone", ":" | "=") => Ok(Filter::Milestone(value.to_owned())),
+        ("project", ":" | "=") => Ok(Filter::Project(value.to_owned())),
+        ("title", ":" | "~" | "=") => Ok(Filter::Title(value.to_owned())),
+        ("priority", _) if operator != "~" => {
+            let comparison: Comparison = parse_comparison(operator);
+            Ok(Filter::Priority(
+                comparison,
+                Priority::try_from(value.to_owned())?,
+            ))
+        }
+        ("created", _) if operator != "~" && operator != ":" => {
+            let comparison: Comparison = parse_comparison(operator);
+            Ok(Filter::Created(comparison, date::parse_duration(value)?))
+        }
+        _ => Err(format!("Unknown filter term '{term}'.")),
+    }
+}
+
+fn parse_comparison(operator: &str) -> Comparison {
+    match operator {
+        "<" => Comparison::Less,
+        "<=" => Comparison::LessOrEqual,
+        ">=" => Comparison::GreaterOrEqual,
+        ">" => Comparison::Greater,
+        _ => Comparison::Equal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn and(left: Filter, right: Filter) -> Filter {
+        Filter::And(Box::new(left), Box::new(right))
+    }
+
+    fn or(left: Filter, right: Filter) -> Filter {
+        Filter::Or(Box::new(left), Box::new(right))
+    }
+
+    #[test]
+    fn test_parse_filter_single_term() {
+        assert_eq!(
+            parse_filter("state:done"),
+            Ok(Filter::State(TaskState::Done))
+        );
+    }
+
+    #[test]
+    fn test_parse_filter_bare_state() {
+        assert_eq!(parse_filter("done"), Ok(Filter::State(TaskState::Done)));
+    }
+
+    #[test]
+    fn test_parse_filter_multiple_terms_are_combined() {
+        assert_eq!(
+            parse_filter("state:in-progress tag:sprint-12"),
+            Ok(and(
+                Filter::State(TaskState::InProgress),
+                Filter::Tag("sprint-12".to_string())
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_filter_or() {
+        assert_eq!(
+            parse_filter("state:in-progress or state:not-started"),
+            Ok(or(
+                Filter::State(TaskState::InProgress),
+                Filter::State(TaskState::NotStarted)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_filter_and_binds_tighter_than_or() {
+        assert_eq!(
+            parse_filter("tag:a or tag:b and tag:c"),
+            Ok(or(
+                Filter::Tag("a".to_string()),
+                and(Filter::Tag("b".to_string()), Filter::Tag("c".to_string()))
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_filter_parentheses_and_not() {
+        assert_eq!(
+            parse_filter("not (tag:a or tag:b)"),
+            Ok(Filter::Not(Box::new(or(
+                Filter::Tag("a".to_string()),
+                Filter::Tag("b".to_string())
+            ))))
+        );
+    }
+
+    #[test]
+    fn test_parse_filter_quoted_title() {
+        assert_eq!(
+            parse_filter("title~\"login bug\""),
+            Ok(Filter::Title("login bug".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_filter_priority_comparison() {
+        assert_eq!(
+            parse_filter("priority>=high"),
+            Ok(Filter::Priority(Comparison::GreaterOrEqual, Priority::High))
+        );
+    }
+
+    #[test]
+    fn test_parse_filter_created_duration() {
+        assert_eq!(
+            parse_filter("created>7d"),
+            Ok(Filter::Created(
+                Comparison::Greater,
+                7 * date::SECONDS_PER_DAY
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_filter_milestone() {
+        assert_eq!(
+            parse_filter("milestone:v1.2"),
+            Ok(Filter::Milestone("v1.2".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_filter_unknown_state() {
+        assert!(parse_filter("state:finished").is_err());
+    }
+
+    #[test]
+    fn test_parse_filter_unknown_field() {
+        assert!(parse_filter("owner:me").is_err());
+    }
+
+    #[test]
+    fn test_parse_filter_missing_parenthesis() {
+        assert!(parse_filter("(tag:a or tag:b").is_err());
+    }
+
+    #[test]
+    fn test_parse_filter_dangling_operator() {
+        assert!(parse_filter("tag:a or").is_err());
+    }
+
+    #[test]
+    fn test_parse_filter_empty() {
+        assert!(parse_filter("  ").is_err());
+    }
+}
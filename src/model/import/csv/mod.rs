@@ -0,0 +1,173 @@
+use super::ImportedTask;
+use crate::csv;
+
+// The fields of a task that can be read from a CSV file.
+const FIELDS: &[&str] = &["title", "state", "priority", "tags", "project", "notes"];
+
+// Which column of a CSV file holds each field of the tasks, by header. By
+// default, the headers are the field names. Only the title column is
+// required.
+#[derive(PartialEq, Debug, Clone)]
+pub struct ColumnMapping {
+    columns: Vec<(String, String)>,
+}
+impl Default for ColumnMapping {
+    fn default() -> Self {
+        ColumnMapping {
+            columns: FIELDS
+                .iter()
+                .map(|field| ((*field).to_owned(), (*field).to_owned()))
+                .collect(),
+        }
+    }
+}
+impl ColumnMapping {
+    pub fn get_column(&self, field: &str) -> &String {
+        let (_, column) = self
+            .columns
+            .iter()
+            .find(|(name, _)| name == field)
+            .expect("Every field has a column");
+        column
+    }
+}
+
+// Parses a mapping given as '<field>=<header>' pairs separated by commas
+// (e.g.: 'title=Summary,state=Status'). Fields left out keep their default
+// column.
+impl TryFrom<String> for ColumnMapping {
+    type Error = String;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let mut mapping: ColumnMapping = ColumnMapping::default();
+        for pair in value.split(',') {
+            let Some((field, header)) = pair.split_once('=') else {
+                return Err(format!(
+                    "The column mapping '{pair}' must be written as <field>=<header>."
+                ));
+            };
+            let field: String = field.trim().to_lowercase();
+            let Some((_, column)) = mapping.columns.iter_mut().find(|(name, _)| *name == field)
+            else {
+                return Err(format!(
+                    "Unknown task field '{field}', fields are {}.",
+                    FIELDS.join(", ")
+                ));
+            };
+            *column = header.trim().to_owned();
+        }
+        Ok(mapping)
+    }
+}
+
+// Reads a CSV file with a header row, one task per row. Tags are separated
+// by commas, semicolons or spaces, and the notes column holds one note.
+pub fn parse(source: &str, mapping: &ColumnMapping) -> Result<Vec<ImportedTask>, String> {
+    let mut records = csv::parse_records(source)?.into_iter();
+    let Some(headers) = records.next() else {
+        return Ok(vec![]);
+    };
+    let position = |field: &str| -> Option<usize> {
+        let column: &String = mapping.get_column(field);
+        headers
+            .iter()
+            .position(|header| header.trim().eq_ignore_ascii_case(column))
+    };
+    let title_position: usize = position("title").ok_or_else(|| {
+        format!(
+            "The CSV has no '{}' column for the titles, choose one with --map title=<header>",
+            mapping.get_column("title")
+        )
+    })?;
+    let state_position: Option<usize> = position("state");
+    let priority_position: Option<usize> = position("priority");
+    let tags_position: Option<usize> = position("tags");
+    let project_position: Option<usize> = position("project");
+    let notes_position: Option<usize> = position("notes");
+
+    let mut tasks: Vec<ImportedTask> = vec![];
+    for (index, record) in records.enumerate() {
+        // The header is the first row.
+        let row: usize = index + 2;
+        let cell = |position: Option<usize>| -> &str {
+            position
+                .and_then(|position| record.get(position))
+                .map_or("", |value| value.trim())
+        };
+        let title: &str = cell(Some(title_position));
+        if title.is_empty() {
+            return Err(format!("The task on row {row} has no title"));
+        }
+        let mut task: ImportedTask = ImportedTask::new(title.to_owned());
+        task.state = super::parse_state(cell(state_position))
+            .map_err(|error| format!("{error} on row {row}"))?;
+        task.priority = super::parse_priority(cell(priority_position))
+            .map_err(|error| format!("{error} on row {row}"))?;
+        for tag in cell(tags_position).split([',', ';', ' ']) {
+            task.add_tag(tag.to_owned());
+        }
+        let project: &str = cell(project_position);
+        if !project.is_empty() {
+            task.add_project(project);
+        }
+        let note: &str = cell(notes_position);
+        if !note.is_empty() {
+            task.notes.push(note.to_owned());
+        }
+        tasks.push(task);
+    }
+    Ok(tasks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Priority, TaskState};
+
+    // Parse a mapping, keeping the default columns of the other fields
+    #[test]
+    fn test_parse_column_mapping() {
+        let mapping: ColumnMapping =
+            ColumnMapping::try_from("title=Summary, state=Status".to_string()).unwrap();
+
+        assert_eq!(mapping.get_column("title"), "Summary");
+        assert_eq!(mapping.get_column("state"), "Status");
+        assert_eq!(mapping.get_column("tags"), "tags");
+        assert!(ColumnMapping::try_from("due=Deadline".to_string()).is_err());
+        assert!(ColumnMapping::try_from("title".to_string()).is_err());
+    }
+
+    // Read the mapped columns, whatever their order
+    #[test]
+    fn test_parse_csv_with_mapping() {
+        let mapping: ColumnMapping =
+            ColumnMapping::try_from("title=Summary,state=Status,tags=Labels".to_string()).unwrap();
+        let source: &str = "Status,Summary,Labels,Priority\n\
+            In Progress,\"Fix login, again\",bug;auth,High\n\
+            ,Write docs,,\n";
+
+        let tasks: Vec<ImportedTask> = parse(source, &mapping).unwrap();
+
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].get_title(), "Fix login, again");
+        assert_eq!(tasks[0].get_state(), &TaskState::InProgress);
+        assert_eq!(tasks[0].get_priority(), &Priority::High);
+        assert_eq!(
+            tasks[0].get_tags(),
+            &vec!["bug".to_string(), "auth".to_string()]
+        );
+        assert_eq!(tasks[1].get_state(), &TaskState::NotStarted);
+        assert!(tasks[1].get_tags().is_empty());
+    }
+
+    // The title column is required, and so are the titles
+    #[test]
+    fn test_parse_csv_without_titles() {
+        let mapping: ColumnMapping = ColumnMapping::default();
+
+        assert!(parse("Summary\nBuy eggs\n", &mapping).is_err());
+        assert_eq!(
+            parse("title,state\n,done\n", &mapping),
+            Err("The task on row 2 has no title".to_string())
+        );
+    }
+}
@@ -0,0 +1,216 @@
+pub mod csv;
+pub mod taskwarrior;
+pub mod todotxt;
+
+use super::project::Project;
+use super::{Priority, Task, TaskState};
+
+// The files tasks are migrated from.
+#[derive(PartialEq, Debug, Clone)]
+pub enum ImportFormat {
+    TodoTxt,
+    Taskwarrior,
+    Csv,
+}
+
+impl From<&ImportFormat> for String {
+    fn from(value: &ImportFormat) -> Self {
+        match value {
+            ImportFormat::TodoTxt => String::from("todotxt"),
+            ImportFormat::Taskwarrior => String::from("taskwarrior"),
+            ImportFormat::Csv => String::from("csv"),
+        }
+    }
+}
+
+impl TryFrom<String> for ImportFormat {
+    type Error = String;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "todotxt" => Ok(ImportFormat::TodoTxt),
+            "taskwarrior" => Ok(ImportFormat::Taskwarrior),
+            "csv" => Ok(ImportFormat::Csv),
+            _ => Err(format!("Unknown import format '{value}'.")),
+        }
+    }
+}
+
+// A task read from another tool, before it is added to the store.
+#[derive(PartialEq, Debug, Clone)]
+pub struct ImportedTask {
+    title: String,
+    state: TaskState,
+    priority: Priority,
+    tags: Vec<String>,
+    project: Option<String>,
+    notes: Vec<String>,
+}
+impl ImportedTask {
+    pub fn new(title: String) -> Self {
+        ImportedTask {
+            title,
+            state: TaskState::NotStarted,
+            priority: Priority::default(),
+            tags: vec![],
+            project: None,
+            notes: vec![],
+        }
+    }
+    pub fn get_title(&self) -> &String {
+        &self.title
+    }
+    pub fn get_state(&self) -> &TaskState {
+        &self.state
+    }
+    pub fn get_priority(&self) -> &Priority {
+        &self.priority
+    }
+    pub fn get_tags(&self) -> &Vec<String> {
+        &self.tags
+    }
+    pub fn get_project(&self) -> Option<&String> {
+        self.project.as_ref()
+    }
+    pub fn get_notes(&self) -> &Vec<String> {
+        &self.notes
+    }
+
+    fn add_tag(&mut self, tag: String) {
+        if !tag.is_empty() && !self.tags.contains(&tag) {
+            self.tags.push(tag);
+        }
+    }
+
+    // Sets the project, when the name can be one in this store. Otherwise,
+    // or when the task already has one, the name is kept as a tag.
+    fn add_project(&mut self, name: &str) {
+        match project_name(name) {
+            Some(project) if self.project.is_none() => self.project = Some(project),
+            _ => self.add_tag(name.to_owned()),
+        }
+    }
+}
+
+// Reads the tasks of a file in the given format. The column mapping only
+// applies to CSV files.
+pub fn parse(
+    format: &ImportFormat,
+    source: &str,
+    mapping: &csv::ColumnMapping,
+) -> Result<Vec<ImportedTask>, String> {
+    match format {
+        ImportFormat::TodoTxt => todotxt::parse(source),
+        ImportFormat::Taskwarrior => taskwarrior::parse(source),
+        ImportFormat::Csv => csv::parse(source, mapping),
+    }
+}
+
+// Splits the imported tasks into the new ones and the duplicates: the ones
+// with the title of an existing task, or of a task earlier in the import.
+// Titles are compared regardless of case and spacing.
+pub fn split_duplicates(
+    imported: Vec<ImportedTask>,
+    existing: &[Task],
+) -> (Vec<ImportedTask>, Vec<ImportedTask>) {
+    let mut titles: Vec<String> = existing
+        .iter()
+        .map(|task| normalize_title(task.get_task()))
+        .collect();
+    let mut new: Vec<ImportedTask> = vec![];
+    let mut duplicates: Vec<ImportedTask> = vec![];
+    for task in imported {
+        let title: String = normalize_title(task.get_title());
+        if titles.contains(&title) {
+            duplicates.push(task);
+        } else {
+            titles.push(title);
+            new.push(task);
+        }
+    }
+    (new, duplicates)
+}
+
+fn normalize_title(title: &str) -> String {
+    title
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+// The name of a project in this store for a project of another tool, whose
+// names may hold other characters (e.g.: 'Home.Garden' -> 'home_garden').
+fn project_name(name: &str) -> Option<String> {
+    let name: String = name
+        .chars()
+        .map(|character| match character.is_ascii_alphanumeric() {
+            true => character,
+            false => '_',
+        })
+        .collect();
+    Project::new(name)
+        .ok()
+        .map(|project| project.get_name().to_owned())
+}
+
+// Reads a state as written by other tools (e.g.: 'To Do', 'completed').
+fn parse_state(value: &str) -> Result<TaskState, String> {
+    let value: String = value.trim().to_lowercase().replace([' ', '_'], "-");
+    match value.as_str() {
+        "" | "not-started" | "todo" | "to-do" | "open" | "pending" | "waiting" => {
+            Ok(TaskState::NotStarted)
+        }
+        "in-progress" | "started" | "doing" | "active" => Ok(TaskState::InProgress),
+        "done" | "completed" | "closed" | "x" => Ok(TaskState::Done),
+        _ => Err(format!("Unknown task state '{value}'")),
+    }
+}
+
+// Reads a priority as written by other tools (e.g.: 'H', 'B', 'High').
+fn parse_priority(value: &str) -> Result<Priority, String> {
+    match value.trim().to_lowercase().as_str() {
+        "high" | "h" | "a" => Ok(Priority::High),
+        "" | "medium" | "m" | "b" => Ok(Priority::Medium),
+        "low" | "l" | "c" => Ok(Priority::Low),
+        value => Err(format!("Unknown priority '{value}'")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Skip tasks with the title of an existing or earlier task
+    #[test]
+    fn test_split_duplicates() {
+        let existing: Vec<Task> =
+            vec![Task::new(1, "Buy  eggs".to_string(), TaskState::NotStarted)];
+        let imported: Vec<ImportedTask> = vec![
+            ImportedTask::new("buy eggs".to_string()),
+            ImportedTask::new("Call mom".to_string()),
+            ImportedTask::new("Call Mom ".to_string()),
+        ];
+
+        let (new, duplicates) = split_duplicates(imported, &existing);
+
+        assert_eq!(new, vec![ImportedTask::new("Call mom".to_string())]);
+        assert_eq!(duplicates.len(), 2);
+    }
+
+    // Turn project names of other tools into valid ones
+    #[test]
+    fn test_project_name() {
+        assert_eq!(project_name("Home.Garden"), Some("home_garden".to_string()));
+        assert_eq!(project_name("2026"), None);
+    }
+
+    // Read states and priorities of other tools
+    #[test]
+    fn test_parse_state_and_priority() {
+        assert_eq!(parse_state("In Progress"), Ok(TaskState::InProgress));
+        assert_eq!(parse_state("completed"), Ok(TaskState::Done));
+        assert!(parse_state("blocked").is_err());
+        assert_eq!(parse_priority("H"), Ok(Priority::High));
+        assert_eq!(parse_priority(""), Ok(Priority::Medium));
+    }
+}
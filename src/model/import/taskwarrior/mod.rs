@@ -0,0 +1,95 @@
+use super::ImportedTask;
+use crate::model::TaskState;
+
+use json::JsonValue;
+
+// Reads the output of 'task export': a JSON array of tasks, or one JSON
+// task per line as older versions wrote it. Deleted tasks are left out,
+// started ones are in progress and annotations become notes.
+pub fn parse(source: &str) -> Result<Vec<ImportedTask>, String> {
+    let json_values: Vec<JsonValue> = match json::parse(source) {
+        Ok(JsonValue::Array(json_values)) => json_values,
+        Ok(json_value) => vec![json_value],
+        Err(_) => source
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| json::parse(line.trim().trim_end_matches(',')))
+            .collect::<Result<Vec<JsonValue>, json::Error>>()
+            .map_err(|error| format!("Can not parse the Taskwarrior export: {error}"))?,
+    };
+    let mut tasks: Vec<ImportedTask> = vec![];
+    for (index, json_value) in json_values.iter().enumerate() {
+        if json_value["status"] == "deleted" {
+            continue;
+        }
+        let task: ImportedTask = parse_task(json_value)
+            .map_err(|error| format!("Can not parse task {}: {error}", index + 1))?;
+        tasks.push(task);
+    }
+    Ok(tasks)
+}
+
+fn parse_task(json_object: &JsonValue) -> Result<ImportedTask, String> {
+    let title: &str = json_object["description"]
+        .as_str()
+        .filter(|title| !title.trim().is_empty())
+        .ok_or("it has no description")?;
+    let mut task: ImportedTask = ImportedTask::new(title.trim().to_owned());
+    task.state = match json_object["status"].as_str().unwrap_or_default() {
+        "completed" => TaskState::Done,
+        _ if json_object.has_key("start") => TaskState::InProgress,
+        _ => TaskState::NotStarted,
+    };
+    task.priority = super::parse_priority(json_object["priority"].as_str().unwrap_or_default())?;
+    if let Some(project) = json_object["project"].as_str() {
+        task.add_project(project);
+    }
+    for tag in json_object["tags"].members().filter_map(JsonValue::as_str) {
+        task.add_tag(tag.to_owned());
+    }
+    for annotation in json_object["annotations"].members() {
+        if let Some(note) = annotation["description"].as_str() {
+            task.notes.push(note.to_owned());
+        }
+    }
+    Ok(task)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Priority;
+
+    // Parse an export, leaving the deleted tasks out
+    #[test]
+    fn test_parse_taskwarrior_export() {
+        let source: &str = r#"[
+            {"description": "Fix login", "status": "pending", "start": "20261001T090000Z",
+             "priority": "H", "project": "Web.Auth", "tags": ["bug"],
+             "annotations": [{"entry": "20261001T090000Z", "description": "Only on Safari"}]},
+            {"description": "Old idea", "status": "deleted"},
+            {"description": "Write docs", "status": "completed"}
+        ]"#;
+
+        let tasks: Vec<ImportedTask> = parse(source).unwrap();
+
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].get_state(), &TaskState::InProgress);
+        assert_eq!(tasks[0].get_priority(), &Priority::High);
+        assert_eq!(tasks[0].get_project(), Some(&"web_auth".to_string()));
+        assert_eq!(tasks[0].get_tags(), &vec!["bug".to_string()]);
+        assert_eq!(tasks[0].get_notes(), &vec!["Only on Safari".to_string()]);
+        assert_eq!(tasks[1].get_state(), &TaskState::Done);
+    }
+
+    // Older versions export one task per line
+    #[test]
+    fn test_parse_taskwarrior_lines() {
+        let source: &str = "{\"description\": \"One\", \"status\": \"pending\"},\n{\"description\": \"Two\", \"status\": \"waiting\"}\n";
+
+        let tasks: Vec<ImportedTask> = parse(source).unwrap();
+
+        assert_eq!(tasks.len(), 2);
+        assert!(parse("[{\"status\": \"pending\"}]").is_err());
+    }
+}
@@ -0,0 +1,139 @@
+use super::ImportedTask;
+use crate::date;
+use crate::model::{Priority, TaskState};
+
+// Reads a todo.txt file, one task per line (e.g.: 'x (A) 2026-10-02
+// 2026-09-30 Call mom +family @phone'). Completed tasks start with 'x', the
+// priority is a letter in parentheses, and the dates are dropped. The first
+// '+project' becomes the project of the task, the other projects and the
+// '@contexts' become tags.
+pub fn parse(source: &str) -> Result<Vec<ImportedTask>, String> {
+    let mut tasks: Vec<ImportedTask> = vec![];
+    for (index, line) in source.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let task: ImportedTask = parse_line(line)
+            .ok_or_else(|| format!("The task on line {} has no title", index + 1))?;
+        tasks.push(task);
+    }
+    Ok(tasks)
+}
+
+fn parse_line(line: &str) -> Option<ImportedTask> {
+    let mut words: Vec<&str> = line.split_whitespace().collect();
+    let mut state: TaskState = TaskState::NotStarted;
+    let mut priority: Priority = Priority::default();
+    if words.first() == Some(&"x") {
+        state = TaskState::Done;
+        words.remove(0);
+    }
+    if let Some(letter) = words.first().and_then(|word| parse_priority_word(word)) {
+        priority = letter_priority(letter);
+        words.remove(0);
+    }
+    // The completion and creation dates.
+    while words
+        .first()
+        .is_some_and(|word| date::parse_date(word).is_ok())
+    {
+        words.remove(0);
+    }
+
+    let mut title: Vec<&str> = vec![];
+    let mut projects: Vec<&str> = vec![];
+    let mut contexts: Vec<&str> = vec![];
+    for word in words {
+        if let Some(project) = word.strip_prefix('+').filter(|name| !name.is_empty()) {
+            projects.push(project);
+        } else if let Some(context) = word.strip_prefix('@').filter(|name| !name.is_empty()) {
+            contexts.push(context);
+        } else if let Some(letter) = word.strip_prefix("pri:").and_then(single_letter) {
+            // Completed tasks keep their priority as a 'pri:' tag.
+            priority = letter_priority(letter);
+        } else {
+            title.push(word);
+        }
+    }
+    if title.is_empty() {
+        return None;
+    }
+    let mut task: ImportedTask = ImportedTask::new(title.join(" "));
+    task.state = state;
+    task.priority = priority;
+    for project in projects {
+        task.add_project(project);
+    }
+    for context in contexts {
+        task.add_tag(context.to_owned());
+    }
+    Some(task)
+}
+
+// The letter of a priority word (e.g.: '(A)' -> 'A').
+fn parse_priority_word(word: &str) -> Option<char> {
+    word.strip_prefix('(')
+        .and_then(|word| word.strip_suffix(')'))
+        .and_then(single_letter)
+}
+
+fn single_letter(value: &str) -> Option<char> {
+    let mut characters = value.chars();
+    match (characters.next(), characters.next()) {
+        (Some(letter), None) if letter.is_ascii_uppercase() => Some(letter),
+        _ => None,
+    }
+}
+
+// todo.txt has a priority per letter, the first ones map to the three
+// priorities here.
+fn letter_priority(letter: char) -> Priority {
+    match letter {
+        'A' => Priority::High,
+        'B' => Priority::Medium,
+        _ => Priority::Low,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Parse the completion, priority, dates, projects and contexts
+    #[test]
+    fn test_parse_todotxt_line() {
+        let tasks: Vec<ImportedTask> =
+            parse("x (A) 2026-10-02 2026-09-30 Call mom +Family +weekly @phone\n\n(C) Buy eggs\n")
+                .unwrap();
+
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].get_title(), "Call mom");
+        assert_eq!(tasks[0].get_state(), &TaskState::Done);
+        assert_eq!(tasks[0].get_priority(), &Priority::High);
+        assert_eq!(tasks[0].get_project(), Some(&"family".to_string()));
+        assert_eq!(
+            tasks[0].get_tags(),
+            &vec!["weekly".to_string(), "phone".to_string()]
+        );
+        assert_eq!(tasks[1].get_state(), &TaskState::NotStarted);
+        assert_eq!(tasks[1].get_priority(), &Priority::Low);
+    }
+
+    // Completed tasks keep their priority in a 'pri:' tag
+    #[test]
+    fn test_parse_todotxt_completed_priority() {
+        let tasks: Vec<ImportedTask> = parse("x 2026-10-02 Ship it pri:A").unwrap();
+
+        assert_eq!(tasks[0].get_title(), "Ship it");
+        assert_eq!(tasks[0].get_priority(), &Priority::High);
+    }
+
+    // A line needs a title
+    #[test]
+    fn test_parse_todotxt_without_title() {
+        assert_eq!(
+            parse("Buy eggs\nx 2026-10-02 +home"),
+            Err("The task on line 2 has no title".to_string())
+        );
+    }
+}
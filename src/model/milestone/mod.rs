@@ -0,0 +1,81 @@
+use json::{JsonValue, object};
+
+// A phase of the roadmap that tasks are grouped into, due by a target date.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Milestone {
+    name: String,
+    target_date: u64,
+    description: String,
+}
+impl Milestone {
+    pub fn new(name: String, target_date: u64, description: String) -> Self {
+        Milestone {
+            name,
+            target_date,
+            description,
+        }
+    }
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+    pub fn get_target_date(&self) -> u64 {
+        self.target_date
+    }
+    pub fn get_description(&self) -> &String {
+        &self.description
+    }
+}
+
+impl From<&Milestone> for JsonValue {
+    fn from(milestone: &Milestone) -> JsonValue {
+        object! {
+            "name" => milestone.get_name().to_owned(),
+            "target_date" => milestone.get_target_date(),
+            "description" => milestone.get_description().to_owned()
+        }
+    }
+}
+
+impl TryFrom<&JsonValue> for Milestone {
+    type Error = String;
+    fn try_from(json_object: &JsonValue) -> Result<Self, Self::Error> {
+        let name: String = json_object["name"]
+            .as_str()
+            .ok_or("Can not parse milestone name")?
+            .to_owned();
+        let target_date: u64 = json_object["target_date"]
+            .as_u64()
+            .ok_or("Can not parse milestone target date")?;
+        let description: String = json_object["description"]
+            .as_str()
+            .unwrap_or_default()
+            .to_owned();
+        Ok(Milestone::new(name, target_date, description))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Convert Milestone to JsonValue and back
+    #[test]
+    fn test_milestone_json_round_trip() {
+        let milestone: Milestone = Milestone::new(
+            "v1.2".to_string(),
+            1_790_000_000,
+            "Sync and sharing".to_string(),
+        );
+
+        let json_value: JsonValue = JsonValue::from(&milestone);
+        assert_eq!(Milestone::try_from(&json_value), Ok(milestone));
+    }
+
+    // Parse Milestone without a target date
+    #[test]
+    fn test_parse_milestone_without_target_date() {
+        let json_value: JsonValue = object! { "name" => "v1.2" };
+
+        assert!(Milestone::try_from(&json_value).is_err());
+    }
+}
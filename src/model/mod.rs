@@ -1,6 +1,22 @@
+pub mod export;
+pub mod filter;
+pub mod import;
+pub mod milestone;
+pub mod project;
+pub mod report;
+pub mod search;
+pub mod sort;
+pub mod stats;
+pub mod sync;
+pub mod token;
+pub mod view;
+
+use crate::date;
+
 use json::{JsonValue, object};
 
-#[derive(PartialEq, Debug, Clone)]
+// Ordered along the workflow, so states sort from new to finished tasks.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone)]
 pub enum TaskState {
     NotStarted,
     InProgress,
@@ -20,11 +36,11 @@ impl From<&TaskState> for String {
 impl TryFrom<String> for TaskState {
     type Error = String;
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        if value == String::from("not-started") {
+        if value == "not-started" {
             Ok(TaskState::NotStarted)
-        } else if value == String::from("in-progress") {
+        } else if value == "in-progress" {
             Ok(TaskState::InProgress)
-        } else if value == String::from("done") {
+        } else if value == "done" {
             Ok(TaskState::Done)
         } else {
             Err("Unknown task state parameter.".to_owned())
@@ -32,15 +48,108 @@ impl TryFrom<String> for TaskState {
     }
 }
 
-#[derive(Debug, Clone)]
+// Ordered from the least to the most important, so priorities compare.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Default)]
+pub enum Priority {
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+impl From<&Priority> for String {
+    fn from(value: &Priority) -> Self {
+        match value {
+            Priority::Low => String::from("low"),
+            Priority::Medium => String::from("medium"),
+            Priority::High => String::from("high"),
+        }
+    }
+}
+
+impl TryFrom<String> for Priority {
+    type Error = String;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value == "low" {
+            Ok(Priority::Low)
+        } else if value == "medium" {
+            Ok(Priority::Medium)
+        } else if value == "high" {
+            Ok(Priority::High)
+        } else {
+            Err("Unknown priority parameter.".to_owned())
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct StateChange {
+    state: TaskState,
+    changed_at: u64,
+}
+impl StateChange {
+    pub fn new(state: TaskState, changed_at: u64) -> Self {
+        StateChange { state, changed_at }
+    }
+    pub fn get_state(&self) -> &TaskState {
+        &self.state
+    }
+    pub fn get_changed_at(&self) -> u64 {
+        self.changed_at
+    }
+}
+
+impl From<&StateChange> for JsonValue {
+    fn from(state_change: &StateChange) -> JsonValue {
+        object! {
+            "state" => String::from(state_change.get_state()),
+            "changed_at" => state_change.get_changed_at()
+        }
+    }
+}
+
+impl TryFrom<&JsonValue> for StateChange {
+    type Error = String;
+    fn try_from(json_object: &JsonValue) -> Result<Self, Self::Error> {
+        let state: TaskState = TaskState::try_from(json_object["state"].to_string())?;
+        let changed_at: u64 = json_object["changed_at"]
+            .as_u64()
+            .ok_or("Can not parse state change time")?;
+        Ok(StateChange::new(state, changed_at))
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
 pub struct Task {
     id: u8,
     task: String,
     state: TaskState,
+    priority: Priority,
+    tags: Vec<String>,
+    notes: Vec<String>,
+    created_at: u64,
+    history: Vec<StateChange>,
+    // The name of the milestone the task is planned for
+    milestone: Option<String>,
+    // The project of the task and the number of the task within it
+    project: Option<(String, u8)>,
 }
 impl Task {
     pub fn new(id: u8, task: String, state: TaskState) -> Self {
-        Task { id, task, state }
+        let created_at: u64 = date::now();
+        let history: Vec<StateChange> = vec![StateChange::new(state.clone(), created_at)];
+        Task {
+            id,
+            task,
+            state,
+            priority: Priority::default(),
+            tags: vec![],
+            notes: vec![],
+            created_at,
+            history,
+            milestone: None,
+            project: None,
+        }
     }
     pub fn get_id(&self) -> u8 {
         self.id
@@ -53,13 +162,107 @@ impl Task {
         &self.state
     }
 
+    pub fn get_priority(&self) -> &Priority {
+        &self.priority
+    }
+
+    pub fn set_priority(&mut self, priority: Priority) {
+        self.priority = priority;
+    }
+
+    pub fn get_tags(&self) -> &Vec<String> {
+        &self.tags
+    }
+
+    pub fn add_tag(&mut self, tag: String) {
+        if !self.tags.contains(&tag) {
+            self.tags.push(tag);
+        }
+    }
+
+    pub fn remove_tag(&mut self, tag: &str) {
+        self.tags.retain(|task_tag| task_tag != tag);
+    }
+
+    pub fn get_notes(&self) -> &Vec<String> {
+        &self.notes
+    }
+
+    pub fn add_note(&mut self, note: String) {
+        self.notes.push(note);
+    }
+
+    pub fn get_created_at(&self) -> u64 {
+        self.created_at
+    }
+
+    pub fn get_history(&self) -> &Vec<StateChange> {
+        &self.history
+    }
+
+    // The time the task was last marked as done, if it is done. Tasks stored
+    // before the state history was recorded have no known time.
+    pub fn get_done_at(&self) -> Option<u64> {
+        if self.state != TaskState::Done {
+            return None;
+        }
+        self.history.last().map(StateChange::get_changed_at)
+    }
+
+    // The state the task was in at the given time, or None when it did not
+    // exist yet. Tasks stored before the state history was recorded are
+    // taken to always have been in their current state.
+    pub fn get_state_at(&self, timestamp: u64) -> Option<&TaskState> {
+        if timestamp < self.created_at {
+            return None;
+        }
+        let state_change: Option<&StateChange> = self
+            .history
+            .iter()
+            .take_while(|state_change| state_change.get_changed_at() <= timestamp)
+            .last();
+        match state_change {
+            Some(state_change) => Some(state_change.get_state()),
+            None if self.history.is_empty() => Some(&self.state),
+            None => self.history.first().map(StateChange::get_state),
+        }
+    }
+
     pub fn set_state(&mut self, state: String) {
-        self.state = TaskState::try_from(state).unwrap();
+        let state: TaskState = TaskState::try_from(state).unwrap();
+        if state != self.state {
+            self.history
+                .push(StateChange::new(state.clone(), date::now()));
+        }
+        self.state = state;
     }
 
     pub fn set_task(&mut self, task: String) {
         self.task = task;
     }
+
+    pub fn get_milestone(&self) -> Option<&String> {
+        self.milestone.as_ref()
+    }
+
+    pub fn set_milestone(&mut self, milestone: Option<String>) {
+        self.milestone = milestone;
+    }
+
+    pub fn get_project(&self) -> Option<&String> {
+        self.project.as_ref().map(|(project, _)| project)
+    }
+
+    // The key of the task within its project (e.g.: 'INFRA-12').
+    pub fn get_key(&self) -> Option<String> {
+        self.project
+            .as_ref()
+            .map(|(project, number)| project::format_key(project, *number))
+    }
+
+    pub fn set_project(&mut self, project: String, number: u8) {
+        self.project = Some((project, number));
+    }
 }
 
 impl From<&Task> for JsonValue {
@@ -67,7 +270,19 @@ impl From<&Task> for JsonValue {
         object! {
             "id"  => task_model.get_id(),
             "task" => task_model.get_task().to_owned(),
-            "state" =>String::from(task_model.get_state())
+            "state" =>String::from(task_model.get_state()),
+            "priority" => String::from(task_model.get_priority()),
+            "tags" => task_model.get_tags().clone(),
+            "notes" => task_model.get_notes().clone(),
+            "created_at" => task_model.get_created_at(),
+            "history" => task_model
+                .get_history()
+                .iter()
+                .map(JsonValue::from)
+                .collect::<Vec<JsonValue>>(),
+            "milestone" => task_model.get_milestone().cloned(),
+            "project" => task_model.get_project().cloned(),
+            "number" => task_model.project.as_ref().map(|(_, number)| *number)
         }
     }
 }
@@ -78,13 +293,93 @@ impl TryFrom<&JsonValue> for Task {
         let id: u8 = json_object["id"].as_u8().ok_or("Can not parse task id")?;
         let task: String = json_object["task"].to_string();
         let state: TaskState = TaskState::try_from(json_object["state"].to_string())?;
-        Ok(Task::new(id, task, state))
+        let priority: Priority = match json_object["priority"].as_str() {
+            Some(priority) => Priority::try_from(priority.to_owned())?,
+            None => Priority::default(),
+        };
+        let mut tags: Vec<String> = vec![];
+        for json_value in json_object["tags"].members() {
+            tags.push(json_value.to_string());
+        }
+        let mut notes: Vec<String> = vec![];
+        for json_value in json_object["notes"].members() {
+            notes.push(json_value.to_string());
+        }
+        // Tasks stored before timestamps were introduced have neither field.
+        let created_at: u64 = json_object["created_at"].as_u64().unwrap_or(0);
+        let mut history: Vec<StateChange> = vec![];
+        for json_value in json_object["history"].members() {
+            history.push(StateChange::try_from(json_value)?);
+        }
+        let milestone: Option<String> = json_object["milestone"].as_str().map(str::to_owned);
+        let project: Option<(String, u8)> = match json_object["project"].as_str() {
+            Some(project) => {
+                let number: u8 = json_object["number"]
+                    .as_u8()
+                    .ok_or("Can not parse task number")?;
+                Some((project.to_owned(), number))
+            }
+            None => None,
+        };
+        Ok(Task {
+            id,
+            task,
+            state,
+            priority,
+            tags,
+            notes,
+            created_at,
+            history,
+            milestone,
+            project,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TrashedTask {
+    task: Task,
+    deleted_at: u64,
+}
+impl TrashedTask {
+    pub fn new(task: Task, deleted_at: u64) -> Self {
+        TrashedTask { task, deleted_at }
+    }
+    pub fn get_task(&self) -> &Task {
+        &self.task
+    }
+    pub fn get_deleted_at(&self) -> u64 {
+        self.deleted_at
+    }
+    pub fn into_task(self) -> Task {
+        self.task
+    }
+}
+
+impl From<&TrashedTask> for JsonValue {
+    fn from(trashed_task: &TrashedTask) -> JsonValue {
+        object! {
+            "task" => JsonValue::from(trashed_task.get_task()),
+            "deleted_at" => trashed_task.get_deleted_at()
+        }
+    }
+}
+
+impl TryFrom<&JsonValue> for TrashedTask {
+    type Error = String;
+    fn try_from(json_object: &JsonValue) -> Result<Self, Self::Error> {
+        let task: Task = Task::try_from(&json_object["task"])?;
+        let deleted_at: u64 = json_object["deleted_at"]
+            .as_u64()
+            .ok_or("Can not parse deletion time")?;
+        Ok(TrashedTask::new(task, deleted_at))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use json::array;
 
     // TaskState tests //
     // Parse TaskState from String not-started
@@ -144,4 +439,118 @@ mod tests {
     fn test_parse_string_done_from_task_state() {
         assert_eq!("done".to_string(), String::from(&TaskState::Done));
     }
+
+    // Priority tests //
+    // Parse Priority from String
+    #[test]
+    fn test_parse_priority_from_string() {
+        assert_eq!(
+            Priority::High,
+            Priority::try_from("high".to_string()).unwrap()
+        );
+    }
+
+    // Parse Priority from invalid String
+    #[test]
+    fn test_parse_priority_from_invalid_string() {
+        assert!(Priority::try_from("urgent".to_string()).is_err());
+    }
+
+    // Priorities are ordered by importance
+    #[test]
+    fn test_priority_ordering() {
+        assert!(Priority::Low < Priority::Medium);
+        assert!(Priority::Medium < Priority::High);
+    }
+
+    // Task tests //
+    // Marking a task records the state change
+    #[test]
+    fn test_set_state_records_state_change() {
+        let mut task: Task = Task::new(1, "task".to_string(), TaskState::NotStarted);
+        task.set_state("done".to_string());
+
+        assert_eq!(task.get_history().len(), 2);
+        assert_eq!(task.get_history()[1].get_state(), &TaskState::Done);
+        assert!(task.get_done_at().is_some());
+    }
+
+    // Marking a task with its current state does not record a state change
+    #[test]
+    fn test_set_state_with_same_state_does_not_record_state_change() {
+        let mut task: Task = Task::new(1, "task".to_string(), TaskState::NotStarted);
+        task.set_state("not-started".to_string());
+
+        assert_eq!(task.get_history().len(), 1);
+        assert_eq!(task.get_done_at(), None);
+    }
+
+    // Looking up the state of a task at a given time
+    #[test]
+    fn test_get_state_at_follows_history() {
+        let json_value: JsonValue = object! {
+            "id" => 1,
+            "task" => "task",
+            "state" => "done",
+            "created_at" => 100,
+            "history" => array![
+                object! { "state" => "not-started", "changed_at" => 100 },
+                object! { "state" => "done", "changed_at" => 200 }
+            ]
+        };
+        let task: Task = Task::try_from(&json_value).unwrap();
+
+        assert_eq!(task.get_state_at(50), None);
+        assert_eq!(task.get_state_at(150), Some(&TaskState::NotStarted));
+        assert_eq!(task.get_state_at(200), Some(&TaskState::Done));
+    }
+
+    // Adding a tag twice keeps a single copy
+    #[test]
+    fn test_add_tag_ignores_duplicates() {
+        let mut task: Task = Task::new(1, "task".to_string(), TaskState::NotStarted);
+        task.add_tag("sprint-12".to_string());
+        task.add_tag("sprint-12".to_string());
+
+        assert_eq!(task.get_tags(), &vec!["sprint-12".to_string()]);
+    }
+
+    // Removing a tag
+    #[test]
+    fn test_remove_tag() {
+        let mut task: Task = Task::new(1, "task".to_string(), TaskState::NotStarted);
+        task.add_tag("sprint-12".to_string());
+        task.remove_tag("sprint-12");
+
+        assert!(task.get_tags().is_empty());
+    }
+
+    // Parse Task stored without timestamps
+    #[test]
+    fn test_parse_task_without_timestamps() {
+        let json_value: JsonValue = object! {
+            "id" => 3,
+            "task" => "task",
+            "state" => "done"
+        };
+        let task: Task = Task::try_from(&json_value).unwrap();
+
+        assert_eq!(task.get_priority(), &Priority::Medium);
+        assert_eq!(task.get_created_at(), 0);
+        assert!(task.get_history().is_empty());
+        assert_eq!(task.get_done_at(), None);
+    }
+
+    // TrashedTask tests //
+    // Convert TrashedTask to JsonValue and back
+    #[test]
+    fn test_trashed_task_json_round_trip() {
+        let task: Task = Task::new(7, "task".to_string(), TaskState::Done);
+        let trashed_task: TrashedTask = TrashedTask::new(task, 42);
+
+        let json_value: JsonValue = JsonValue::from(&trashed_task);
+        let parsed: TrashedTask = TrashedTask::try_from(&json_value).unwrap();
+        assert_eq!(parsed.get_task().get_id(), 7);
+        assert_eq!(parsed.get_deleted_at(), 42);
+    }
 }
@@ -0,0 +1,128 @@
+use json::{JsonValue, object};
+
+// A group of tasks within the store. Tasks of a project are numbered on
+// their own and referenced by the project key (e.g.: 'INFRA-12').
+#[derive(PartialEq, Debug, Clone)]
+pub struct Project {
+    name: String,
+    last_number: u8,
+}
+impl Project {
+    pub fn new(name: String) -> Result<Self, String> {
+        let valid: bool = name
+            .chars()
+            .next()
+            .is_some_and(|character| character.is_ascii_alphabetic())
+            && name
+                .chars()
+                .all(|character| character.is_ascii_alphanumeric() || character == '_');
+        if !valid {
+            return Err(format!(
+                "The project name '{name}' must start with a letter and contain only letters, digits and '_'."
+            ));
+        }
+        Ok(Project {
+            name: name.to_lowercase(),
+            last_number: 0,
+        })
+    }
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+    pub fn get_last_number(&self) -> u8 {
+        self.last_number
+    }
+
+    // Hands out the number of the next task of the project.
+    pub fn next_number(&mut self) -> u8 {
+        self.last_number = self
+            .last_number
+            .checked_add(1)
+            .unwrap_or_else(|| panic!("The project '{}' is full.", self.name));
+        self.last_number
+    }
+}
+
+// Formats the key of a task of a project (e.g.: 'INFRA-12').
+pub fn format_key(project: &str, number: u8) -> String {
+    format!("{}-{number}", project.to_uppercase())
+}
+
+// Splits a task key into the project name and the task number.
+pub fn parse_key(value: &str) -> Option<(String, u8)> {
+    let (project, number) = value.rsplit_once('-')?;
+    Project::new(project.to_owned()).ok()?;
+    let number: u8 = number.parse().ok()?;
+    Some((project.to_lowercase(), number))
+}
+
+impl From<&Project> for JsonValue {
+    fn from(project: &Project) -> JsonValue {
+        object! {
+            "name" => project.get_name().to_owned(),
+            "last_number" => project.get_last_number()
+        }
+    }
+}
+
+impl TryFrom<&JsonValue> for Project {
+    type Error = String;
+    fn try_from(json_object: &JsonValue) -> Result<Self, Self::Error> {
+        let name: String = json_object["name"]
+            .as_str()
+            .ok_or("Can not parse project name")?
+            .to_owned();
+        let mut project: Project = Project::new(name)?;
+        project.last_number = json_object["last_number"].as_u8().unwrap_or(0);
+        Ok(project)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Project names are stored in lowercase
+    #[test]
+    fn test_new_project_lowercases_name() {
+        assert_eq!(
+            Project::new("Infra".to_string()).unwrap().get_name(),
+            "infra"
+        );
+    }
+
+    // Project names can not contain the key separator
+    #[test]
+    fn test_new_project_with_invalid_name() {
+        assert!(Project::new("infra-2".to_string()).is_err());
+        assert!(Project::new("2infra".to_string()).is_err());
+    }
+
+    // Numbers are handed out in turn
+    #[test]
+    fn test_next_number_increments() {
+        let mut project: Project = Project::new("infra".to_string()).unwrap();
+
+        assert_eq!(project.next_number(), 1);
+        assert_eq!(project.next_number(), 2);
+    }
+
+    // Parse and format task keys
+    #[test]
+    fn test_parse_key_round_trip() {
+        assert_eq!(parse_key("INFRA-12"), Some(("infra".to_string(), 12)));
+        assert_eq!(format_key("infra", 12), "INFRA-12");
+        assert_eq!(parse_key("3-12"), None);
+        assert_eq!(parse_key("login-bug"), None);
+    }
+
+    // Convert Project to JsonValue and back
+    #[test]
+    fn test_project_json_round_trip() {
+        let mut project: Project = Project::new("infra".to_string()).unwrap();
+        project.next_number();
+
+        let json_value: JsonValue = JsonValue::from(&project);
+        assert_eq!(Project::try_from(&json_value), Ok(project));
+    }
+}
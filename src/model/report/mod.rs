@@ -0,0 +1,150 @@
+use super::{Task, TaskState};
+use crate::date;
+
+// How a report is rendered: a chart for the terminal, or a file for slides.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub enum ReportFormat {
+    #[default]
+    Text,
+    Svg,
+    Csv,
+}
+
+impl From<&ReportFormat> for String {
+    fn from(value: &ReportFormat) -> Self {
+        match value {
+            ReportFormat::Text => String::from("text"),
+            ReportFormat::Svg => String::from("svg"),
+            ReportFormat::Csv => String::from("csv"),
+        }
+    }
+}
+
+impl TryFrom<String> for ReportFormat {
+    type Error = String;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "text" => Ok(ReportFormat::Text),
+            "svg" => Ok(ReportFormat::Svg),
+            "csv" => Ok(ReportFormat::Csv),
+            _ => Err(format!("Unknown report format '{value}'.")),
+        }
+    }
+}
+
+// The tasks open and done at the end of a day, and the tasks completed
+// during that day.
+#[derive(PartialEq, Debug, Clone)]
+pub struct BurndownDay {
+    date: u64,
+    open: usize,
+    done: usize,
+    completed: usize,
+}
+impl BurndownDay {
+    pub fn get_date(&self) -> u64 {
+        self.date
+    }
+    pub fn get_open(&self) -> usize {
+        self.open
+    }
+    pub fn get_done(&self) -> usize {
+        self.done
+    }
+    pub fn get_completed(&self) -> usize {
+        self.completed
+    }
+}
+
+// Replays the state history of the tasks day by day, from the day of
+// 'since' until the day of 'now'.
+pub fn burndown(tasks: &[Task], since: u64, now: u64) -> Vec<BurndownDay> {
+    let first_day: u64 = since / date::SECONDS_PER_DAY;
+    let last_day: u64 = now / date::SECONDS_PER_DAY;
+    (first_day..=last_day)
+        .map(|day| {
+            let start: u64 = day * date::SECONDS_PER_DAY;
+            let end: u64 = (start + date::SECONDS_PER_DAY - 1).min(now);
+            let states: Vec<&TaskState> = tasks
+                .iter()
+                .filter_map(|task| task.get_state_at(end))
+                .collect();
+            let done: usize = states
+                .iter()
+                .filter(|state| ***state == TaskState::Done)
+                .count();
+            let completed: usize = tasks
+                .iter()
+                .flat_map(Task::get_history)
+                .filter(|state_change| {
+                    let changed_at: u64 = state_change.get_changed_at();
+                    *state_change.get_state() == TaskState::Done
+                        && start <= changed_at
+                        && changed_at <= end
+                })
+                .count();
+            BurndownDay {
+                date: start,
+                open: states.len() - done,
+                done,
+                completed,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use json::{JsonValue, array, object};
+
+    const DAY: u64 = date::SECONDS_PER_DAY;
+
+    fn task(id: u8, created_day: u64, done_day: Option<u64>) -> Task {
+        let mut history: JsonValue = array![object! {
+            "state" => "not-started",
+            "changed_at" => created_day * DAY
+        }];
+        if let Some(done_day) = done_day {
+            let _ =
+                history.push(object! { "state" => "done", "changed_at" => done_day * DAY + 60 });
+        }
+        let json_value: JsonValue = object! {
+            "id" => id,
+            "task" => "task",
+            "state" => if done_day.is_some() { "done" } else { "not-started" },
+            "created_at" => created_day * DAY,
+            "history" => history
+        };
+        Task::try_from(&json_value).unwrap()
+    }
+
+    // Parse ReportFormat from String
+    #[test]
+    fn test_parse_report_format_from_string() {
+        assert_eq!(
+            ReportFormat::try_from("svg".to_string()),
+            Ok(ReportFormat::Svg)
+        );
+        assert!(ReportFormat::try_from("pdf".to_string()).is_err());
+    }
+
+    // Count open and done tasks per day
+    #[test]
+    fn test_burndown_counts_open_and_done_per_day() {
+        let tasks: Vec<Task> = vec![
+            task(1, 10, Some(11)),
+            task(2, 10, Some(12)),
+            task(3, 11, None),
+        ];
+
+        let days: Vec<BurndownDay> = burndown(&tasks, 10 * DAY, 12 * DAY + 100);
+        let counts: Vec<(usize, usize, usize)> = days
+            .iter()
+            .map(|day| (day.get_open(), day.get_done(), day.get_completed()))
+            .collect();
+
+        assert_eq!(counts, vec![(2, 0, 0), (2, 1, 1), (1, 2, 1)]);
+        assert_eq!(days[0].get_date(), 10 * DAY);
+    }
+}
@@ -0,0 +1,119 @@
+pub mod query;
+
+use super::Task;
+
+// One part of a search query. Every term has to occur in a task for it to
+// be found.
+#[derive(PartialEq, Debug, Clone)]
+pub enum SearchTerm {
+    Word(String),
+    // Matches every word starting with the prefix (e.g.: 'log*').
+    Prefix(String),
+    // Matches the words next to each other, in order (e.g.: '"login bug"').
+    Phrase(Vec<String>),
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    task: Task,
+    matched_words: Vec<String>,
+}
+impl SearchHit {
+    pub fn new(task: Task, matched_words: Vec<String>) -> Self {
+        SearchHit {
+            task,
+            matched_words,
+        }
+    }
+    pub fn get_task(&self) -> &Task {
+        &self.task
+    }
+    // The indexed words the query matched, used to highlight the results.
+    pub fn get_matched_words(&self) -> &Vec<String> {
+        &self.matched_words
+    }
+}
+
+// Splits a text into lowercase words, as stored in the search index.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|character: char| !character.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+// The loosest match the commands deleting or changing the state of a task
+// accept: a part of the title, so a stray letter never targets a task.
+pub const SUBSTRING_MATCH: u8 = 1;
+// The loosest match of the other commands: the characters in order.
+pub const SUBSEQUENCE_MATCH: u8 = 3;
+
+// How well a title matches a fuzzy task reference, lower is better:
+// 0 for the whole title, 1 for a part of it, 2 when every word of the
+// reference starts a word of the title (e.g.: 'log bug' for 'Fix login bug'),
+// 3 when the characters appear in order (e.g.: 'lgnbg'). None otherwise.
+pub fn fuzzy_match(reference: &str, title: &str) -> Option<u8> {
+    let reference: String = reference.trim().to_lowercase();
+    let title: String = title.to_lowercase();
+    if reference.is_empty() {
+        return None;
+    }
+    if title == reference {
+        return Some(0);
+    }
+    if title.contains(&reference) {
+        return Some(1);
+    }
+    let title_words: Vec<String> = tokenize(&title);
+    let reference_words: Vec<String> = tokenize(&reference);
+    let all_words_match: bool = !reference_words.is_empty()
+        && reference_words.iter().all(|reference_word| {
+            title_words
+                .iter()
+                .any(|title_word| title_word.starts_with(reference_word.as_str()))
+        });
+    if all_words_match {
+        return Some(2);
+    }
+    let mut title_characters = title.chars();
+    let in_order: bool = reference
+        .chars()
+        .filter(|character| !character.is_whitespace())
+        .all(|character| title_characters.any(|title_character| title_character == character));
+    if in_order { Some(3) } else { None }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_splits_on_punctuation_and_lowercases() {
+        assert_eq!(
+            tokenize("Fix the Login-bug, again!"),
+            vec!["fix", "the", "login", "bug", "again"]
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_match_ranks_kinds_of_matches() {
+        assert_eq!(fuzzy_match("fix login bug", "Fix login bug"), Some(0));
+        assert_eq!(fuzzy_match("login bug", "Fix login bug"), Some(1));
+        assert_eq!(fuzzy_match("log bug", "Fix login bug"), Some(2));
+        assert_eq!(fuzzy_match("lgnbg", "Fix login bug"), Some(3));
+        assert_eq!(fuzzy_match("signup", "Fix login bug"), None);
+    }
+
+    #[test]
+    fn test_fuzzy_match_letters_in_order_are_looser_than_substrings() {
+        let quality: Option<u8> = fuzzy_match("fx", "Fix login bug");
+
+        assert!(quality.is_some_and(|quality| quality > SUBSTRING_MATCH));
+        assert!(quality.is_some_and(|quality| quality <= SUBSEQUENCE_MATCH));
+    }
+
+    #[test]
+    fn test_tokenize_empty_text() {
+        assert!(tokenize(" -- ").is_empty());
+    }
+}
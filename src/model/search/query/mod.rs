@@ -0,0 +1,89 @@
+use super::{SearchTerm, tokenize};
+
+// Parses search terms: plain words, prefixes ending with '*' (e.g.: 'log*')
+// and phrases in double quotes (e.g.: '"login bug"').
+pub fn parse_search(source: &str) -> Result<Vec<SearchTerm>, String> {
+    let mut terms: Vec<SearchTerm> = vec![];
+    for (index, part) in source.split('"').enumerate() {
+        // Every odd part was enclosed in double quotes.
+        if index % 2 == 1 {
+            let words: Vec<String> = tokenize(part);
+            match words.len() {
+                0 => {}
+                1 => terms.push(SearchTerm::Word(words[0].clone())),
+                _ => terms.push(SearchTerm::Phrase(words)),
+            }
+            continue;
+        }
+        for word in part.split_whitespace() {
+            terms.push(parse_word(word)?);
+        }
+    }
+    if source.matches('"').count() % 2 == 1 {
+        return Err("Unterminated quote in the search.".to_owned());
+    }
+    if terms.is_empty() {
+        return Err("The search must not be empty.".to_owned());
+    }
+    Ok(terms)
+}
+
+fn parse_word(word: &str) -> Result<SearchTerm, String> {
+    let (word, prefix) = match word.strip_suffix('*') {
+        Some(word) => (word, true),
+        None => (word, false),
+    };
+    let mut words: Vec<String> = tokenize(word);
+    match (words.len(), prefix) {
+        (0, _) => Err(format!("Can not search for '{word}'.")),
+        (1, true) => Ok(SearchTerm::Prefix(words.remove(0))),
+        (1, false) => Ok(SearchTerm::Word(words.remove(0))),
+        // Words joined by punctuation (e.g.: 'login-bug') are searched as a phrase.
+        _ => Ok(SearchTerm::Phrase(words)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_search_words() {
+        assert_eq!(
+            parse_search("Login bug"),
+            Ok(vec![
+                SearchTerm::Word("login".to_string()),
+                SearchTerm::Word("bug".to_string())
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_search_prefix() {
+        assert_eq!(
+            parse_search("log*"),
+            Ok(vec![SearchTerm::Prefix("log".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_parse_search_phrase() {
+        assert_eq!(
+            parse_search("\"login bug\" docs"),
+            Ok(vec![
+                SearchTerm::Phrase(vec!["login".to_string(), "bug".to_string()]),
+                SearchTerm::Word("docs".to_string())
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_search_unterminated_quote() {
+        assert!(parse_search("\"login bug").is_err());
+    }
+
+    #[test]
+    fn test_parse_search_empty() {
+        assert!(parse_search(" ").is_err());
+    }
+}
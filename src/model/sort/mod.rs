@@ -0,0 +1,171 @@
+use super::Task;
+use super::view::Column;
+
+use std::cmp::Ordering;
+
+// A field to sort the tasks by. Written as 'field' or 'field:desc'.
+#[derive(PartialEq, Debug, Clone)]
+pub struct SortKey {
+    column: Column,
+    descending: bool,
+}
+
+impl SortKey {
+    pub fn new(column: Column, descending: bool) -> Self {
+        SortKey { column, descending }
+    }
+    pub fn get_column(&self) -> &Column {
+        &self.column
+    }
+    pub fn is_descending(&self) -> bool {
+        self.descending
+    }
+
+    pub fn compare(&self, left: &Task, right: &Task) -> Ordering {
+        let ordering: Ordering = match self.column {
+            Column::Id => left.get_id().cmp(&right.get_id()),
+            Column::State => left.get_state().cmp(right.get_state()),
+            Column::Priority => left.get_priority().cmp(right.get_priority()),
+            Column::Tags => left.get_tags().cmp(right.get_tags()),
+            Column::Created => left.get_created_at().cmp(&right.get_created_at()),
+            Column::Title => left
+                .get_task()
+                .to_lowercase()
+                .cmp(&right.get_task().to_lowercase()),
+        };
+        match self.descending {
+            true => ordering.reverse(),
+            false => ordering,
+        }
+    }
+}
+
+impl From<&SortKey> for String {
+    fn from(value: &SortKey) -> Self {
+        let column: String = String::from(value.get_column());
+        match value.is_descending() {
+            true => format!("{column}:desc"),
+            false => column,
+        }
+    }
+}
+
+impl TryFrom<String> for SortKey {
+    type Error = String;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let (column, direction) = value.split_once(':').unwrap_or((&value, "asc"));
+        let descending: bool = match direction {
+            "asc" => false,
+            "desc" => true,
+            _ => return Err(format!("Unknown sort direction '{direction}'.")),
+        };
+        Ok(SortKey::new(
+            Column::try_from(column.to_owned())?,
+            descending,
+        ))
+    }
+}
+
+// How to order and page the tasks of a list. The default keeps the store
+// order and shows every task.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ListOptions {
+    sort: Vec<SortKey>,
+    limit: Option<usize>,
+    offset: usize,
+}
+
+impl ListOptions {
+    pub fn new(sort: Vec<SortKey>, limit: Option<usize>, offset: usize) -> Self {
+        ListOptions {
+            sort,
+            limit,
+            offset,
+        }
+    }
+    pub fn get_sort(&self) -> &Vec<SortKey> {
+        &self.sort
+    }
+    pub fn set_sort(&mut self, sort: Vec<SortKey>) {
+        self.sort = sort;
+    }
+
+    // Sorts the tasks by the keys in turn, later keys breaking ties of the
+    // earlier ones, and keeps the requested page.
+    pub fn apply(&self, mut tasks: Vec<Task>) -> Vec<Task> {
+        tasks.sort_by(|left, right| {
+            self.sort
+                .iter()
+                .map(|key| key.compare(left, right))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+        let limit: usize = self.limit.unwrap_or(usize::MAX);
+        tasks.into_iter().skip(self.offset).take(limit).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Priority, TaskState};
+
+    fn task(id: u8, text: &str, priority: Priority) -> Task {
+        let mut task: Task = Task::new(id, text.to_string(), TaskState::NotStarted);
+        task.set_priority(priority);
+        task
+    }
+
+    fn ids(tasks: &[Task]) -> Vec<u8> {
+        tasks.iter().map(Task::get_id).collect()
+    }
+
+    // Parse SortKey from String
+    #[test]
+    fn test_parse_sort_key_from_string() {
+        assert_eq!(
+            SortKey::try_from("priority:desc".to_string()),
+            Ok(SortKey::new(Column::Priority, true))
+        );
+        assert_eq!(
+            SortKey::try_from("title".to_string()),
+            Ok(SortKey::new(Column::Title, false))
+        );
+    }
+
+    // Parse SortKey with an invalid direction
+    #[test]
+    fn test_parse_sort_key_with_invalid_direction() {
+        assert!(SortKey::try_from("title:up".to_string()).is_err());
+    }
+
+    // Sort by several keys
+    #[test]
+    fn test_apply_sorts_by_keys_in_turn() {
+        let tasks: Vec<Task> = vec![
+            task(1, "b", Priority::Low),
+            task(2, "b", Priority::High),
+            task(3, "a", Priority::High),
+        ];
+        let options: ListOptions = ListOptions::new(
+            vec![
+                SortKey::new(Column::Priority, true),
+                SortKey::new(Column::Title, false),
+            ],
+            None,
+            0,
+        );
+
+        assert_eq!(ids(&options.apply(tasks)), vec![3, 2, 1]);
+    }
+
+    // Page through the sorted tasks
+    #[test]
+    fn test_apply_keeps_requested_page() {
+        let tasks: Vec<Task> = (1..=5).map(|id| task(id, "task", Priority::Low)).collect();
+        let options: ListOptions =
+            ListOptions::new(vec![SortKey::new(Column::Id, true)], Some(2), 1);
+
+        assert_eq!(ids(&options.apply(tasks)), vec![4, 3]);
+    }
+}
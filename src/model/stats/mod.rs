@@ -0,0 +1,349 @@
+use super::{Priority, Task, TaskState};
+use crate::date;
+
+use json::{JsonValue, object};
+
+use std::collections::BTreeMap;
+
+// The number of past weeks the completion rate is reported for.
+const WEEK_COUNT: u64 = 8;
+// The number of open tasks listed as the oldest ones.
+const OLDEST_OPEN_COUNT: usize = 5;
+
+// Tasks created and completed in one week, and the share of all the tasks
+// created until the end of the week that were done by then.
+#[derive(PartialEq, Debug, Clone)]
+pub struct WeekStats {
+    start: u64,
+    created: usize,
+    completed: usize,
+    completion_rate: f64,
+}
+impl WeekStats {
+    pub fn get_start(&self) -> u64 {
+        self.start
+    }
+    pub fn get_created(&self) -> usize {
+        self.created
+    }
+    pub fn get_completed(&self) -> usize {
+        self.completed
+    }
+    pub fn get_completion_rate(&self) -> f64 {
+        self.completion_rate
+    }
+}
+
+// A summary of the tasks for reports.
+#[derive(Debug, Clone)]
+pub struct Stats {
+    total: usize,
+    states: Vec<(TaskState, usize)>,
+    priorities: Vec<(Priority, usize)>,
+    // Most used tags first
+    tags: Vec<(String, usize)>,
+    // Oldest week first
+    weeks: Vec<WeekStats>,
+    // Average seconds spent in each state, None when no task left it yet
+    time_in_state: Vec<(TaskState, Option<u64>)>,
+    oldest_open: Vec<Task>,
+}
+
+impl Stats {
+    pub fn compute(tasks: &[Task], now: u64) -> Self {
+        let states: Vec<TaskState> = vec![
+            TaskState::NotStarted,
+            TaskState::InProgress,
+            TaskState::Done,
+        ];
+        let state_counts: Vec<(TaskState, usize)> = states
+            .iter()
+            .map(|state| {
+                let count: usize = tasks
+                    .iter()
+                    .filter(|task| task.get_state() == state)
+                    .count();
+                (state.clone(), count)
+            })
+            .collect();
+
+        let priorities: Vec<(Priority, usize)> = [Priority::High, Priority::Medium, Priority::Low]
+            .into_iter()
+            .map(|priority| {
+                let count: usize = tasks
+                    .iter()
+                    .filter(|task| task.get_priority() == &priority)
+                    .count();
+                (priority, count)
+            })
+            .collect();
+
+        let mut tag_counts: BTreeMap<String, usize> = BTreeMap::new();
+        for tag in tasks.iter().flat_map(Task::get_tags) {
+            *tag_counts.entry(tag.clone()).or_default() += 1;
+        }
+        let mut tags: Vec<(String, usize)> = tag_counts.into_iter().collect();
+        tags.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+        let mut oldest_open: Vec<Task> = tasks
+            .iter()
+            .filter(|task| task.get_state() != &TaskState::Done)
+            .cloned()
+            .collect();
+        oldest_open.sort_by_key(Task::get_created_at);
+        oldest_open.truncate(OLDEST_OPEN_COUNT);
+
+        Stats {
+            total: tasks.len(),
+            states: state_counts,
+            priorities,
+            tags,
+            weeks: weeks(tasks, now),
+            time_in_state: states
+                .into_iter()
+                .map(|state| {
+                    let average: Option<u64> = average_time_in_state(tasks, &state, now);
+                    (state, average)
+                })
+                .collect(),
+            oldest_open,
+        }
+    }
+
+    pub fn get_total(&self) -> usize {
+        self.total
+    }
+    pub fn get_states(&self) -> &Vec<(TaskState, usize)> {
+        &self.states
+    }
+    pub fn get_priorities(&self) -> &Vec<(Priority, usize)> {
+        &self.priorities
+    }
+    pub fn get_tags(&self) -> &Vec<(String, usize)> {
+        &self.tags
+    }
+    pub fn get_weeks(&self) -> &Vec<WeekStats> {
+        &self.weeks
+    }
+    pub fn get_time_in_state(&self) -> &Vec<(TaskState, Option<u64>)> {
+        &self.time_in_state
+    }
+    pub fn get_oldest_open(&self) -> &Vec<Task> {
+        &self.oldest_open
+    }
+}
+
+fn weeks(tasks: &[Task], now: u64) -> Vec<WeekStats> {
+    const SECONDS_PER_WEEK: u64 = 7 * date::SECONDS_PER_DAY;
+    (0..WEEK_COUNT)
+        .rev()
+        .map(|weeks_ago| {
+            let end: u64 = now.saturating_sub(weeks_ago * SECONDS_PER_WEEK);
+            let start: u64 = end.saturating_sub(SECONDS_PER_WEEK);
+            let in_week = |timestamp: u64| start < timestamp && timestamp <= end;
+            let created: usize = tasks
+                .iter()
+                .filter(|task| in_week(task.get_created_at()))
+                .count();
+            let completed: usize = tasks
+                .iter()
+                .filter(|task| task.get_done_at().is_some_and(in_week))
+                .count();
+            let existing: Vec<&TaskState> = tasks
+                .iter()
+                .filter_map(|task| task.get_state_at(end))
+                .collect();
+            let done: usize = existing
+                .iter()
+                .filter(|state| ***state == TaskState::Done)
+                .count();
+            let completion_rate: f64 = match existing.len() {
+                0 => 0.0,
+                count => done as f64 / count as f64,
+            };
+            WeekStats {
+                start,
+                created,
+                completed,
+                completion_rate,
+            }
+        })
+        .collect()
+}
+
+// Averages the time tasks stayed in a state before moving on. Tasks still in
+// the state count until now, except done tasks which stay done for good.
+fn average_time_in_state(tasks: &[Task], state: &TaskState, now: u64) -> Option<u64> {
+    let mut total: u64 = 0;
+    let mut count: u64 = 0;
+    for task in tasks {
+        let history = task.get_history();
+        for (index, state_change) in history.iter().enumerate() {
+            if state_change.get_state() != state {
+                continue;
+            }
+            let left_at: u64 = match history.get(index + 1) {
+                Some(next_change) => next_change.get_changed_at(),
+                None if *state == TaskState::Done => continue,
+                None => now,
+            };
+            total += left_at.saturating_sub(state_change.get_changed_at());
+            count += 1;
+        }
+    }
+    match count {
+        0 => None,
+        count => Some(total / count),
+    }
+}
+
+impl From<&Stats> for JsonValue {
+    fn from(stats: &Stats) -> JsonValue {
+        let mut states: JsonValue = JsonValue::new_object();
+        for (state, count) in stats.get_states() {
+            states[String::from(state)] = (*count).into();
+        }
+        let mut priorities: JsonValue = JsonValue::new_object();
+        for (priority, count) in stats.get_priorities() {
+            priorities[String::from(priority)] = (*count).into();
+        }
+        let mut tags: JsonValue = JsonValue::new_object();
+        for (tag, count) in stats.get_tags() {
+            tags[tag.as_str()] = (*count).into();
+        }
+        let weeks: Vec<JsonValue> = stats
+            .get_weeks()
+            .iter()
+            .map(|week| {
+                object! {
+                    "start" => date::format_date(week.get_start()),
+                    "created" => week.get_created(),
+                    "completed" => week.get_completed(),
+                    "completion_rate" => week.get_completion_rate()
+                }
+            })
+            .collect();
+        let mut time_in_state: JsonValue = JsonValue::new_object();
+        for (state, average) in stats.get_time_in_state() {
+            time_in_state[String::from(state)] = (*average).into();
+        }
+        let oldest_open: Vec<JsonValue> = stats
+            .get_oldest_open()
+            .iter()
+            .map(|task| {
+                object! {
+                    "id" => task.get_id(),
+                    "task" => task.get_task().to_owned(),
+                    "created" => date::format_date(task.get_created_at())
+                }
+            })
+            .collect();
+        object! {
+            "total" => stats.get_total(),
+            "states" => states,
+            "priorities" => priorities,
+            "tags" => tags,
+            "weeks" => weeks,
+            "average_seconds_in_state" => time_in_state,
+            "oldest_open" => oldest_open
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use json::array;
+
+    const NOW: u64 = 100 * date::SECONDS_PER_DAY;
+
+    // A task created the given number of days ago, moving through the
+    // given states on the given days ago.
+    fn task(id: u8, tag: &str, changes: &[(&str, u64)]) -> Task {
+        let mut history: JsonValue = array![];
+        for (state, days_ago) in changes {
+            let _ = history.push(object! {
+                "state" => *state,
+                "changed_at" => NOW - days_ago * date::SECONDS_PER_DAY
+            });
+        }
+        let json_value: JsonValue = object! {
+            "id" => id,
+            "task" => "task",
+            "state" => changes.last().unwrap().0,
+            "tags" => array![tag],
+            "created_at" => NOW - changes[0].1 * date::SECONDS_PER_DAY,
+            "history" => history
+        };
+        Task::try_from(&json_value).unwrap()
+    }
+
+    fn tasks() -> Vec<Task> {
+        vec![
+            task(
+                1,
+                "team",
+                &[("not-started", 20), ("in-progress", 10), ("done", 4)],
+            ),
+            task(2, "team", &[("not-started", 30), ("in-progress", 2)]),
+            task(3, "docs", &[("not-started", 3)]),
+        ]
+    }
+
+    // Count tasks per state and tag
+    #[test]
+    fn test_compute_counts_states_and_tags() {
+        let stats: Stats = Stats::compute(&tasks(), NOW);
+
+        assert_eq!(stats.get_total(), 3);
+        assert_eq!(
+            stats.get_states(),
+            &vec![
+                (TaskState::NotStarted, 1),
+                (TaskState::InProgress, 1),
+                (TaskState::Done, 1)
+            ]
+        );
+        assert_eq!(
+            stats.get_tags(),
+            &vec![("team".to_string(), 2), ("docs".to_string(), 1)]
+        );
+    }
+
+    // Average the time spent in each state
+    #[test]
+    fn test_compute_averages_time_in_state() {
+        let stats: Stats = Stats::compute(&tasks(), NOW);
+
+        // Not started for 10, 28 and 3 days; in progress for 6 and 2 days
+        assert_eq!(
+            stats.get_time_in_state(),
+            &vec![
+                (TaskState::NotStarted, Some(41 * date::SECONDS_PER_DAY / 3)),
+                (TaskState::InProgress, Some(4 * date::SECONDS_PER_DAY)),
+                (TaskState::Done, None)
+            ]
+        );
+    }
+
+    // Report the completion of the last weeks
+    #[test]
+    fn test_compute_reports_weekly_completion() {
+        let stats: Stats = Stats::compute(&tasks(), NOW);
+        let last_week: &WeekStats = stats.get_weeks().last().unwrap();
+
+        assert_eq!(stats.get_weeks().len(), WEEK_COUNT as usize);
+        assert_eq!(last_week.get_created(), 1);
+        assert_eq!(last_week.get_completed(), 1);
+        assert_eq!(last_week.get_completion_rate(), 1.0 / 3.0);
+    }
+
+    // List the oldest open tasks first
+    #[test]
+    fn test_compute_lists_oldest_open_tasks() {
+        let stats: Stats = Stats::compute(&tasks(), NOW);
+        let ids: Vec<u8> = stats.get_oldest_open().iter().map(Task::get_id).collect();
+
+        assert_eq!(ids, vec![2, 3]);
+    }
+}
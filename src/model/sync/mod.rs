@@ -0,0 +1,554 @@
+use super::{Task, TaskState};
+
+use json::{JsonValue, object};
+
+// The hidden comment linking a checkbox item to its task (e.g.: '- [ ] Buy
+// eggs <!-- task:7 -->').
+const ID_COMMENT_START: &str = "<!-- task:";
+const ID_COMMENT_END: &str = "-->";
+
+// A Markdown file, as lines of text kept as they are and checkbox items
+// synced with tasks.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Document {
+    lines: Vec<Line>,
+    line_ending: String,
+    final_line_ending: bool,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+enum Line {
+    Text(String),
+    Item(Item),
+}
+
+// A checkbox item. The key is its line in the parsed file, so it can be
+// found again while lines are removed.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Item {
+    key: usize,
+    // The indentation and list marker (e.g.: '  - ').
+    prefix: String,
+    done: bool,
+    title: String,
+    id: Option<u8>,
+}
+impl Item {
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+    pub fn get_title(&self) -> &String {
+        &self.title
+    }
+    pub fn get_id(&self) -> Option<u8> {
+        self.id
+    }
+
+    fn render(&self) -> String {
+        let mark: char = if self.done { 'x' } else { ' ' };
+        let mut line: String = format!("{}[{mark}] {}", self.prefix, self.title);
+        if let Some(id) = self.id {
+            line.push_str(&format!(" {ID_COMMENT_START}{id} {ID_COMMENT_END}"));
+        }
+        line
+    }
+}
+
+impl Document {
+    // Parses the checkbox items of the file, leaving the ones in code blocks
+    // as text.
+    pub fn parse(source: &str) -> Self {
+        let mut lines: Vec<Line> = vec![];
+        let mut fenced: bool = false;
+        for (key, line) in source.lines().enumerate() {
+            let trimmed: &str = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                fenced = !fenced;
+            }
+            match parse_item(line, key).filter(|_| !fenced) {
+                Some(item) => lines.push(Line::Item(item)),
+                None => lines.push(Line::Text(line.to_owned())),
+            }
+        }
+        let line_ending: &str = if source.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        Document {
+            lines,
+            line_ending: line_ending.to_owned(),
+            final_line_ending: source.ends_with('\n'),
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let lines: Vec<String> = self
+            .lines
+            .iter()
+            .map(|line| match line {
+                Line::Text(text) => text.to_owned(),
+                Line::Item(item) => item.render(),
+            })
+            .collect();
+        let mut output: String = lines.join(&self.line_ending);
+        if self.final_line_ending && !lines.is_empty() {
+            output.push_str(&self.line_ending);
+        }
+        output
+    }
+
+    pub fn get_items(&self) -> Vec<&Item> {
+        self.lines
+            .iter()
+            .filter_map(|line| match line {
+                Line::Item(item) => Some(item),
+                Line::Text(_) => None,
+            })
+            .collect()
+    }
+
+    // Links the item with the given key to a task.
+    pub fn link(&mut self, key: usize, id: u8) {
+        if let Some(item) = self.get_mut_item(|item| item.key == key) {
+            item.id = Some(id);
+        }
+    }
+
+    pub fn set_done(&mut self, id: u8, done: bool) {
+        if let Some(item) = self.get_mut_item(|item| item.id == Some(id)) {
+            item.done = done;
+        }
+    }
+
+    pub fn set_title(&mut self, id: u8, title: &str) {
+        if let Some(item) = self.get_mut_item(|item| item.id == Some(id)) {
+            item.title = title.replace(['\r', '\n'], " ");
+        }
+    }
+
+    pub fn remove(&mut self, id: u8) {
+        self.lines.retain(|line| match line {
+            Line::Item(item) => item.id != Some(id),
+            Line::Text(_) => true,
+        });
+    }
+
+    fn get_mut_item(&mut self, matches: impl Fn(&Item) -> bool) -> Option<&mut Item> {
+        self.lines.iter_mut().find_map(|line| match line {
+            Line::Item(item) if matches(item) => Some(item),
+            _ => None,
+        })
+    }
+}
+
+// Parses a checkbox item of a bullet or numbered list (e.g.: '- [x] Buy
+// eggs <!-- task:7 -->'). Items without a title are left as text.
+fn parse_item(line: &str, key: usize) -> Option<Item> {
+    let trimmed: &str = line.trim_start();
+    let indent: &str = &line[..line.len() - trimmed.len()];
+    let marker_length: usize = if trimmed.starts_with(['-', '*', '+']) {
+        1
+    } else {
+        let digits: usize = trimmed.chars().take_while(char::is_ascii_digit).count();
+        let numbered: bool = digits > 0 && trimmed[digits..].starts_with(['.', ')']);
+        if !numbered {
+            return None;
+        }
+        digits + 1
+    };
+    let rest: &str = trimmed[marker_length..].strip_prefix(' ')?;
+    let done: bool = match rest.get(..3)? {
+        "[ ]" => false,
+        "[x]" | "[X]" => true,
+        _ => return None,
+    };
+    let text: &str = rest[3..].strip_prefix(' ')?;
+    let (title, id) = match text.rfind(ID_COMMENT_START) {
+        Some(start) => {
+            let comment: &str = &text[start + ID_COMMENT_START.len()..];
+            let id: Option<u8> = comment
+                .trim_end()
+                .strip_suffix(ID_COMMENT_END)
+                .and_then(|id| id.trim().parse().ok());
+            match id {
+                Some(id) => (&text[..start], Some(id)),
+                None => (text, None),
+            }
+        }
+        None => (text, None),
+    };
+    let title: &str = title.trim();
+    if title.is_empty() {
+        return None;
+    }
+    Some(Item {
+        key,
+        prefix: format!("{indent}{}", &trimmed[..marker_length + 1]),
+        done,
+        title: title.to_owned(),
+        id,
+    })
+}
+
+// An item as it was after the last sync, on both sides. Comparing both sides
+// to it tells which one was edited since.
+#[derive(PartialEq, Debug, Clone)]
+pub struct SyncedItem {
+    id: u8,
+    title: String,
+    done: bool,
+}
+impl SyncedItem {
+    pub fn new(id: u8, title: String, done: bool) -> Self {
+        SyncedItem { id, title, done }
+    }
+    pub fn get_id(&self) -> u8 {
+        self.id
+    }
+    pub fn get_title(&self) -> &String {
+        &self.title
+    }
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+}
+
+impl From<&SyncedItem> for JsonValue {
+    fn from(item: &SyncedItem) -> JsonValue {
+        object! {
+            "id" => item.get_id(),
+            "title" => item.get_title().to_owned(),
+            "done" => item.is_done()
+        }
+    }
+}
+
+impl TryFrom<&JsonValue> for SyncedItem {
+    type Error = String;
+    fn try_from(json_object: &JsonValue) -> Result<Self, Self::Error> {
+        let id: u8 = json_object["id"]
+            .as_u8()
+            .ok_or("Can not parse synced item id")?;
+        let title: String = json_object["title"]
+            .as_str()
+            .ok_or("Can not parse synced item title")?
+            .to_owned();
+        let done: bool = json_object["done"]
+            .as_bool()
+            .ok_or("Can not parse synced item state")?;
+        Ok(SyncedItem::new(id, title, done))
+    }
+}
+
+// A change made by a sync, to the tasks from the edits of the file, or to
+// the file from the edits of the tasks.
+#[derive(PartialEq, Debug, Clone)]
+pub enum Change {
+    AddTask {
+        key: usize,
+        title: String,
+        done: bool,
+    },
+    LinkTask {
+        key: usize,
+        id: u8,
+    },
+    MarkTask {
+        id: u8,
+        done: bool,
+    },
+    RenameTask {
+        id: u8,
+        title: String,
+    },
+    DeleteTask(u8),
+    CheckItem {
+        id: u8,
+        done: bool,
+    },
+    RenameItem {
+        id: u8,
+        title: String,
+    },
+    RemoveItem(u8),
+    // The title was edited on both sides, the one of the task is kept.
+    Conflict(u8),
+}
+
+// Merges the edits of the file and of the tasks since the last sync, as
+// recorded in the base. Items without a base are taken as they are in the
+// file. Items without an id become new tasks, or are linked to a task with
+// the same title. So do items with an id but no base whose task has another
+// title, since their id may come from another store. The items of archived
+// tasks are left as they are.
+pub fn merge(
+    document: &Document,
+    tasks: &[Task],
+    archived: &[Task],
+    base: &[SyncedItem],
+) -> Vec<Change> {
+    let mut changes: Vec<Change> = vec![];
+    let mut linked: Vec<u8> = vec![];
+    let items: Vec<&Item> = document.get_items();
+    for item in &items {
+        let base_item: Option<&SyncedItem> = item
+            .id
+            .and_then(|id| base.iter().find(|base_item| base_item.id == id));
+        let task: Option<&Task> = item
+            .id
+            .filter(|id| !linked.contains(id))
+            .and_then(|id| tasks.iter().find(|task| task.get_id() == id))
+            .filter(|task| base_item.is_some() || has_title(task, item));
+        if let Some(task) = task {
+            linked.push(task.get_id());
+            merge_item(item, task, base_item, &mut changes);
+            continue;
+        }
+        let archived_task: Option<&Task> = item
+            .id
+            .and_then(|id| archived.iter().find(|task| task.get_id() == id))
+            .filter(|task| base_item.is_some() || has_title(task, item));
+        if archived_task.is_some() {
+            continue;
+        }
+        if let (Some(id), Some(_)) = (item.id, base_item)
+            && !linked.contains(&id)
+        {
+            // The task was deleted since the last sync.
+            changes.push(Change::RemoveItem(id));
+            continue;
+        }
+        let same_title: Option<&Task> = tasks.iter().find(|task| {
+            !linked.contains(&task.get_id())
+                && !items.iter().any(|other| other.id == Some(task.get_id()))
+                && has_title(task, item)
+        });
+        match same_title {
+            Some(task) => {
+                linked.push(task.get_id());
+                changes.push(Change::LinkTask {
+                    key: item.key,
+                    id: task.get_id(),
+                });
+                merge_item(item, task, None, &mut changes);
+            }
+            None => changes.push(Change::AddTask {
+                key: item.key,
+                title: item.title.to_owned(),
+                done: item.done,
+            }),
+        }
+    }
+    for base_item in base {
+        let in_file: bool = items.iter().any(|item| item.id == Some(base_item.id));
+        let in_store: bool = tasks.iter().any(|task| task.get_id() == base_item.id);
+        if !in_file && in_store {
+            changes.push(Change::DeleteTask(base_item.id));
+        }
+    }
+    changes
+}
+
+fn has_title(task: &Task, item: &Item) -> bool {
+    task.get_task().trim().to_lowercase() == item.title.to_lowercase()
+}
+
+// Merges one item with its task: a side edited since the base wins over an
+// unchanged one, and the task wins when both sides were edited.
+fn merge_item(item: &Item, task: &Task, base: Option<&SyncedItem>, changes: &mut Vec<Change>) {
+    let id: u8 = task.get_id();
+    let task_done: bool = task.get_state() == &TaskState::Done;
+    let task_title: &String = task.get_task();
+    let (base_done, base_title) = match base {
+        Some(base) => (base.done, &base.title),
+        None => (task_done, task_title),
+    };
+
+    if item.done != base_done && task_done == base_done {
+        changes.push(Change::MarkTask {
+            id,
+            done: item.done,
+        });
+    } else if item.done != task_done {
+        changes.push(Change::CheckItem {
+            id,
+            done: task_done,
+        });
+    }
+
+    if &item.title != base_title && task_title == base_title {
+        changes.push(Change::RenameTask {
+            id,
+            title: item.title.to_owned(),
+        });
+    } else if &item.title != task_title {
+        if &item.title != base_title {
+            changes.push(Change::Conflict(id));
+        }
+        changes.push(Change::RenameItem {
+            id,
+            title: task_title.to_owned(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_task(id: u8, title: &str, done: bool) -> Task {
+        let mut task: Task = Task::new(id, title.to_string(), TaskState::NotStarted);
+        if done {
+            task.set_state("done".to_string());
+        }
+        task
+    }
+
+    // Keep the text, the code blocks and the line endings as they are
+    #[test]
+    fn test_document_round_trip() {
+        let source: &str = "# TODO\r\n\r\n- [ ] Buy eggs <!-- task:3 -->\r\n  * [X] Nested\r\n```\r\n- [ ] In code\r\n```\r\n1. [x] Numbered\r\n- [ ]\r\n";
+
+        let document: Document = Document::parse(source);
+        let items: Vec<&Item> = document.get_items();
+
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].get_id(), Some(3));
+        assert_eq!(items[0].get_title(), "Buy eggs");
+        assert!(items[1].is_done());
+        assert_eq!(items[2].get_title(), "Numbered");
+        assert_eq!(document.render(), source.replace("[X]", "[x]"));
+    }
+
+    // Link, check, rename and remove items
+    #[test]
+    fn test_document_edits() {
+        let mut document: Document =
+            Document::parse("- [ ] Buy eggs\n- [ ] Call mom <!-- task:2 -->\nNotes\n");
+
+        document.link(0, 5);
+        document.set_done(5, true);
+        document.set_title(5, "Buy 6 eggs");
+        document.remove(2);
+
+        assert_eq!(
+            document.render(),
+            "- [x] Buy 6 eggs <!-- task:5 -->\nNotes\n"
+        );
+    }
+
+    // Apply the side edited since the last sync
+    #[test]
+    fn test_merge_edits_of_either_side() {
+        let document: Document = Document::parse(
+            "- [x] Buy eggs <!-- task:1 -->\n- [ ] Call mom <!-- task:2 -->\n- [ ] Water plants <!-- task:3 -->\n",
+        );
+        let tasks: Vec<Task> = vec![
+            sample_task(1, "Buy eggs", false),
+            sample_task(2, "Call mom", true),
+            sample_task(3, "Water the plants", false),
+        ];
+        let base: Vec<SyncedItem> = vec![
+            SyncedItem::new(1, "Buy eggs".to_string(), false),
+            SyncedItem::new(2, "Call mom".to_string(), false),
+            SyncedItem::new(3, "Water plants".to_string(), false),
+        ];
+
+        assert_eq!(
+            merge(&document, &tasks, &[], &base),
+            vec![
+                Change::MarkTask { id: 1, done: true },
+                Change::CheckItem { id: 2, done: true },
+                Change::RenameItem {
+                    id: 3,
+                    title: "Water the plants".to_string()
+                },
+            ]
+        );
+    }
+
+    // Keep the task title when both sides were renamed
+    #[test]
+    fn test_merge_conflict() {
+        let document: Document = Document::parse("- [ ] Buy milk <!-- task:1 -->\n");
+        let tasks: Vec<Task> = vec![sample_task(1, "Buy bread", false)];
+        let base: Vec<SyncedItem> = vec![SyncedItem::new(1, "Buy eggs".to_string(), false)];
+
+        assert_eq!(
+            merge(&document, &tasks, &[], &base),
+            vec![
+                Change::Conflict(1),
+                Change::RenameItem {
+                    id: 1,
+                    title: "Buy bread".to_string()
+                },
+            ]
+        );
+    }
+
+    // Add new items, link items to tasks of the same title, and delete on
+    // the other side what was deleted on one
+    #[test]
+    fn test_merge_added_and_deleted_items() {
+        let document: Document =
+            Document::parse("- [x] Write docs\n- [ ] call MOM\n- [ ] Old <!-- task:4 -->\n");
+        let tasks: Vec<Task> = vec![
+            sample_task(2, "Call mom", false),
+            sample_task(3, "Buy eggs", false),
+        ];
+        let base: Vec<SyncedItem> = vec![
+            SyncedItem::new(3, "Buy eggs".to_string(), false),
+            SyncedItem::new(4, "Old".to_string(), false),
+        ];
+
+        assert_eq!(
+            merge(&document, &tasks, &[], &base),
+            vec![
+                Change::AddTask {
+                    key: 0,
+                    title: "Write docs".to_string(),
+                    done: true
+                },
+                Change::LinkTask { key: 1, id: 2 },
+                Change::RenameTask {
+                    id: 2,
+                    title: "call MOM".to_string()
+                },
+                Change::RemoveItem(4),
+                Change::DeleteTask(3),
+            ]
+        );
+    }
+
+    // Leave the items of archived tasks in the file
+    #[test]
+    fn test_merge_archived_item() {
+        let document: Document = Document::parse("- [x] Ship v1 <!-- task:1 -->\n");
+        let archived: Vec<Task> = vec![sample_task(1, "Ship v1", true)];
+        let base: Vec<SyncedItem> = vec![SyncedItem::new(1, "Ship v1".to_string(), true)];
+
+        assert!(merge(&document, &[], &archived, &base).is_empty());
+        assert!(merge(&document, &[], &archived, &[]).is_empty());
+    }
+
+    // Only trust the id of an item without a base when the titles match
+    #[test]
+    fn test_merge_id_without_base() {
+        let document: Document =
+            Document::parse("- [x] Buy eggs <!-- task:1 -->\n- [ ] Call mom <!-- task:2 -->\n");
+        let tasks: Vec<Task> = vec![
+            sample_task(1, "Deploy production", false),
+            sample_task(2, "Call mom", false),
+        ];
+
+        assert_eq!(
+            merge(&document, &tasks, &[], &[]),
+            vec![Change::AddTask {
+                key: 0,
+                title: "Buy eggs".to_string(),
+                done: true
+            }]
+        );
+    }
+}
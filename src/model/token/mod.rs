@@ -0,0 +1,150 @@
+use crate::sha256;
+
+use json::{JsonValue, object};
+
+// What a token allows on the server: reading the tasks, or changing them too.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub enum Scope {
+    #[default]
+    Read,
+    ReadWrite,
+}
+
+impl From<&Scope> for String {
+    fn from(value: &Scope) -> Self {
+        match value {
+            Scope::Read => String::from("read"),
+            Scope::ReadWrite => String::from("read-write"),
+        }
+    }
+}
+
+impl TryFrom<String> for Scope {
+    type Error = String;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "read" => Ok(Scope::Read),
+            "read-write" => Ok(Scope::ReadWrite),
+            _ => Err(format!("Unknown token scope '{value}'.")),
+        }
+    }
+}
+
+// An API token of the server, owned by one user. Only the hash of its secret
+// is kept, so reading the tokens file does not give away the secrets.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Token {
+    owner: String,
+    secret_hash: String,
+    scope: Scope,
+    created_at: u64,
+}
+impl Token {
+    pub fn new(owner: String, secret_hash: String, scope: Scope, created_at: u64) -> Self {
+        Token {
+            owner,
+            secret_hash,
+            scope,
+            created_at,
+        }
+    }
+    pub fn get_owner(&self) -> &String {
+        &self.owner
+    }
+    pub fn get_secret_hash(&self) -> &String {
+        &self.secret_hash
+    }
+    pub fn get_scope(&self) -> &Scope {
+        &self.scope
+    }
+    pub fn get_created_at(&self) -> u64 {
+        self.created_at
+    }
+
+    pub fn can_write(&self) -> bool {
+        self.scope == Scope::ReadWrite
+    }
+
+    // Every byte of the hashes is compared, so the time taken does not tell
+    // how much of a guessed secret was right.
+    pub fn has_secret(&self, secret: &str) -> bool {
+        let hash: String = sha256::hex_digest(secret);
+        hash.len() == self.secret_hash.len()
+            && hash
+                .bytes()
+                .zip(self.secret_hash.bytes())
+                .fold(0, |difference, (left, right)| difference | (left ^ right))
+                == 0
+    }
+}
+
+impl From<&Token> for JsonValue {
+    fn from(token: &Token) -> JsonValue {
+        object! {
+            "owner" => token.get_owner().to_owned(),
+            "secret_hash" => token.get_secret_hash().to_owned(),
+            "scope" => String::from(token.get_scope()),
+            "created_at" => token.get_created_at()
+        }
+    }
+}
+
+impl TryFrom<&JsonValue> for Token {
+    type Error = String;
+    fn try_from(json_object: &JsonValue) -> Result<Self, Self::Error> {
+        let owner: String = json_object["owner"]
+            .as_str()
+            .ok_or("Can not parse token owner")?
+            .to_owned();
+        let secret_hash: String = json_object["secret_hash"]
+            .as_str()
+            .ok_or("Can not parse token secret hash")?
+            .to_owned();
+        let scope: Scope = Scope::try_from(json_object["scope"].to_string())?;
+        let created_at: u64 = json_object["created_at"].as_u64().unwrap_or(0);
+        Ok(Token::new(owner, secret_hash, scope, created_at))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Parse Scope from String
+    #[test]
+    fn test_parse_scope_from_string() {
+        assert_eq!(
+            Scope::try_from("read-write".to_string()),
+            Ok(Scope::ReadWrite)
+        );
+        assert!(Scope::try_from("admin".to_string()).is_err());
+    }
+
+    // Convert Token to JsonValue and back
+    #[test]
+    fn test_token_json_round_trip() {
+        let token: Token = Token::new(
+            "alice".to_string(),
+            sha256::hex_digest("tt_0123"),
+            Scope::Read,
+            1_790_000_000,
+        );
+
+        let json_value: JsonValue = JsonValue::from(&token);
+        assert_eq!(Token::try_from(&json_value), Ok(token));
+    }
+
+    // Check a secret against the hash of the token
+    #[test]
+    fn test_token_has_secret() {
+        let token: Token = Token::new(
+            "alice".to_string(),
+            sha256::hex_digest("tt_0123"),
+            Scope::Read,
+            1_790_000_000,
+        );
+
+        assert!(token.has_secret("tt_0123"));
+        assert!(!token.has_secret("tt_4567"));
+    }
+}
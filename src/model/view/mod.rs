@@ -0,0 +1,147 @@
+use super::sort::SortKey;
+
+use json::{JsonValue, object};
+
+// A task field that can be shown as a column of the list output.
+#[derive(PartialEq, Debug, Clone)]
+pub enum Column {
+    Id,
+    State,
+    Priority,
+    Tags,
+    Created,
+    Title,
+}
+
+impl From<&Column> for String {
+    fn from(value: &Column) -> Self {
+        match value {
+            Column::Id => String::from("id"),
+            Column::State => String::from("state"),
+            Column::Priority => String::from("priority"),
+            Column::Tags => String::from("tags"),
+            Column::Created => String::from("created"),
+            Column::Title => String::from("title"),
+        }
+    }
+}
+
+impl TryFrom<String> for Column {
+    type Error = String;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "id" => Ok(Column::Id),
+            "state" => Ok(Column::State),
+            "priority" => Ok(Column::Priority),
+            "tags" => Ok(Column::Tags),
+            "created" => Ok(Column::Created),
+            "title" => Ok(Column::Title),
+            _ => Err(format!("Unknown column '{value}'.")),
+        }
+    }
+}
+
+// A named filter query together with the columns and the order to list the
+// tasks with. Empty columns and sort keys keep the default output.
+#[derive(PartialEq, Debug, Clone)]
+pub struct View {
+    name: String,
+    query: Option<String>,
+    columns: Vec<Column>,
+    sort: Vec<SortKey>,
+}
+impl View {
+    pub fn new(
+        name: String,
+        query: Option<String>,
+        columns: Vec<Column>,
+        sort: Vec<SortKey>,
+    ) -> Self {
+        View {
+            name,
+            query,
+            columns,
+            sort,
+        }
+    }
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+    pub fn get_query(&self) -> Option<&String> {
+        self.query.as_ref()
+    }
+    pub fn get_columns(&self) -> &Vec<Column> {
+        &self.columns
+    }
+    pub fn get_sort(&self) -> &Vec<SortKey> {
+        &self.sort
+    }
+}
+
+impl From<&View> for JsonValue {
+    fn from(view: &View) -> JsonValue {
+        let columns: Vec<String> = view.get_columns().iter().map(String::from).collect();
+        let sort: Vec<String> = view.get_sort().iter().map(String::from).collect();
+        object! {
+            "name" => view.get_name().to_owned(),
+            "query" => view.get_query().cloned(),
+            "columns" => columns,
+            "sort" => sort
+        }
+    }
+}
+
+impl TryFrom<&JsonValue> for View {
+    type Error = String;
+    fn try_from(json_object: &JsonValue) -> Result<Self, Self::Error> {
+        let name: String = json_object["name"]
+            .as_str()
+            .ok_or("Can not parse view name")?
+            .to_owned();
+        let query: Option<String> = json_object["query"].as_str().map(str::to_owned);
+        let mut columns: Vec<Column> = vec![];
+        for json_value in json_object["columns"].members() {
+            columns.push(Column::try_from(json_value.to_string())?);
+        }
+        // Views saved before sorting existed have no sort keys.
+        let mut sort: Vec<SortKey> = vec![];
+        for json_value in json_object["sort"].members() {
+            sort.push(SortKey::try_from(json_value.to_string())?);
+        }
+        Ok(View::new(name, query, columns, sort))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Parse Column from String
+    #[test]
+    fn test_parse_column_from_string() {
+        assert_eq!(
+            Column::Title,
+            Column::try_from("title".to_string()).unwrap()
+        );
+    }
+
+    // Parse Column from invalid String
+    #[test]
+    fn test_parse_column_from_invalid_string() {
+        assert!(Column::try_from("owner".to_string()).is_err());
+    }
+
+    // Convert View to JsonValue and back
+    #[test]
+    fn test_view_json_round_trip() {
+        let view: View = View::new(
+            "standup".to_string(),
+            Some("state:in-progress tag:team".to_string()),
+            vec![Column::Id, Column::Title],
+            vec![SortKey::new(Column::Priority, true)],
+        );
+
+        let json_value: JsonValue = JsonValue::from(&view);
+        assert_eq!(View::try_from(&json_value), Ok(view));
+    }
+}
@@ -0,0 +1,119 @@
+use crate::model::{Task, TaskState};
+
+use std::fmt::Write as _;
+
+// The narrowest a column gets, however small the terminal is.
+const MIN_COLUMN_WIDTH: usize = 12;
+const SEPARATOR: &str = " │ ";
+
+// A board column: the state it holds, its WIP limit and its tasks.
+pub struct BoardColumn<'a> {
+    state: TaskState,
+    limit: Option<usize>,
+    tasks: Vec<&'a Task>,
+}
+impl<'a> BoardColumn<'a> {
+    pub fn new(state: TaskState, limit: Option<usize>, tasks: Vec<&'a Task>) -> Self {
+        BoardColumn {
+            state,
+            limit,
+            tasks,
+        }
+    }
+    pub fn is_over_limit(&self) -> bool {
+        self.limit.is_some_and(|limit| self.tasks.len() > limit)
+    }
+
+    // The column title with the count of tasks, and the limit if any
+    // (e.g.: 'IN-PROGRESS 4/3 !').
+    fn header(&self) -> String {
+        let state: String = String::from(&self.state).to_uppercase();
+        let count: usize = self.tasks.len();
+        match self.limit {
+            Some(limit) if self.is_over_limit() => format!("{state} {count}/{limit} !"),
+            Some(limit) => format!("{state} {count}/{limit}"),
+            None => format!("{state} {count}"),
+        }
+    }
+}
+
+// Lays the columns out side by side within the given width, one task per
+// line with its title cut to fit.
+pub fn render(columns: &[BoardColumn], width: usize) -> String {
+    let separators: usize = SEPARATOR.chars().count() * columns.len().saturating_sub(1);
+    let column_width: usize =
+        (width.saturating_sub(separators) / columns.len().max(1)).max(MIN_COLUMN_WIDTH);
+    let rows: usize = columns
+        .iter()
+        .map(|column| column.tasks.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut output: String = String::new();
+    let headers: Vec<String> = columns
+        .iter()
+        .map(|column| truncate(&column.header(), column_width))
+        .collect();
+    write_row(&mut output, &headers, column_width);
+    let rules: Vec<String> = columns.iter().map(|_| "─".repeat(column_width)).collect();
+    write_row(&mut output, &rules, column_width);
+    for row in 0..rows {
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|column| match column.tasks.get(row) {
+                Some(task) => {
+                    let card: String = format!("#{} {}", task.get_id(), task.get_task());
+                    truncate(&card, column_width)
+                }
+                None => String::new(),
+            })
+            .collect();
+        write_row(&mut output, &cells, column_width);
+    }
+    output
+}
+
+fn write_row(output: &mut String, cells: &[String], column_width: usize) {
+    let cells: Vec<String> = cells
+        .iter()
+        .map(|cell| format!("{cell:<column_width$}"))
+        .collect();
+    let _ = writeln!(output, "{}", cells.join(SEPARATOR).trim_end());
+}
+
+// Cuts a text to the given number of characters, marking the cut with '…'.
+pub fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_owned();
+    }
+    let mut truncated: String = text.chars().take(width.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_marks_cut_titles() {
+        assert_eq!(truncate("Fix the login bug", 8), "Fix the…");
+        assert_eq!(truncate("Fix", 8), "Fix");
+    }
+
+    #[test]
+    fn test_render_lays_columns_side_by_side() {
+        let first: Task = Task::new(1, "Write docs".to_string(), TaskState::NotStarted);
+        let second: Task = Task::new(2, "Fix the logins page".to_string(), TaskState::InProgress);
+        let columns: Vec<BoardColumn> = vec![
+            BoardColumn::new(TaskState::NotStarted, None, vec![&first]),
+            BoardColumn::new(TaskState::InProgress, Some(0), vec![&second]),
+        ];
+
+        let board: String = render(&columns, 40);
+        let lines: Vec<&str> = board.lines().collect();
+
+        assert_eq!(lines[0], "NOT-STARTED 1      │ IN-PROGRESS 1/0 !");
+        assert_eq!(lines[2], "#1 Write docs      │ #2 Fix the logins…");
+    }
+}
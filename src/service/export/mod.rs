@@ -0,0 +1,309 @@
+use crate::csv;
+use crate::date;
+use crate::model::milestone::Milestone;
+use crate::model::{Priority, Task, TaskState};
+
+use std::fmt::Write as _;
+
+// The longest iCalendar line, in bytes, before it is folded.
+const ICS_LINE_LENGTH: usize = 75;
+
+// Renders a checklist per state, in the order of the workflow, with the tags
+// as code and the notes as nested items.
+pub fn render_markdown(tasks: &[Task]) -> String {
+    let mut output: String = String::from("# Tasks\n");
+    for (state, heading) in [
+        (TaskState::NotStarted, "Not started"),
+        (TaskState::InProgress, "In progress"),
+        (TaskState::Done, "Done"),
+    ] {
+        let state_tasks: Vec<&Task> = tasks
+            .iter()
+            .filter(|task| task.get_state() == &state)
+            .collect();
+        if state_tasks.is_empty() {
+            continue;
+        }
+        let _ = writeln!(output, "\n## {heading}\n");
+        for task in state_tasks {
+            let checkbox: &str = match task.get_state() {
+                TaskState::Done => "[x]",
+                _ => "[ ]",
+            };
+            let mut line: String = format!("- {checkbox} {}", task.get_task());
+            if let Some(key) = task.get_key() {
+                let _ = write!(line, " ({key})");
+            }
+            for tag in task.get_tags() {
+                let _ = write!(line, " `{tag}`");
+            }
+            let _ = writeln!(output, "{line}");
+            for note in task.get_notes() {
+                let _ = writeln!(output, "  - {note}");
+            }
+        }
+    }
+    output
+}
+
+// Renders a todo.txt line per task, the tags as contexts. Completed tasks
+// keep their priority as a 'pri:' tag, as the format asks.
+pub fn render_todotxt(tasks: &[Task]) -> String {
+    let mut output: String = String::new();
+    for task in tasks {
+        let letter: char = match task.get_priority() {
+            Priority::High => 'A',
+            Priority::Medium => 'B',
+            Priority::Low => 'C',
+        };
+        let created: String = date::format_date(task.get_created_at());
+        // The creation date can only follow a completion date.
+        let mut line: String = match (task.get_state(), task.get_done_at()) {
+            (TaskState::Done, Some(done_at)) => {
+                format!("x {} {created} ", date::format_date(done_at))
+            }
+            (TaskState::Done, None) => String::from("x "),
+            _ => format!("({letter}) {created} "),
+        };
+        line.push_str(task.get_task());
+        if let Some(project) = task.get_project() {
+            let _ = write!(line, " +{project}");
+        }
+        for tag in task.get_tags() {
+            let _ = write!(line, " @{tag}");
+        }
+        if task.get_state() == &TaskState::Done {
+            let _ = write!(line, " pri:{letter}");
+        }
+        let _ = writeln!(output, "{line}");
+    }
+    output
+}
+
+// Renders a row per task, with the columns 'import csv' reads by default.
+pub fn render_csv(tasks: &[Task]) -> String {
+    let header: Vec<String> = [
+        "id",
+        "title",
+        "state",
+        "priority",
+        "tags",
+        "project",
+        "milestone",
+        "created",
+        "notes",
+    ]
+    .iter()
+    .map(|column| (*column).to_owned())
+    .collect();
+    let mut output: String = csv::format_record(&header);
+    output.push('\n');
+    for task in tasks {
+        let record: Vec<String> = vec![
+            task.get_id().to_string(),
+            task.get_task().to_owned(),
+            String::from(task.get_state()),
+            String::from(task.get_priority()),
+            task.get_tags().join(";"),
+            task.get_project().cloned().unwrap_or_default(),
+            task.get_milestone().cloned().unwrap_or_default(),
+            date::format_date(task.get_created_at()),
+            task.get_notes().join("\n"),
+        ];
+        let _ = writeln!(output, "{}", csv::format_record(&record));
+    }
+    output
+}
+
+// Renders a calendar with a VTODO per task. Tasks planned for a milestone
+// are due on its target date.
+pub fn render_ics(tasks: &[Task], milestones: &[Milestone], now: u64) -> String {
+    let mut lines: Vec<String> = vec![
+        "BEGIN:VCALENDAR".to_owned(),
+        "VERSION:2.0".to_owned(),
+        "PRODID:-//roadmap-task-tracker//EN".to_owned(),
+    ];
+    for task in tasks {
+        lines.push("BEGIN:VTODO".to_owned());
+        lines.push(format!("UID:task-{}@roadmap-task-tracker", task.get_id()));
+        lines.push(format!("DTSTAMP:{}", format_ics_time(now)));
+        lines.push(format!(
+            "CREATED:{}",
+            format_ics_time(task.get_created_at())
+        ));
+        lines.push(format!("SUMMARY:{}", escape_ics_text(task.get_task())));
+        lines.push(format!("STATUS:{}", ics_status(task.get_state())));
+        let priority: u8 = match task.get_priority() {
+            Priority::High => 1,
+            Priority::Medium => 5,
+            Priority::Low => 9,
+        };
+        lines.push(format!("PRIORITY:{priority}"));
+        if let Some(done_at) = task.get_done_at()
+            && task.get_state() == &TaskState::Done
+        {
+            lines.push(format!("COMPLETED:{}", format_ics_time(done_at)));
+        }
+        if let Some(milestone) = task.get_milestone().and_then(|name| {
+            milestones
+                .iter()
+                .find(|milestone| milestone.get_name() == name)
+        }) {
+            let due: String = date::format_date(milestone.get_target_date()).replace('-', "");
+            lines.push(format!("DUE;VALUE=DATE:{due}"));
+        }
+        if !task.get_tags().is_empty() {
+            let tags: Vec<String> = task
+                .get_tags()
+                .iter()
+                .map(|tag| escape_ics_text(tag))
+                .collect();
+            lines.push(format!("CATEGORIES:{}", tags.join(",")));
+        }
+        if !task.get_notes().is_empty() {
+            let notes: String = escape_ics_text(&task.get_notes().join("\n"));
+            lines.push(format!("DESCRIPTION:{notes}"));
+        }
+        lines.push("END:VTODO".to_owned());
+    }
+    lines.push("END:VCALENDAR".to_owned());
+    lines
+        .iter()
+        .map(|line| format!("{}\r\n", fold_ics_line(line)))
+        .collect()
+}
+
+fn ics_status(state: &TaskState) -> &'static str {
+    match state {
+        TaskState::NotStarted => "NEEDS-ACTION",
+        TaskState::InProgress => "IN-PROCESS",
+        TaskState::Done => "COMPLETED",
+    }
+}
+
+// Formats a unix timestamp as an iCalendar time in UTC (e.g.:
+// '20261001T093000Z').
+fn format_ics_time(timestamp: u64) -> String {
+    let day: String = date::format_date(timestamp).replace('-', "");
+    let seconds: u64 = timestamp % date::SECONDS_PER_DAY;
+    format!(
+        "{day}T{:02}{:02}{:02}Z",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+fn escape_ics_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+// Folds a line longer than the iCalendar limit into continuation lines
+// starting with a space, without splitting characters.
+fn fold_ics_line(line: &str) -> String {
+    let mut folded: String = String::new();
+    let mut length: usize = 0;
+    for character in line.chars() {
+        if length + character.len_utf8() > ICS_LINE_LENGTH {
+            folded.push_str("\r\n ");
+            // The space counts towards the continuation line.
+            length = 1;
+        }
+        folded.push(character);
+        length += character.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_tasks() -> Vec<Task> {
+        let mut open: Task = Task::new(1, "Fix login, again".to_string(), TaskState::NotStarted);
+        open.set_priority(Priority::High);
+        open.add_tag("bug".to_string());
+        open.set_milestone(Some("v1.2".to_string()));
+        let mut done: Task = Task::new(2, "Write docs".to_string(), TaskState::NotStarted);
+        done.set_state("done".to_string());
+        done.add_note("Ask for review".to_string());
+        vec![open, done]
+    }
+
+    // Group the checklist by state
+    #[test]
+    fn test_render_markdown() {
+        let output: String = render_markdown(&sample_tasks());
+
+        assert_eq!(
+            output,
+            "# Tasks\n\n## Not started\n\n- [ ] Fix login, again `bug`\n\n## Done\n\n- [x] Write docs\n  - Ask for review\n"
+        );
+    }
+
+    // Write completed tasks with their dates and priority tag
+    #[test]
+    fn test_render_todotxt() {
+        let tasks: Vec<Task> = sample_tasks();
+        let today: String = date::format_date(date::now());
+
+        let output: String = render_todotxt(&tasks);
+
+        assert_eq!(
+            output,
+            format!("(A) {today} Fix login, again @bug\nx {today} {today} Write docs pri:B\n")
+        );
+    }
+
+    // Write the header read by the import, and quote the titles
+    #[test]
+    fn test_render_csv() {
+        let output: String = render_csv(&sample_tasks());
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "id,title,state,priority,tags,project,milestone,created,notes"
+        );
+        assert!(lines[1].starts_with("1,\"Fix login, again\",not-started,high,bug,,v1.2,"));
+    }
+
+    // Map the states, and the milestones to due dates
+    #[test]
+    fn test_render_ics() {
+        let milestones: Vec<Milestone> = vec![Milestone::new(
+            "v1.2".to_string(),
+            date::parse_date("2026-12-01").unwrap(),
+            String::new(),
+        )];
+
+        let output: String = render_ics(&sample_tasks(), &milestones, 0);
+
+        assert!(output.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(output.contains("SUMMARY:Fix login\\, again\r\n"));
+        assert!(
+            output.contains("STATUS:NEEDS-ACTION\r\nPRIORITY:1\r\nDUE;VALUE=DATE:20261201\r\n")
+        );
+        assert!(output.contains("STATUS:COMPLETED\r\n"));
+        assert!(output.contains("DTSTAMP:19700101T000000Z\r\n"));
+        assert!(output.ends_with("END:VCALENDAR\r\n"));
+    }
+
+    // Fold long lines at 75 bytes
+    #[test]
+    fn test_fold_ics_line() {
+        let line: String = format!("SUMMARY:{}", "é".repeat(40));
+
+        let folded: String = fold_ics_line(&line);
+
+        assert!(
+            folded
+                .split("\r\n")
+                .all(|part| part.len() <= ICS_LINE_LENGTH)
+        );
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+}
@@ -1,67 +1,1230 @@
+pub mod board;
+pub mod export;
+pub mod report;
+pub mod rpc;
+pub mod server;
+pub mod shell;
+pub mod terminal;
+pub mod tui;
+
+use crate::cli;
+use crate::config::Config;
+use crate::date;
 use crate::model;
+use crate::model::export::ExportFormat;
+use crate::model::filter::{self, Filter, Selection, TaskReference};
+use crate::model::import::csv::ColumnMapping;
+use crate::model::import::{self, ImportFormat, ImportedTask};
+use crate::model::milestone::Milestone;
+use crate::model::project::{self, Project};
+use crate::model::report::{BurndownDay, ReportFormat, burndown};
+use crate::model::search::{
+    SUBSEQUENCE_MATCH, SUBSTRING_MATCH, SearchHit, SearchTerm, fuzzy_match,
+};
+use crate::model::sort::ListOptions;
+use crate::model::sort::SortKey;
+use crate::model::stats::Stats;
+use crate::model::sync::{self, Change, Document, SyncedItem};
+use crate::model::token::Scope;
+use crate::model::view::{Column, View};
 use crate::store;
+use crate::store::format_ids;
+use crate::store::sync_bases::SyncBases;
+use crate::store::tokens::Tokens;
+
+use json::JsonValue;
+
+use std::any::Any;
+use std::env;
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io::{self, IsTerminal, Write};
+use std::process::{self, Stdio};
+use std::vec;
 
 pub struct TaskService<'a> {
     store: &'a mut dyn store::TaskStore,
+    config: &'a Config,
 }
 
 impl<'a> TaskService<'a> {
-    pub fn new(store: &'a mut dyn store::TaskStore) -> Self {
-        TaskService { store }
+    pub fn new(store: &'a mut dyn store::TaskStore, config: &'a Config) -> Self {
+        TaskService { store, config }
     }
 
-    pub fn list(self, state_filter: String) {
-        let tasks: Vec<model::Task> = self.store.get_tasks(state_filter);
-        for task in tasks {
+    // Runs a command parsed from the command line, or typed in the shell.
+    pub fn execute(self, command: cli::Command) {
+        match command {
+            cli::Command::List((filter, options)) => self.list(filter, options),
+            cli::Command::ListArchived(options) => self.list_archived(options),
+            cli::Command::ListView((name, options)) => self.list_view(name, options),
+            cli::Command::Board(filter) => self.board(filter),
+            cli::Command::ViewSave(view) => self.save_view(view),
+            cli::Command::ViewList => self.list_views(),
+            cli::Command::ViewDelete(name) => self.delete_view(name),
+            cli::Command::Search(terms) => self.search(terms),
+            cli::Command::Add((task, milestone, project)) => self.add(task, milestone, project),
+            cli::Command::Show(reference) => self.show(reference),
+            cli::Command::Update((reference, updated_task)) => self.update(reference, updated_task),
+            cli::Command::Delete(selection) => self.delete(selection),
+            cli::Command::Mark((selection, state)) => self.mark(selection, state),
+            cli::Command::Priority((selection, priority)) => self.prioritize(selection, priority),
+            cli::Command::Tag((selection, tag)) => self.tag(selection, tag),
+            cli::Command::Note((reference, note)) => self.note(reference, note),
+            cli::Command::Untag((selection, tag)) => self.untag(selection, tag),
+            cli::Command::Plan((selection, milestone)) => self.plan(selection, milestone),
+            cli::Command::Unplan(selection) => self.unplan(selection),
+            cli::Command::MilestoneAdd(milestone) => self.add_milestone(milestone),
+            cli::Command::MilestoneDelete(name) => self.delete_milestone(name),
+            cli::Command::Roadmap => self.roadmap(),
+            cli::Command::Trash => self.trash(),
+            cli::Command::Restore(task_id) => self.restore(task_id),
+            cli::Command::Purge(older_than) => self.purge(older_than),
+            cli::Command::Archive(older_than) => self.archive(older_than),
+            cli::Command::Unarchive(task_id) => self.unarchive(task_id),
+            cli::Command::Stats((json, project)) => self.stats(json, project),
+            cli::Command::ProjectAdd(name) => self.add_project(name),
+            cli::Command::ProjectList => self.list_projects(),
+            cli::Command::ReportBurndown((since, format)) => self.report_burndown(since, format),
+            cli::Command::Tui => self.tui(),
+            cli::Command::Shell => self.shell(),
+            cli::Command::Serve(bind) => self.serve(bind),
+            cli::Command::TokenCreate((owner, scope)) => self.create_token(owner, scope),
+            cli::Command::TokenList => self.list_tokens(),
+            cli::Command::TokenRevoke(owner) => self.revoke_token(owner),
+            cli::Command::Rpc => self.rpc(),
+            cli::Command::Import((format, path, mapping, dry_run)) => {
+                self.import(format, path, mapping, dry_run)
+            }
+            cli::Command::Export((format, filter)) => self.export(format, filter),
+            cli::Command::SyncMarkdown(path) => self.sync_markdown(path),
+            cli::Command::Undo => self.undo(),
+            cli::Command::Redo => self.redo(),
+            cli::Command::Unknown => println!("Unknown command called"),
+        }
+    }
+
+    pub fn list(self, filter: Option<Filter>, options: ListOptions) {
+        let tasks: Vec<model::Task> = match filter {
+            Some(filter) => self.store.find_tasks(&filter),
+            None => self.store.get_tasks(),
+        };
+        page(&format_tasks(&options.apply(tasks), &[]));
+    }
+
+    // Lists the tasks of a view. Sort keys given on the command line replace
+    // the ones saved with the view.
+    pub fn list_view(self, name: String, mut options: ListOptions) {
+        let Some(view) = self.store.get_view(&name) else {
+            println!("No view found with name '{name}'");
+            return;
+        };
+        let tasks: Vec<model::Task> = match view.get_query() {
+            Some(query) => {
+                let filter: Filter =
+                    filter::query::parse_filter(query).unwrap_or_else(|error| panic!("{error}"));
+                self.store.find_tasks(&filter)
+            }
+            None => self.store.get_tasks(),
+        };
+        if options.get_sort().is_empty() {
+            options.set_sort(view.get_sort().clone());
+        }
+        page(&format_tasks(&options.apply(tasks), view.get_columns()));
+    }
+
+    pub fn save_view(self, view: View) {
+        let name: String = view.get_name().to_owned();
+        self.store.save_view(view);
+        println!("Successfully saved view '{name}'");
+    }
+
+    pub fn list_views(self) {
+        for view in self.store.get_views() {
+            let name: &String = view.get_name();
+            let query: &str = view.get_query().map_or("(all tasks)", String::as_str);
+            let mut line: String = format!("@{name}: {query}");
+            let columns: Vec<String> = view.get_columns().iter().map(String::from).collect();
+            if !columns.is_empty() {
+                let columns: String = columns.join(",");
+                line.push_str(&format!(" [columns: {columns}]"));
+            }
+            let sort: Vec<String> = view.get_sort().iter().map(String::from).collect();
+            if !sort.is_empty() {
+                let sort: String = sort.join(",");
+                line.push_str(&format!(" [sort: {sort}]"));
+            }
+            println!("{line}");
+        }
+    }
+
+    pub fn delete_view(self, name: String) {
+        match self.store.remove_view(&name) {
+            true => println!("Successfully deleted view '{name}'"),
+            false => println!("Can not delete view '{name}'"),
+        };
+    }
+
+    pub fn search(self, terms: Vec<SearchTerm>) {
+        let hits: Vec<SearchHit> = self.store.search(&terms);
+        if hits.is_empty() {
+            println!("No tasks found");
+        }
+        for hit in hits {
+            print!(
+                "{}",
+                format_highlighted_task(hit.get_task(), hit.get_matched_words())
+            );
+        }
+    }
+
+    pub fn list_archived(self, options: ListOptions) {
+        let tasks: Vec<model::Task> = self.store.get_archived_tasks();
+        page(&format_tasks(&options.apply(tasks), &[]));
+    }
+
+    pub fn add(self, task: String, milestone: Option<String>, project: Option<String>) {
+        if let Some(milestone) = &milestone
+            && self.store.get_milestone(milestone).is_none()
+        {
+            println!("No milestone found with name '{milestone}'");
+            return;
+        }
+        if let Some(project) = &project
+            && self.store.get_project(project).is_none()
+        {
+            println!("No project found with name '{project}'");
+            return;
+        }
+        let id: u8 = self
+            .store
+            .add_task(task, milestone, project)
+            .unwrap_or_else(|error| panic!("{error}"));
+        match self.store.get_task(id).and_then(model::Task::get_key) {
+            Some(key) => println!("Task added with id #{id} ({key})."),
+            None => println!("Task added with id #{id}."),
+        }
+    }
+
+    pub fn add_project(self, name: String) {
+        let project: Project = Project::new(name.clone()).unwrap_or_else(|error| panic!("{error}"));
+        match self.store.add_project(project) {
+            true => println!("Successfully added project '{name}'"),
+            false => println!("Can not add project '{name}', it already exists"),
+        };
+    }
+
+    pub fn list_projects(self) {
+        let tasks: Vec<model::Task> = self.store.get_tasks();
+        for project in self.store.get_projects() {
+            let name: &String = project.get_name();
+            let prefix: String = name.to_uppercase();
+            let project_tasks: Vec<&model::Task> = tasks
+                .iter()
+                .filter(|task| task.get_project() == Some(name))
+                .collect();
+            let count: usize = project_tasks.len();
+            let open: usize = project_tasks
+                .iter()
+                .filter(|task| task.get_state() != &model::TaskState::Done)
+                .count();
+            println!("{name} ({prefix}-*): {count} task(s), {open} open");
+        }
+    }
+
+    pub fn show(self, reference: TaskReference) {
+        let Some(id) = self.resolve(reference, SUBSEQUENCE_MATCH) else {
+            return;
+        };
+        let task: Option<&model::Task> = self.store.get_task(id);
+        match task {
+            Some(task) => print_task(task),
+            None => println!("No task found with id {id}"),
+        }
+    }
+
+    pub fn update(self, reference: TaskReference, updated_task: String) {
+        let Some(id) = self.resolve(reference, SUBSEQUENCE_MATCH) else {
+            return;
+        };
+        match self.store.update_task(id, updated_task.clone()) {
+            true => println!("Successfully updated task #{id}"),
+            false => println!("Can not update task #{id}"),
+        };
+    }
+
+    pub fn delete(self, selection: Selection) {
+        if let Some(ids) = self.select(selection, "delete", SUBSTRING_MATCH) {
+            let deleted: Vec<u8> = self.store.remove_tasks(&ids);
+            print_summary("deleted", "delete", &ids, &deleted);
+        }
+    }
+
+    pub fn mark(self, selection: Selection, state: model::TaskState) {
+        if let Some(ids) = self.select(selection, "mark", SUBSTRING_MATCH) {
+            let marked: Vec<u8> = self.store.set_state(&ids, String::from(&state));
+            print_summary("marked", "mark", &ids, &marked);
+            if !marked.is_empty()
+                && let Some(warning) = wip_warning(&*self.store, self.config, state)
+            {
+                println!("{warning}");
+            }
+        }
+    }
+
+    // Shows the tasks in a column per state, the most important first.
+    pub fn board(self, filter: Option<Filter>) {
+        const DEFAULT_WIDTH: usize = 80;
+        let tasks: Vec<model::Task> = match filter {
+            Some(filter) => self.store.find_tasks(&filter),
+            None => self.store.get_tasks(),
+        };
+        let order: ListOptions = ListOptions::new(
+            vec![
+                SortKey::new(Column::Priority, true),
+                SortKey::new(Column::Id, false),
+            ],
+            None,
+            0,
+        );
+        let tasks: Vec<model::Task> = order.apply(tasks);
+        let columns: Vec<board::BoardColumn> = board_columns(&tasks, self.config);
+        let width: usize = terminal_width().unwrap_or(DEFAULT_WIDTH);
+        page(&board::render(&columns, width));
+    }
+
+    pub fn prioritize(self, selection: Selection, priority: model::Priority) {
+        if let Some(ids) = self.select(selection, "prioritize", SUBSEQUENCE_MATCH) {
+            let prioritized: Vec<u8> = self.store.set_priority(&ids, priority);
+            print_summary("prioritized", "prioritize", &ids, &prioritized);
+        }
+    }
+
+    pub fn tag(self, selection: Selection, tag: String) {
+        if let Some(ids) = self.select(selection, "tag", SUBSEQUENCE_MATCH) {
+            let tagged: Vec<u8> = self.store.tag_tasks(&ids, tag);
+            print_summary("tagged", "tag", &ids, &tagged);
+        }
+    }
+
+    pub fn note(self, reference: TaskReference, note: String) {
+        let Some(id) = self.resolve(reference, SUBSEQUENCE_MATCH) else {
+            return;
+        };
+        match self.store.add_note(id, note) {
+            true => println!("Successfully added a note to task #{id}"),
+            false => println!("Can not add a note to task #{id}"),
+        };
+    }
+
+    pub fn untag(self, selection: Selection, tag: String) {
+        if let Some(ids) = self.select(selection, "untag", SUBSEQUENCE_MATCH) {
+            let untagged: Vec<u8> = self.store.untag_tasks(&ids, tag);
+            print_summary("untagged", "untag", &ids, &untagged);
+        }
+    }
+
+    pub fn plan(self, selection: Selection, milestone: String) {
+        if self.store.get_milestone(&milestone).is_none() {
+            println!("No milestone found with name '{milestone}'");
+            return;
+        }
+        if let Some(ids) = self.select(selection, "plan", SUBSEQUENCE_MATCH) {
+            let planned: Vec<u8> = self.store.set_milestone(&ids, Some(milestone));
+            print_summary("planned", "plan", &ids, &planned);
+        }
+    }
+
+    pub fn unplan(self, selection: Selection) {
+        if let Some(ids) = self.select(selection, "unplan", SUBSEQUENCE_MATCH) {
+            let unplanned: Vec<u8> = self.store.set_milestone(&ids, None);
+            print_summary("unplanned", "unplan", &ids, &unplanned);
+        }
+    }
+
+    pub fn add_milestone(self, milestone: Milestone) {
+        let name: String = milestone.get_name().to_owned();
+        self.store.save_milestone(milestone);
+        println!("Successfully saved milestone '{name}'");
+    }
+
+    pub fn delete_milestone(self, name: String) {
+        match self.store.remove_milestone(&name) {
+            true => println!("Successfully deleted milestone '{name}'"),
+            false => println!("Can not delete milestone '{name}'"),
+        };
+    }
+
+    // Lists the milestones by target date with their progress and open tasks.
+    // Archived tasks count as done work of their milestone.
+    pub fn roadmap(self) {
+        const PROGRESS_WIDTH: usize = 20;
+        let mut milestones: Vec<Milestone> = self.store.get_milestones();
+        if milestones.is_empty() {
+            println!("No milestones found");
+            return;
+        }
+        milestones.sort_by_key(Milestone::get_target_date);
+        let mut tasks: Vec<model::Task> = self.store.get_tasks();
+        tasks.extend(self.store.get_archived_tasks());
+        let now: u64 = date::now();
+
+        for milestone in milestones {
+            let name: &String = milestone.get_name();
+            let planned: Vec<&model::Task> = tasks
+                .iter()
+                .filter(|task| task.get_milestone() == Some(name))
+                .collect();
+            let open: Vec<&model::Task> = planned
+                .iter()
+                .filter(|task| task.get_state() != &model::TaskState::Done)
+                .copied()
+                .collect();
+            let total: usize = planned.len();
+            let done: usize = total - open.len();
+            let filled: usize = match total {
+                0 => 0,
+                total => done * PROGRESS_WIDTH / total,
+            };
+            let progress: String = format!(
+                "{}{}",
+                "█".repeat(filled),
+                "░".repeat(PROGRESS_WIDTH - filled)
+            );
+            let percent: usize = match total {
+                0 => 0,
+                total => done * 100 / total,
+            };
+            let target_date: String = date::format_date(milestone.get_target_date());
+            println!("{name}  due {target_date}  [{progress}] {percent}% ({done}/{total} done)");
+            let description: &String = milestone.get_description();
+            if !description.is_empty() {
+                println!("  {description}");
+            }
+            // The target date is met until the end of that day.
+            let deadline: u64 = milestone.get_target_date() + date::SECONDS_PER_DAY;
+            if now > deadline && !open.is_empty() {
+                let days_late: u64 = (now - deadline) / date::SECONDS_PER_DAY + 1;
+                let open_count: usize = open.len();
+                println!(
+                    "  Warning: {days_late} day(s) past the target date with {open_count} open task(s)"
+                );
+            }
+            for task in open {
+                let task_id: u8 = task.get_id();
+                let task_state: String = String::from(task.get_state());
+                let task_content: &String = task.get_task();
+                println!("  - #{task_id} [{task_state}] {task_content}");
+            }
+            println!();
+        }
+    }
+
+    pub fn trash(self) {
+        let trash: Vec<model::TrashedTask> = self.store.get_trash();
+        for trashed_task in trash {
+            let task: &model::Task = trashed_task.get_task();
             let task_id: u8 = task.get_id();
             println!("### {task_id} ###");
             let task_state: &model::TaskState = task.get_state();
             println!("State: {task_state:?}");
+            let deleted_at: String = date::format_date(trashed_task.get_deleted_at());
+            println!("Deleted: {deleted_at}");
             let task_content: &String = task.get_task();
             println!("{task_content}");
             println!()
         }
     }
 
-    pub fn add(self, task: String) {
-        let id: u8 = self.store.add_task(task);
-        println!("Task added with id #{id}.");
+    pub fn restore(self, id: u8) {
+        match self.store.restore_task(id) {
+            true => println!("Successfully restored task #{id}"),
+            false => println!("Can not restore task #{id}"),
+        };
     }
 
-    pub fn show(self, id: u8) {
-        let task: Option<&model::Task> = self.store.get_task(id);
-        match task {
-            Some(task) => {
+    pub fn purge(self, older_than: Option<u64>) {
+        let purged: Vec<u8> = self.store.purge_trash(older_than);
+        let count: usize = purged.len();
+        println!("Permanently deleted {count} task(s) from the trash");
+    }
+
+    pub fn archive(self, older_than: Option<u64>) {
+        let archived: Vec<u8> = self.store.archive_tasks(older_than);
+        let count: usize = archived.len();
+        println!("Archived {count} done task(s)");
+    }
+
+    pub fn unarchive(self, id: u8) {
+        match self.store.unarchive_task(id) {
+            true => println!("Successfully unarchived task #{id}"),
+            false => println!("Can not unarchive task #{id}"),
+        };
+    }
+
+    // Reports on the tasks, the archived ones included so that completed work
+    // still counts after archiving.
+    pub fn stats(self, json: bool, project: Option<String>) {
+        let mut tasks: Vec<model::Task> = self.store.get_tasks();
+        tasks.extend(self.store.get_archived_tasks());
+        if let Some(project) = project {
+            if self.store.get_project(&project.to_lowercase()).is_none() {
+                println!("No project found with name '{project}'");
+                return;
+            }
+            let filter: Filter = Filter::Project(project);
+            tasks.retain(|task| filter.matches(task));
+        }
+        let stats: Stats = Stats::compute(&tasks, date::now());
+        if json {
+            println!("{}", json::stringify_pretty(JsonValue::from(&stats), 2));
+            return;
+        }
+
+        let total: usize = stats.get_total();
+        println!("Tasks: {total}");
+        println!();
+        println!("By state:");
+        for (state, count) in stats.get_states() {
+            let state: String = String::from(state);
+            println!("  {state:<12} {count}");
+        }
+        println!();
+        println!("By priority:");
+        for (priority, count) in stats.get_priorities() {
+            let priority: String = String::from(priority);
+            println!("  {priority:<12} {count}");
+        }
+        if !stats.get_tags().is_empty() {
+            println!();
+            println!("By tag:");
+            for (tag, count) in stats.get_tags() {
+                println!("  {tag:<12} {count}");
+            }
+        }
+        println!();
+        println!("Completion by week:");
+        for week in stats.get_weeks() {
+            let start: String = date::format_date(week.get_start());
+            let created: usize = week.get_created();
+            let completed: usize = week.get_completed();
+            let rate: f64 = week.get_completion_rate() * 100.0;
+            println!("  {start}  created {created:<3} completed {completed:<3} done {rate:.0}%");
+        }
+        println!();
+        println!("Average time in state:");
+        for (state, average) in stats.get_time_in_state() {
+            let state: String = String::from(state);
+            let average: String = average.map_or("-".to_owned(), date::format_duration);
+            println!("  {state:<12} {average}");
+        }
+        if !stats.get_oldest_open().is_empty() {
+            println!();
+            println!("Oldest open tasks:");
+            for task in stats.get_oldest_open() {
                 let task_id: u8 = task.get_id();
-                println!("### {task_id} ###");
-                let task_state: &model::TaskState = task.get_state();
-                println!("State: {task_state:?}");
+                let created: String = date::format_date(task.get_created_at());
                 let task_content: &String = task.get_task();
-                println!("{task_content}");
-                println!()
+                println!("  #{task_id:<4} {created}  {task_content}");
             }
-            None => println!("No task found with id {id}"),
         }
     }
 
-    pub fn update(self, id: u8, updated_task: String) {
-        match self.store.update_task(id, updated_task.clone()) {
-            true => println!("Successfully updated task #{id}"),
-            false => println!("Can not update task #{id}"),
+    // Charts the open and done tasks per day, by default over the last 30 days.
+    pub fn report_burndown(self, since: Option<u64>, format: ReportFormat) {
+        const DEFAULT_DAYS: u64 = 30;
+        let now: u64 = date::now();
+        let since: u64 = since.unwrap_or(now.saturating_sub(DEFAULT_DAYS * date::SECONDS_PER_DAY));
+        if since > now {
+            println!("The report can not start in the future");
+            return;
+        }
+        let mut tasks: Vec<model::Task> = self.store.get_tasks();
+        tasks.extend(self.store.get_archived_tasks());
+        let days: Vec<BurndownDay> = burndown(&tasks, since, now);
+        let output: String = match format {
+            ReportFormat::Text => report::render_text(&days),
+            ReportFormat::Svg => report::render_svg(&days),
+            ReportFormat::Csv => report::render_csv(&days),
         };
+        print!("{output}");
     }
 
-    pub fn delete(self, id: u8) {
-        match self.store.remove_task(id) {
-            true => println!("Successfully deleted task #{id}"),
-            false => println!("Can not delete task #{id}"),
+    // Adds the tasks of a file from another tool, skipping the ones already
+    // in the store, as one operation undone at once. A dry run only lists the
+    // tasks that would be added.
+    pub fn import(self, format: ImportFormat, path: String, mapping: ColumnMapping, dry_run: bool) {
+        let source: String = match fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(error) => {
+                println!("Can not read '{path}': {error}");
+                return;
+            }
         };
+        let imported: Vec<ImportedTask> =
+            import::parse(&format, &source, &mapping).unwrap_or_else(|error| panic!("{error}"));
+        let mut existing: Vec<model::Task> = self.store.get_tasks();
+        existing.extend(self.store.get_archived_tasks());
+        let (tasks, duplicates) = import::split_duplicates(imported, &existing);
+        for task in &duplicates {
+            println!("Skipping duplicate '{}'", task.get_title());
+        }
+        let count: usize = tasks.len();
+        if dry_run {
+            println!("Would import {count} task(s):");
+            for task in &tasks {
+                println!("  {}", format_imported_task(task));
+            }
+            return;
+        }
+        if tasks.is_empty() {
+            println!("No tasks to import");
+            return;
+        }
+
+        let mut projects: Vec<Project> = vec![];
+        for name in tasks.iter().filter_map(ImportedTask::get_project) {
+            if self.store.get_project(name).is_none()
+                && !projects.iter().any(|project| project.get_name() == name)
+            {
+                projects.push(Project::new(name.clone()).unwrap_or_else(|error| panic!("{error}")));
+            }
+        }
+        let names: Vec<String> = projects
+            .iter()
+            .map(|project| project.get_name().to_owned())
+            .collect();
+        let ids: Vec<u8> = self
+            .store
+            .import_tasks(projects, &tasks)
+            .unwrap_or_else(|error| panic!("{error}"));
+        for name in names {
+            println!("Added project '{name}'");
+        }
+        println!("Imported {count} task(s): {}", format_ids(&ids));
+    }
+
+    // Prints the tasks, or the ones matching the filter, in the format of
+    // another tool.
+    pub fn export(self, format: ExportFormat, filter: Option<Filter>) {
+        let tasks: Vec<model::Task> = match filter {
+            Some(filter) => self.store.find_tasks(&filter),
+            None => self.store.get_tasks(),
+        };
+        let output: String = match format {
+            ExportFormat::Markdown => export::render_markdown(&tasks),
+            ExportFormat::TodoTxt => export::render_todotxt(&tasks),
+            ExportFormat::Csv => export::render_csv(&tasks),
+            ExportFormat::Ics => {
+                export::render_ics(&tasks, &self.store.get_milestones(), date::now())
+            }
+        };
+        print!("{output}");
+    }
+
+    // Merges the edits of a Markdown file and of the tasks since the last
+    // sync, then writes the file back when it changed.
+    pub fn sync_markdown(self, path: String) {
+        let source: String = match fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(error) => {
+                println!("Can not read '{path}': {error}");
+                return;
+            }
+        };
+        // The same file is found under any relative path.
+        let file: String = fs::canonicalize(&path)
+            .map(|file| file.display().to_string())
+            .unwrap_or_else(|_| path.clone());
+        let mut bases: SyncBases = SyncBases::load();
+        let mut document: Document = Document::parse(&source);
+        let changes: Vec<Change> = sync::merge(
+            &document,
+            &self.store.get_tasks(),
+            &self.store.get_archived_tasks(),
+            &bases.get_items(&file),
+        );
+        // The tasks are changed first and in one operation, so a single undo
+        // reverts the whole sync.
+        let mut added: vec::IntoIter<u8> = self
+            .store
+            .sync_tasks(&changes)
+            .unwrap_or_else(|error| panic!("{error}"))
+            .into_iter();
+        for change in &changes {
+            match change {
+                Change::AddTask { key, title, .. } => {
+                    let id: u8 = added.next().unwrap();
+                    document.link(*key, id);
+                    println!("Added #{id} '{title}' from the file");
+                }
+                Change::LinkTask { key, id } => {
+                    document.link(*key, *id);
+                    println!("Linked an item of the file to #{id}");
+                }
+                Change::MarkTask { id, done } => {
+                    let state: model::TaskState = match done {
+                        true => model::TaskState::Done,
+                        false => model::TaskState::NotStarted,
+                    };
+                    println!("Marked #{id} {} from the file", String::from(&state));
+                }
+                Change::RenameTask { id, title } => {
+                    println!("Renamed #{id} to '{title}' from the file");
+                }
+                Change::DeleteTask(id) => {
+                    println!("Deleted #{id}, its item was removed from the file");
+                }
+                Change::CheckItem { id, done } => {
+                    document.set_done(*id, *done);
+                    match done {
+                        true => println!("Checked #{id} in the file"),
+                        false => println!("Unchecked #{id} in the file"),
+                    }
+                }
+                Change::RenameItem { id, title } => {
+                    document.set_title(*id, title);
+                    println!("Renamed #{id} to '{title}' in the file");
+                }
+                Change::RemoveItem(id) => {
+                    document.remove(*id);
+                    println!("Removed #{id} from the file, the task was deleted");
+                }
+                Change::Conflict(id) => {
+                    println!("#{id} was renamed on both sides, keeping the title of the task");
+                }
+            }
+        }
+        let output: String = document.render();
+        if output != source {
+            fs::write(&path, output)
+                .unwrap_or_else(|error| panic!("Can not write '{path}': {error}"));
+        }
+        let items: Vec<SyncedItem> = document
+            .get_items()
+            .iter()
+            .filter_map(|item| {
+                let id: u8 = item.get_id()?;
+                Some(SyncedItem::new(
+                    id,
+                    item.get_title().clone(),
+                    item.is_done(),
+                ))
+            })
+            .collect();
+        bases.set_items(file, items);
+        bases.save();
+        if changes.is_empty() {
+            println!("'{path}' is already in sync");
+        }
+    }
+
+    // Opens the full-screen interface until the user quits it.
+    pub fn tui(self) {
+        tui::run(self.store, self.config);
+    }
+
+    // Reads commands from the user until the end of the input, keeping the
+    // store open between them.
+    pub fn shell(self) {
+        shell::run(self.store, self.config);
+    }
+
+    // Serves the tasks over HTTP until the process is stopped.
+    pub fn serve(self, bind: String) {
+        server::run(self.store, &bind);
+    }
+
+    // Answers JSON-RPC requests on stdin until the end of the input.
+    pub fn rpc(self) {
+        rpc::run(self.store);
+    }
+
+    // Prints the secret of the new token, which is the only time it is shown.
+    pub fn create_token(self, owner: String, scope: Scope) {
+        let mut tokens: Tokens = Tokens::load();
+        let scope_name: String = String::from(&scope);
+        match tokens.create(owner.clone(), scope) {
+            Ok(secret) => {
+                println!("Created a {scope_name} token for '{owner}': {secret}");
+                tokens.save();
+            }
+            Err(error) => println!("{error}"),
+        }
+    }
+
+    pub fn list_tokens(self) {
+        let tokens: Tokens = Tokens::load();
+        if tokens.is_empty() {
+            println!("No tokens, the server is open to every local user");
+        }
+        for token in tokens.get_tokens() {
+            let scope: String = String::from(token.get_scope());
+            let created: String = date::format_date(token.get_created_at());
+            println!("{}: {scope}, created {created}", token.get_owner());
+        }
+    }
+
+    pub fn revoke_token(self, owner: String) {
+        let mut tokens: Tokens = Tokens::load();
+        match tokens.revoke(&owner) {
+            true => {
+                tokens.save();
+                println!("Successfully revoked the token of '{owner}'");
+            }
+            false => println!("No token found for '{owner}'"),
+        }
     }
 
-    pub fn mark(self, id: u8, state: String) {
-        match self.store.set_state(id, state) {
-            true => println!("Successfully marked task #{id}"),
-            false => println!("Can not mark task #{id}"),
+    pub fn undo(self) {
+        match self.store.undo() {
+            Some(operation) => println!("Successfully undid '{operation}'"),
+            None => println!("Nothing to undo"),
         };
     }
+
+    pub fn redo(self) {
+        match self.store.redo() {
+            Some(operation) => println!("Successfully redid '{operation}'"),
+            None => println!("Nothing to redo"),
+        };
+    }
+
+    // Resolves a task reference to an id. A title has to match one task
+    // better than the others, no looser than the given quality; otherwise the
+    // user picks one of the best matches on a terminal. References that do
+    // not resolve fail the command.
+    fn resolve(&self, reference: TaskReference, loosest: u8) -> Option<u8> {
+        let title: String = match reference {
+            TaskReference::Id(id) => return Some(id),
+            TaskReference::Key(project, number) => {
+                let key: String = project::format_key(&project, number);
+                let task: Option<model::Task> = self
+                    .store
+                    .get_tasks()
+                    .into_iter()
+                    .find(|task| task.get_key().as_ref() == Some(&key));
+                return match task {
+                    Some(task) => Some(task.get_id()),
+                    None => panic!("No task found with key {key}"),
+                };
+            }
+            TaskReference::Title(title) => title,
+        };
+        let mut candidates: Vec<(u8, model::Task)> = self
+            .store
+            .get_tasks()
+            .into_iter()
+            .filter_map(|task| fuzzy_match(&title, task.get_task()).map(|quality| (quality, task)))
+            .filter(|(quality, _)| *quality <= loosest)
+            .collect();
+        let best: Option<u8> = candidates.iter().map(|(quality, _)| *quality).min();
+        candidates.retain(|(quality, _)| Some(*quality) == best);
+        let candidates: Vec<model::Task> = candidates.into_iter().map(|(_, task)| task).collect();
+
+        match candidates.len() {
+            0 => panic!("No task matches '{title}'"),
+            1 => Some(candidates[0].get_id()),
+            _ if io::stdin().is_terminal() && io::stdout().is_terminal() => {
+                choose(&title, &candidates)
+            }
+            _ => {
+                let mut message: String =
+                    format!("'{title}' matches several tasks, use one of their ids:");
+                for task in &candidates {
+                    let task_id: u8 = task.get_id();
+                    let task_content: &String = task.get_task();
+                    let _ = write!(message, "\n  #{task_id} {task_content}");
+                }
+                panic!("{message}")
+            }
+        }
+    }
+
+    // Resolves the ids a bulk command applies to. Returns None when nothing
+    // should be changed: on a dry run, when no task matches the filter, or
+    // when the user does not confirm a large change.
+    fn select(&self, selection: Selection, action: &str, loosest: u8) -> Option<Vec<u8>> {
+        let (filter, dry_run) = match selection {
+            Selection::Ids(ids) => return Some(ids),
+            Selection::Title(title) => {
+                return self
+                    .resolve(TaskReference::Title(title), loosest)
+                    .map(|id| vec![id]);
+            }
+            Selection::Key(project, number) => {
+                return self
+                    .resolve(TaskReference::Key(project, number), loosest)
+                    .map(|id| vec![id]);
+            }
+            Selection::Where { filter, dry_run } => (filter, dry_run),
+        };
+        let tasks: Vec<model::Task> = self.store.find_tasks(&filter);
+        let count: usize = tasks.len();
+        if count == 0 {
+            println!("No tasks match the filter");
+            return None;
+        }
+        if dry_run {
+            println!("Would {action} {count} task(s):");
+            println!();
+            for task in &tasks {
+                print_task(task);
+            }
+            return None;
+        }
+        if count > self.config.get_confirm_threshold() && !confirm(action, count) {
+            println!("Aborted, no tasks changed");
+            return None;
+        }
+        Some(tasks.iter().map(model::Task::get_id).collect())
+    }
+}
+
+// A warning when the tasks in the given state exceed its WIP limit.
+fn wip_warning(
+    store: &dyn store::TaskStore,
+    config: &Config,
+    state: model::TaskState,
+) -> Option<String> {
+    let limit: usize = config.get_wip_limit(&state)?;
+    let count: usize = store.find_tasks(&Filter::State(state.clone())).len();
+    if count <= limit {
+        return None;
+    }
+    let state: String = String::from(&state);
+    Some(format!(
+        "Warning: {count} tasks are {state}, above the WIP limit of {limit}"
+    ))
+}
+
+// Lists the tasks matching a filter query, ordered by comma-separated sort
+// keys and paged, as the server and the JSON-RPC interface take them (e.g.:
+// 'state:done', 'priority:desc,created').
+fn query_tasks(
+    store: &dyn store::TaskStore,
+    filter: Option<&str>,
+    sort: Option<&str>,
+    limit: Option<usize>,
+    offset: usize,
+) -> Result<Vec<model::Task>, String> {
+    let tasks: Vec<model::Task> = match filter {
+        Some(query) => store.find_tasks(&filter::query::parse_filter(query)?),
+        None => store.get_tasks(),
+    };
+    let sort: Vec<SortKey> = match sort {
+        Some(sort) => sort
+            .split(',')
+            .map(|key| SortKey::try_from(key.to_owned()))
+            .collect::<Result<Vec<SortKey>, String>>()?,
+        None => vec![],
+    };
+    Ok(ListOptions::new(sort, limit, offset).apply(tasks))
+}
+
+// The message of a panic, as raised by the commands on invalid input.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        return (*message).to_owned();
+    }
+    match payload.downcast_ref::<String>() {
+        Some(message) => message.to_owned(),
+        None => "The command failed".to_owned(),
+    }
+}
+
+// Splits the tasks into a column per state, in workflow order.
+fn board_columns<'t>(tasks: &'t [model::Task], config: &Config) -> Vec<board::BoardColumn<'t>> {
+    [
+        model::TaskState::NotStarted,
+        model::TaskState::InProgress,
+        model::TaskState::Done,
+    ]
+    .into_iter()
+    .map(|state| {
+        let limit: Option<usize> = config.get_wip_limit(&state);
+        let column_tasks: Vec<&model::Task> = tasks
+            .iter()
+            .filter(|task| task.get_state() == &state)
+            .collect();
+        board::BoardColumn::new(state, limit, column_tasks)
+    })
+    .collect()
+}
+
+// Formats the tasks one after the other, or as a table of the given columns.
+fn format_tasks(tasks: &[model::Task], columns: &[Column]) -> String {
+    let mut output: String = String::new();
+    if columns.is_empty() {
+        for task in tasks {
+            output.push_str(&format_highlighted_task(task, &[]));
+        }
+        return output;
+    }
+    let mut rows: Vec<Vec<String>> = vec![
+        columns
+            .iter()
+            .map(|column| String::from(column).to_uppercase())
+            .collect(),
+    ];
+    for task in tasks {
+        rows.push(
+            columns
+                .iter()
+                .map(|column| column_value(task, column))
+                .collect(),
+        );
+    }
+    let mut widths: Vec<usize> = vec![0; columns.len()];
+    for row in &rows {
+        for (index, value) in row.iter().enumerate() {
+            widths[index] = widths[index].max(value.chars().count());
+        }
+    }
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(value, width)| format!("{value:<width$}"))
+            .collect();
+        let _ = writeln!(output, "{}", cells.join("  ").trim_end());
+    }
+    output
+}
+
+// Prints the output, through the pager of the user when it does not fit on
+// the terminal. Falls back to printing it when no pager can be started.
+fn page(output: &str) {
+    let fits: bool = match terminal_height() {
+        Some(height) => output.lines().count() < height,
+        None => true,
+    };
+    if fits || !io::stdout().is_terminal() {
+        print!("{output}");
+        return;
+    }
+    let pager: String = env::var("PAGER").unwrap_or_else(|_| "less -R".to_owned());
+    let mut words = pager.split_whitespace();
+    let Some(program) = words.next() else {
+        print!("{output}");
+        return;
+    };
+    let child = process::Command::new(program)
+        .args(words)
+        .stdin(Stdio::piped())
+        .spawn();
+    let Ok(mut child) = child else {
+        print!("{output}");
+        return;
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        // The user may quit the pager before reading everything.
+        let _ = stdin.write_all(output.as_bytes());
+    }
+    let _ = child.wait();
+}
+
+// The number of rows of the terminal, from $LINES or asked from the terminal.
+fn terminal_height() -> Option<usize> {
+    match env::var("LINES").ok().and_then(|lines| lines.parse().ok()) {
+        Some(lines) => Some(lines),
+        None => terminal_size().map(|(rows, _)| rows),
+    }
+}
+
+// The number of columns of the terminal, from $COLUMNS or asked from the terminal.
+fn terminal_width() -> Option<usize> {
+    match env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+    {
+        Some(columns) => Some(columns),
+        None => terminal_size().map(|(_, columns)| columns),
+    }
+}
+
+// The rows and columns of the terminal, as reported by 'stty size'.
+fn terminal_size() -> Option<(usize, usize)> {
+    let terminal: File = File::open("/dev/tty").ok()?;
+    let output: process::Output = process::Command::new("stty")
+        .arg("size")
+        .stdin(terminal)
+        .output()
+        .ok()?;
+    let size: String = String::from_utf8(output.stdout).ok()?;
+    let mut size = size.split_whitespace();
+    let rows: usize = size.next()?.parse().ok()?;
+    let columns: usize = size.next()?.parse().ok()?;
+    Some((rows, columns))
+}
+
+fn column_value(task: &model::Task, column: &Column) -> String {
+    match column {
+        Column::Id => task.get_id().to_string(),
+        Column::State => String::from(task.get_state()),
+        Column::Priority => String::from(task.get_priority()),
+        Column::Tags => task.get_tags().join(","),
+        Column::Created => date::format_date(task.get_created_at()),
+        Column::Title => task.get_task().to_owned(),
+    }
+}
+
+// Formats a task to import on one line (e.g.: '[done] (high) Call mom
+// +family #phone').
+fn format_imported_task(task: &ImportedTask) -> String {
+    let state: String = String::from(task.get_state());
+    let priority: String = String::from(task.get_priority());
+    let mut line: String = format!("[{state}] ({priority}) {}", task.get_title());
+    if let Some(project) = task.get_project() {
+        let _ = write!(line, " +{project}");
+    }
+    for tag in task.get_tags() {
+        let _ = write!(line, " #{tag}");
+    }
+    line
+}
+
+fn print_task(task: &model::Task) {
+    print!("{}", format_highlighted_task(task, &[]))
+}
+
+// Formats a task with the given lowercase words highlighted.
+fn format_highlighted_task(task: &model::Task, words: &[String]) -> String {
+    let mut output: String = String::new();
+    let task_id: u8 = task.get_id();
+    let _ = writeln!(output, "### {task_id} ###");
+    let task_state: &model::TaskState = task.get_state();
+    let _ = writeln!(output, "State: {task_state:?}");
+    let task_priority: &model::Priority = task.get_priority();
+    let _ = writeln!(output, "Priority: {task_priority:?}");
+    let task_tags: &Vec<String> = task.get_tags();
+    if !task_tags.is_empty() {
+        let task_tags: String = highlight(&task_tags.join(", "), words);
+        let _ = writeln!(output, "Tags: {task_tags}");
+    }
+    if let (Some(task_project), Some(task_key)) = (task.get_project(), task.get_key()) {
+        let _ = writeln!(output, "Project: {task_project} ({task_key})");
+    }
+    if let Some(task_milestone) = task.get_milestone() {
+        let _ = writeln!(output, "Milestone: {task_milestone}");
+    }
+    let task_content: String = highlight(task.get_task(), words);
+    let _ = writeln!(output, "{task_content}");
+    for note in task.get_notes() {
+        let note: String = highlight(note, words);
+        let _ = writeln!(output, "  - {note}");
+    }
+    output.push('\n');
+    output
+}
+
+// Wraps the given words of a text in terminal colors. Output that does not
+// go to a terminal is left as it is.
+fn highlight(text: &str, words: &[String]) -> String {
+    const HIGHLIGHT_START: &str = "\x1b[1;33m";
+    const HIGHLIGHT_END: &str = "\x1b[0m";
+    if words.is_empty() || !io::stdout().is_terminal() {
+        return text.to_owned();
+    }
+    let mut highlighted: String = String::new();
+    let mut word: String = String::new();
+    for character in text.chars().chain(std::iter::once(' ')) {
+        if character.is_alphanumeric() {
+            word.push(character);
+            continue;
+        }
+        if !word.is_empty() {
+            if words.contains(&word.to_lowercase()) {
+                highlighted.push_str(&format!("{HIGHLIGHT_START}{word}{HIGHLIGHT_END}"));
+            } else {
+                highlighted.push_str(&word);
+            }
+            word.clear();
+        }
+        highlighted.push(character);
+    }
+    highlighted.pop();
+    highlighted
+}
+
+// Lets the user pick one of several tasks matching a title on the terminal.
+fn choose(title: &str, candidates: &[model::Task]) -> Option<u8> {
+    println!("'{title}' matches several tasks:");
+    for (index, task) in candidates.iter().enumerate() {
+        let number: usize = index + 1;
+        let task_id: u8 = task.get_id();
+        let task_content: &String = task.get_task();
+        println!("  {number}) #{task_id} {task_content}");
+    }
+    let count: usize = candidates.len();
+    println!("Choose a task [1-{count}]:");
+    let mut answer: String = String::new();
+    io::stdin().read_line(&mut answer).ok()?;
+    match answer.trim().parse::<usize>() {
+        Ok(number) if (1..=count).contains(&number) => Some(candidates[number - 1].get_id()),
+        _ => {
+            println!("No task chosen");
+            None
+        }
+    }
+}
+
+// Asks the user on the terminal. Anything but an explicit yes, including a
+// closed standard input, counts as a no.
+fn confirm(action: &str, count: usize) -> bool {
+    println!("This will {action} {count} tasks. Continue? [y/N]");
+    let mut answer: String = String::new();
+    match io::stdin().read_line(&mut answer) {
+        Ok(_) => matches!(answer.trim(), "y" | "Y" | "yes"),
+        Err(_) => false,
+    }
+}
+
+// Reports which of the requested tasks were changed and which were missing.
+fn print_summary(done: &str, action: &str, requested: &[u8], applied: &[u8]) {
+    if !applied.is_empty() {
+        let applied_ids: String = format_ids(applied);
+        println!("Successfully {done} task(s) {applied_ids}");
+    }
+    let missing: Vec<u8> = requested
+        .iter()
+        .filter(|id| !applied.contains(id))
+        .copied()
+        .collect();
+    if !missing.is_empty() {
+        let missing_ids: String = format_ids(&missing);
+        println!("Can not {action} task(s) {missing_ids}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::TaskStore;
+    use crate::store::json_store::tests::empty_store;
+
+    use std::path::PathBuf;
+
+    // Undo a whole sync at once
+    #[test]
+    fn test_sync_markdown_is_undone_at_once() {
+        let (_home, mut store) = empty_store();
+        let config: Config = Config::default();
+        store
+            .add_task(String::from("Write docs"), None, None)
+            .unwrap();
+        let path: PathBuf = env::home_dir().unwrap().join("TODO.md");
+        fs::write(&path, "- [ ] Fix login\n- [x] Ship it\n").unwrap();
+
+        TaskService::new(&mut store, &config).sync_markdown(path.display().to_string());
+        assert_eq!(store.get_tasks().len(), 3);
+        assert_eq!(
+            store.get_task(3).unwrap().get_state(),
+            &model::TaskState::Done
+        );
+
+        assert_eq!(store.undo(), Some(String::from("sync #2, #3")));
+        assert_eq!(store.get_tasks().len(), 1);
+        assert_eq!(store.undo(), Some(String::from("add #1")));
+    }
 }
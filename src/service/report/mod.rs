@@ -0,0 +1,176 @@
+use crate::date;
+use crate::model::report::BurndownDay;
+
+use std::fmt::Write as _;
+
+// The width of the longest bar of the terminal chart, in characters.
+const CHART_WIDTH: usize = 50;
+// The size of the SVG chart and the room left around the plot for labels.
+const SVG_WIDTH: f64 = 720.0;
+const SVG_HEIGHT: f64 = 360.0;
+const SVG_MARGIN: f64 = 48.0;
+
+// Draws one bar per day, the open tasks in full blocks followed by the done
+// tasks in light shade, with the tasks completed that day as throughput.
+pub fn render_text(days: &[BurndownDay]) -> String {
+    let mut output: String = String::new();
+    let largest: usize = largest_total(days);
+    let _ = writeln!(output, "Burndown (█ open, ░ done, + completed that day)");
+    for day in days {
+        let open: usize = scale(day.get_open(), largest, CHART_WIDTH);
+        let done: usize = scale(day.get_open() + day.get_done(), largest, CHART_WIDTH) - open;
+        let bar: String = format!("{}{}", "█".repeat(open), "░".repeat(done));
+        let date: String = date::format_date(day.get_date());
+        let open_count: usize = day.get_open();
+        let done_count: usize = day.get_done();
+        let completed: usize = day.get_completed();
+        let _ = writeln!(
+            output,
+            "{date} │{bar:<CHART_WIDTH$} {open_count:>4} open {done_count:>4} done  +{completed}"
+        );
+    }
+    output
+}
+
+pub fn render_csv(days: &[BurndownDay]) -> String {
+    let mut output: String = String::from("date,open,done,completed\n");
+    for day in days {
+        let date: String = date::format_date(day.get_date());
+        let open: usize = day.get_open();
+        let done: usize = day.get_done();
+        let completed: usize = day.get_completed();
+        let _ = writeln!(output, "{date},{open},{done},{completed}");
+    }
+    output
+}
+
+// Plots the open and done tasks as lines over bars of the daily throughput.
+pub fn render_svg(days: &[BurndownDay]) -> String {
+    let largest: usize = largest_total(days).max(1);
+    let plot_width: f64 = SVG_WIDTH - 2.0 * SVG_MARGIN;
+    let plot_height: f64 = SVG_HEIGHT - 2.0 * SVG_MARGIN;
+    let step: f64 = plot_width / days.len().max(2).saturating_sub(1) as f64;
+    let x = |index: usize| SVG_MARGIN + index as f64 * step;
+    let y = |count: usize| SVG_HEIGHT - SVG_MARGIN - count as f64 / largest as f64 * plot_height;
+    let points = |count: fn(&BurndownDay) -> usize| -> String {
+        days.iter()
+            .enumerate()
+            .map(|(index, day)| format!("{:.1},{:.1}", x(index), y(count(day))))
+            .collect::<Vec<String>>()
+            .join(" ")
+    };
+
+    let mut output: String = String::new();
+    let _ = writeln!(
+        output,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{SVG_WIDTH}" height="{SVG_HEIGHT}" font-family="sans-serif" font-size="12">"#
+    );
+    let _ = writeln!(
+        output,
+        r#"  <rect width="{SVG_WIDTH}" height="{SVG_HEIGHT}" fill="white"/>"#
+    );
+    let bottom: f64 = SVG_HEIGHT - SVG_MARGIN;
+    let right: f64 = SVG_WIDTH - SVG_MARGIN;
+    let _ = writeln!(
+        output,
+        r#"  <path d="M{SVG_MARGIN},{SVG_MARGIN} V{bottom} H{right}" fill="none" stroke="black"/>"#
+    );
+    let bar_width: f64 = (step * 0.6).max(1.0);
+    for (index, day) in days.iter().enumerate() {
+        if day.get_completed() == 0 {
+            continue;
+        }
+        let top: f64 = y(day.get_completed());
+        let _ = writeln!(
+            output,
+            r##"  <rect x="{:.1}" y="{top:.1}" width="{bar_width:.1}" height="{:.1}" fill="#9ecae1"/>"##,
+            x(index) - bar_width / 2.0,
+            bottom - top
+        );
+    }
+    let _ = writeln!(
+        output,
+        r##"  <polyline points="{}" fill="none" stroke="#d62728" stroke-width="2"/>"##,
+        points(BurndownDay::get_open)
+    );
+    let _ = writeln!(
+        output,
+        r##"  <polyline points="{}" fill="none" stroke="#2ca02c" stroke-width="2"/>"##,
+        points(BurndownDay::get_done)
+    );
+    let _ = writeln!(
+        output,
+        r#"  <text x="{}" y="{SVG_MARGIN}" text-anchor="end">{largest}</text>"#,
+        SVG_MARGIN - 6.0
+    );
+    let _ = writeln!(
+        output,
+        r#"  <text x="{}" y="{bottom}" text-anchor="end">0</text>"#,
+        SVG_MARGIN - 6.0
+    );
+    if let (Some(first), Some(last)) = (days.first(), days.last()) {
+        let label_y: f64 = bottom + 18.0;
+        let first_date: String = date::format_date(first.get_date());
+        let last_date: String = date::format_date(last.get_date());
+        let _ = writeln!(
+            output,
+            r#"  <text x="{SVG_MARGIN}" y="{label_y}">{first_date}</text>"#
+        );
+        let _ = writeln!(
+            output,
+            r#"  <text x="{right}" y="{label_y}" text-anchor="end">{last_date}</text>"#
+        );
+    }
+    let legend_y: f64 = SVG_MARGIN - 18.0;
+    let _ = writeln!(
+        output,
+        r##"  <text x="{SVG_MARGIN}" y="{legend_y}"><tspan fill="#d62728">open</tspan> <tspan fill="#2ca02c">done</tspan> <tspan fill="#6baed6">completed per day</tspan></text>"##
+    );
+    output.push_str("</svg>\n");
+    output
+}
+
+fn largest_total(days: &[BurndownDay]) -> usize {
+    days.iter()
+        .map(|day| day.get_open() + day.get_done())
+        .max()
+        .unwrap_or(0)
+}
+
+fn scale(count: usize, largest: usize, width: usize) -> usize {
+    match largest {
+        0 => 0,
+        largest => (count * width).div_ceil(largest),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Task;
+    use crate::model::TaskState;
+    use crate::model::report::burndown;
+
+    fn days() -> Vec<BurndownDay> {
+        let tasks: Vec<Task> = vec![Task::new(1, "task".to_string(), TaskState::NotStarted)];
+        let now: u64 = date::now();
+        burndown(&tasks, now, now)
+    }
+
+    #[test]
+    fn test_render_csv_writes_one_row_per_day() {
+        let today: String = date::format_date(date::now());
+
+        assert_eq!(
+            render_csv(&days()),
+            format!("date,open,done,completed\n{today},1,0,0\n")
+        );
+    }
+
+    #[test]
+    fn test_render_text_scales_bars_to_chart_width() {
+        let chart: String = render_text(&days());
+
+        assert!(chart.contains(&"█".repeat(CHART_WIDTH)));
+    }
+}
@@ -0,0 +1,484 @@
+use super::server::events::{self, Event};
+use crate::date;
+use crate::model::search::{self, SearchHit, SearchTerm};
+use crate::model::{Priority, Task, TaskState};
+use crate::store::TaskStore;
+
+use json::{JsonValue, object};
+
+use std::io::{self, BufRead, Write};
+use std::panic::{self, AssertUnwindSafe};
+
+// The error codes of JSON-RPC 2.0, one for missing tasks, milestones and
+// projects, and one for a store without free task ids.
+const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+const INVALID_PARAMS: i32 = -32602;
+const INTERNAL_ERROR: i32 = -32603;
+const NOT_FOUND: i32 = -32001;
+const STORE_FULL: i32 = -32002;
+
+#[derive(PartialEq, Debug)]
+struct RpcError {
+    code: i32,
+    message: String,
+}
+impl RpcError {
+    fn new(code: i32, message: &str) -> Self {
+        RpcError {
+            code,
+            message: message.to_owned(),
+        }
+    }
+}
+
+// Answers JSON-RPC 2.0 requests read from stdin, one JSON message per line,
+// until the end of the input. Every change of the tasks is also sent as a
+// notification named after the event (e.g.: 'task-state-changed').
+pub fn run(store: &mut dyn TaskStore) {
+    // Errors are sent back to the client rather than printed.
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let mut stdout = io::stdout().lock();
+    for line in io::stdin().lock().lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        for message in handle_line(store, &line) {
+            let _ = writeln!(stdout, "{}", message.dump());
+        }
+        let _ = stdout.flush();
+    }
+    panic::set_hook(default_hook);
+}
+
+// Handles a line holding a request or a batch of them. Returns the messages
+// to send back: the response, if any, then the notifications of the changes.
+fn handle_line(store: &mut dyn TaskStore, line: &str) -> Vec<JsonValue> {
+    let Ok(message) = json::parse(line) else {
+        return vec![error_response(
+            JsonValue::Null,
+            RpcError::new(PARSE_ERROR, "Parse error"),
+        )];
+    };
+    // Commands may have changed the tasks since the last request, which is
+    // notified as well.
+    let before: Vec<Task> = store.get_tasks();
+    store.reload();
+    let mut messages: Vec<JsonValue> = match &message {
+        JsonValue::Array(requests) if requests.is_empty() => vec![error_response(
+            JsonValue::Null,
+            RpcError::new(INVALID_REQUEST, "The batch is empty"),
+        )],
+        JsonValue::Array(requests) => {
+            let responses: Vec<JsonValue> = requests
+                .iter()
+                .filter_map(|request| handle_request(store, request))
+                .collect();
+            match responses.is_empty() {
+                true => vec![],
+                false => vec![JsonValue::Array(responses)],
+            }
+        }
+        request => handle_request(store, request).into_iter().collect(),
+    };
+    for event in events::changes(&before, &store.get_tasks()) {
+        messages.push(notification(&event));
+    }
+    messages
+}
+
+// Calls the method of a request. Requests without an id are notifications
+// and get no response.
+fn handle_request(store: &mut dyn TaskStore, request: &JsonValue) -> Option<JsonValue> {
+    let id: JsonValue = request["id"].clone();
+    let method: Option<&str> = request["method"].as_str();
+    let (true, Some(method)) = (request["jsonrpc"] == "2.0", method) else {
+        return Some(error_response(
+            id,
+            RpcError::new(INVALID_REQUEST, "Invalid request"),
+        ));
+    };
+    let params: &JsonValue = &request["params"];
+    let result: Result<JsonValue, RpcError> = if params.is_null() || params.is_object() {
+        panic::catch_unwind(AssertUnwindSafe(|| call(store, method, params))).unwrap_or_else(
+            |payload| {
+                Err(RpcError::new(
+                    INTERNAL_ERROR,
+                    &super::panic_message(&*payload),
+                ))
+            },
+        )
+    } else {
+        Err(RpcError::new(
+            INVALID_PARAMS,
+            "The params must be an object",
+        ))
+    };
+    if !request.has_key("id") {
+        return None;
+    }
+    Some(match result {
+        Ok(result) => object! { "jsonrpc" => "2.0", "id" => id, "result" => result },
+        Err(error) => error_response(id, error),
+    })
+}
+
+fn error_response(id: JsonValue, error: RpcError) -> JsonValue {
+    object! {
+        "jsonrpc" => "2.0",
+        "id" => id,
+        "error" => object! { "code" => error.code, "message" => error.message }
+    }
+}
+
+fn notification(event: &Event) -> JsonValue {
+    object! {
+        "jsonrpc" => "2.0",
+        "method" => event.get_name(),
+        "params" => JsonValue::from(event)
+    }
+}
+
+// Runs a method, named after the command doing the same on the command line,
+// or after what it lists. Methods changing several tasks take their 'ids' and
+// return the ones that changed; methods changing one task take its 'id' and
+// return it.
+fn call(
+    store: &mut dyn TaskStore,
+    method: &str,
+    params: &JsonValue,
+) -> Result<JsonValue, RpcError> {
+    match method {
+        "list" => list(&*store, params),
+        "search" => {
+            let query: String = parse_string(params, "query")?;
+            let terms: Vec<SearchTerm> = search::query::parse_search(&query)
+                .map_err(|error| RpcError::new(INVALID_PARAMS, &error))?;
+            let hits: Vec<JsonValue> = store
+                .search(&terms)
+                .iter()
+                .map(|hit: &SearchHit| {
+                    object! {
+                        "task" => JsonValue::from(hit.get_task()),
+                        "matched_words" => hit.get_matched_words().clone()
+                    }
+                })
+                .collect();
+            Ok(JsonValue::Array(hits))
+        }
+        "show" => {
+            let id: u8 = parse_id(&*store, params)?;
+            Ok(task_result(&*store, id))
+        }
+        "add" => {
+            let text: String = parse_string(params, "task")?;
+            let milestone: Option<String> = parse_optional_string(params, "milestone")?;
+            let project: Option<String> = parse_optional_string(params, "project")?;
+            if let Some(milestone) = &milestone {
+                find_milestone(&*store, milestone)?;
+            }
+            if let Some(project) = &project
+                && store.get_project(project).is_none()
+            {
+                return Err(RpcError::new(
+                    NOT_FOUND,
+                    &format!("No project found with name '{project}'"),
+                ));
+            }
+            let id: u8 = store
+                .add_task(text, milestone, project)
+                .map_err(|error| RpcError::new(STORE_FULL, &error))?;
+            Ok(task_result(&*store, id))
+        }
+        "update" => {
+            let id: u8 = parse_id(&*store, params)?;
+            let text: String = parse_string(params, "task")?;
+            store.update_task(id, text);
+            Ok(task_result(&*store, id))
+        }
+        "note" => {
+            let id: u8 = parse_id(&*store, params)?;
+            let note: String = parse_string(params, "note")?;
+            store.add_note(id, note);
+            Ok(task_result(&*store, id))
+        }
+        "delete" => {
+            let ids: Vec<u8> = parse_ids(params)?;
+            Ok(ids_result(&store.remove_tasks(&ids)))
+        }
+        "mark" => {
+            let ids: Vec<u8> = parse_ids(params)?;
+            let state: String = parse_string(params, "state")?;
+            TaskState::try_from(state.clone())
+                .map_err(|error| RpcError::new(INVALID_PARAMS, &error))?;
+            Ok(ids_result(&store.set_state(&ids, state)))
+        }
+        "priority" => {
+            let ids: Vec<u8> = parse_ids(params)?;
+            let priority: Priority = Priority::try_from(parse_string(params, "priority")?)
+                .map_err(|error| RpcError::new(INVALID_PARAMS, &error))?;
+            Ok(ids_result(&store.set_priority(&ids, priority)))
+        }
+        "tag" | "untag" => {
+            let ids: Vec<u8> = parse_ids(params)?;
+            let tag: String = parse_string(params, "tag")?;
+            let changed: Vec<u8> = match method {
+                "tag" => store.tag_tasks(&ids, tag),
+                _ => store.untag_tasks(&ids, tag),
+            };
+            Ok(ids_result(&changed))
+        }
+        "plan" => {
+            let ids: Vec<u8> = parse_ids(params)?;
+            let milestone: String = parse_string(params, "milestone")?;
+            find_milestone(&*store, &milestone)?;
+            Ok(ids_result(&store.set_milestone(&ids, Some(milestone))))
+        }
+        "unplan" => {
+            let ids: Vec<u8> = parse_ids(params)?;
+            Ok(ids_result(&store.set_milestone(&ids, None)))
+        }
+        "trash" => Ok(JsonValue::Array(
+            store.get_trash().iter().map(JsonValue::from).collect(),
+        )),
+        "archived" => Ok(JsonValue::Array(
+            store
+                .get_archived_tasks()
+                .iter()
+                .map(JsonValue::from)
+                .collect(),
+        )),
+        "restore" | "unarchive" => {
+            let id: u8 = parse_id_param(params)?;
+            let (moved, place): (bool, &str) = match method {
+                "restore" => (store.restore_task(id), "trash"),
+                _ => (store.unarchive_task(id), "archive"),
+            };
+            match moved {
+                true => Ok(task_result(&*store, id)),
+                false => Err(RpcError::new(
+                    NOT_FOUND,
+                    &format!("No task found with id {id} in the {place}"),
+                )),
+            }
+        }
+        "purge" | "archive" => {
+            let older_than: Option<u64> = parse_optional_duration(params, "older_than")?;
+            let ids: Vec<u8> = match method {
+                "purge" => store.purge_trash(older_than),
+                _ => store.archive_tasks(older_than),
+            };
+            Ok(ids_result(&ids))
+        }
+        "milestones" => Ok(JsonValue::Array(
+            store.get_milestones().iter().map(JsonValue::from).collect(),
+        )),
+        "projects" => Ok(JsonValue::Array(
+            store.get_projects().iter().map(JsonValue::from).collect(),
+        )),
+        "views" => Ok(JsonValue::Array(
+            store.get_views().iter().map(JsonValue::from).collect(),
+        )),
+        "undo" | "redo" => {
+            let operation: Option<String> = match method {
+                "undo" => store.undo(),
+                _ => store.redo(),
+            };
+            Ok(object! { "operation" => operation })
+        }
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            &format!("Unknown method '{method}'"),
+        )),
+    }
+}
+
+// Lists the tasks matching the 'filter' query, ordered by the 'sort' keys
+// and paged by 'limit' and 'offset'.
+fn list(store: &dyn TaskStore, params: &JsonValue) -> Result<JsonValue, RpcError> {
+    let filter: Option<String> = parse_optional_string(params, "filter")?;
+    let sort: Option<String> = parse_optional_string(params, "sort")?;
+    let limit: Option<usize> = parse_optional_count(params, "limit")?;
+    let offset: usize = parse_optional_count(params, "offset")?.unwrap_or(0);
+    let tasks: Vec<Task> =
+        super::query_tasks(store, filter.as_deref(), sort.as_deref(), limit, offset)
+            .map_err(|error| RpcError::new(INVALID_PARAMS, &error))?;
+    Ok(JsonValue::Array(
+        tasks.iter().map(JsonValue::from).collect(),
+    ))
+}
+
+fn task_result(store: &dyn TaskStore, id: u8) -> JsonValue {
+    store
+        .get_task(id)
+        .map(JsonValue::from)
+        .unwrap_or(JsonValue::Null)
+}
+
+fn ids_result(ids: &[u8]) -> JsonValue {
+    object! { "ids" => ids.to_vec() }
+}
+
+fn find_milestone(store: &dyn TaskStore, milestone: &str) -> Result<(), RpcError> {
+    match store.get_milestone(milestone) {
+        Some(_) => Ok(()),
+        None => Err(RpcError::new(
+            NOT_FOUND,
+            &format!("No milestone found with name '{milestone}'"),
+        )),
+    }
+}
+
+// The 'id' of an existing task.
+fn parse_id(store: &dyn TaskStore, params: &JsonValue) -> Result<u8, RpcError> {
+    let id: u8 = parse_id_param(params)?;
+    match store.get_task(id) {
+        Some(_) => Ok(id),
+        None => Err(RpcError::new(
+            NOT_FOUND,
+            &format!("No task found with id {id}"),
+        )),
+    }
+}
+
+// The 'id' of a task that may be in the trash or the archive.
+fn parse_id_param(params: &JsonValue) -> Result<u8, RpcError> {
+    params["id"]
+        .as_u8()
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, "The 'id' param must be a task id"))
+}
+
+fn parse_ids(params: &JsonValue) -> Result<Vec<u8>, RpcError> {
+    let error = || {
+        RpcError::new(
+            INVALID_PARAMS,
+            "The 'ids' param must be an array of task ids",
+        )
+    };
+    if !params["ids"].is_array() {
+        return Err(error());
+    }
+    params["ids"]
+        .members()
+        .map(|id| id.as_u8().ok_or_else(error))
+        .collect()
+}
+
+fn parse_string(params: &JsonValue, name: &str) -> Result<String, RpcError> {
+    parse_optional_string(params, name)?
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, &format!("The '{name}' param is required")))
+}
+
+fn parse_optional_string(params: &JsonValue, name: &str) -> Result<Option<String>, RpcError> {
+    let value: &JsonValue = &params[name];
+    if value.is_null() {
+        return Ok(None);
+    }
+    value
+        .as_str()
+        .map(|value| Some(value.to_owned()))
+        .ok_or_else(|| {
+            RpcError::new(
+                INVALID_PARAMS,
+                &format!("The '{name}' param must be a string"),
+            )
+        })
+}
+
+// A duration given as on the command line (e.g.: '30d').
+fn parse_optional_duration(params: &JsonValue, name: &str) -> Result<Option<u64>, RpcError> {
+    parse_optional_string(params, name)?
+        .map(|duration| {
+            date::parse_duration(&duration).map_err(|error| RpcError::new(INVALID_PARAMS, &error))
+        })
+        .transpose()
+}
+
+fn parse_optional_count(params: &JsonValue, name: &str) -> Result<Option<usize>, RpcError> {
+    let value: &JsonValue = &params[name];
+    if value.is_null() {
+        return Ok(None);
+    }
+    value.as_usize().map(Some).ok_or_else(|| {
+        RpcError::new(
+            INVALID_PARAMS,
+            &format!("The '{name}' param must be a number"),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Read task ids from the params
+    #[test]
+    fn test_parse_ids() {
+        assert_eq!(
+            parse_ids(&object! { "ids" => json::array![3, 5] }),
+            Ok(vec![3, 5])
+        );
+        assert_eq!(
+            parse_ids(&object! { "ids" => 3 }).unwrap_err().code,
+            INVALID_PARAMS
+        );
+        assert_eq!(
+            parse_ids(&object! { "ids" => json::array![300] })
+                .unwrap_err()
+                .code,
+            INVALID_PARAMS
+        );
+    }
+
+    // Read durations as on the command line
+    #[test]
+    fn test_parse_optional_duration() {
+        assert_eq!(
+            parse_optional_duration(&object! { "older_than" => "2d" }, "older_than"),
+            Ok(Some(2 * 24 * 60 * 60))
+        );
+        assert_eq!(
+            parse_optional_duration(&JsonValue::Null, "older_than"),
+            Ok(None)
+        );
+        assert_eq!(
+            parse_optional_duration(&object! { "older_than" => "soon" }, "older_than")
+                .unwrap_err()
+                .code,
+            INVALID_PARAMS
+        );
+    }
+
+    // Optional params may be missing or null, but not of another type
+    #[test]
+    fn test_parse_optional_string() {
+        assert_eq!(parse_optional_string(&JsonValue::Null, "filter"), Ok(None));
+        assert_eq!(
+            parse_optional_string(&object! { "filter" => "done" }, "filter"),
+            Ok(Some("done".to_string()))
+        );
+        assert!(parse_optional_string(&object! { "filter" => 1 }, "filter").is_err());
+    }
+
+    // Format errors and notifications as JSON-RPC messages
+    #[test]
+    fn test_format_messages() {
+        assert_eq!(
+            error_response(
+                JsonValue::from(7),
+                RpcError::new(METHOD_NOT_FOUND, "Unknown method 'fly'")
+            )
+            .dump(),
+            r#"{"jsonrpc":"2.0","id":7,"error":{"code":-32601,"message":"Unknown method 'fly'"}}"#
+        );
+        assert_eq!(
+            notification(&Event::Deleted(3)).dump(),
+            r#"{"jsonrpc":"2.0","method":"task-deleted","params":{"id":3}}"#
+        );
+    }
+}
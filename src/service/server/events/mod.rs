@@ -0,0 +1,123 @@
+use crate::model::{Task, TaskState};
+
+use json::{JsonValue, object};
+
+// A change of the tasks, as pushed to the subscribers of the event stream.
+#[derive(PartialEq, Debug)]
+pub enum Event {
+    Created(Task),
+    Updated(Task),
+    Deleted(u8),
+    // The task, and the state it was in before
+    StateChanged((Task, TaskState)),
+}
+impl Event {
+    // The name of the event in the stream (e.g.: 'task-created').
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Event::Created(_) => "task-created",
+            Event::Updated(_) => "task-updated",
+            Event::Deleted(_) => "task-deleted",
+            Event::StateChanged(_) => "task-state-changed",
+        }
+    }
+
+    // Formats the event as a server-sent event, with its data as one line
+    // of JSON.
+    pub fn format(&self) -> String {
+        format!(
+            "event: {}\ndata: {}\n\n",
+            self.get_name(),
+            JsonValue::from(self).dump()
+        )
+    }
+}
+
+impl From<&Event> for JsonValue {
+    fn from(event: &Event) -> JsonValue {
+        match event {
+            Event::Created(task) | Event::Updated(task) => object! {
+                "id" => task.get_id(),
+                "task" => JsonValue::from(task)
+            },
+            Event::Deleted(id) => object! { "id" => *id },
+            Event::StateChanged((task, previous_state)) => object! {
+                "id" => task.get_id(),
+                "previous_state" => String::from(previous_state),
+                "task" => JsonValue::from(task)
+            },
+        }
+    }
+}
+
+// The events turning the tasks before a request into the tasks after it.
+// A task whose state changed gives a single state change event, even when
+// other fields changed too.
+pub fn changes(before: &[Task], after: &[Task]) -> Vec<Event> {
+    let mut events: Vec<Event> = vec![];
+    for task in after {
+        match before
+            .iter()
+            .find(|previous| previous.get_id() == task.get_id())
+        {
+            None => events.push(Event::Created(task.clone())),
+            Some(previous) if previous.get_state() != task.get_state() => events.push(
+                Event::StateChanged((task.clone(), previous.get_state().clone())),
+            ),
+            Some(previous) if previous != task => events.push(Event::Updated(task.clone())),
+            Some(_) => {}
+        }
+    }
+    for previous in before {
+        if !after.iter().any(|task| task.get_id() == previous.get_id()) {
+            events.push(Event::Deleted(previous.get_id()));
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Tell created, updated, deleted and state changed tasks apart
+    #[test]
+    fn test_changes_between_tasks() {
+        let kept: Task = Task::new(1, "Kept".to_string(), TaskState::NotStarted);
+        let updated: Task = Task::new(2, "Updated".to_string(), TaskState::NotStarted);
+        let marked: Task = Task::new(3, "Marked".to_string(), TaskState::NotStarted);
+        let deleted: Task = Task::new(4, "Deleted".to_string(), TaskState::NotStarted);
+        let before: Vec<Task> = vec![kept.clone(), updated.clone(), marked.clone(), deleted];
+
+        let mut updated_after: Task = updated;
+        updated_after.set_task("Updated again".to_string());
+        let mut marked_after: Task = marked;
+        marked_after.set_state("done".to_string());
+        let created: Task = Task::new(5, "Created".to_string(), TaskState::NotStarted);
+        let after: Vec<Task> = vec![
+            kept,
+            updated_after.clone(),
+            marked_after.clone(),
+            created.clone(),
+        ];
+
+        assert_eq!(
+            changes(&before, &after),
+            vec![
+                Event::Updated(updated_after),
+                Event::StateChanged((marked_after, TaskState::NotStarted)),
+                Event::Created(created),
+                Event::Deleted(4),
+            ]
+        );
+    }
+
+    // Format an event as a server-sent event
+    #[test]
+    fn test_format_deleted_event() {
+        assert_eq!(
+            Event::Deleted(4).format(),
+            "event: task-deleted\ndata: {\"id\":4}\n\n"
+        );
+    }
+}
@@ -0,0 +1,281 @@
+use json::{JsonValue, object};
+
+use std::fmt::Write as _;
+use std::io::{self, BufRead, Write};
+
+// The largest request body read, to keep a client from filling the memory.
+const MAX_BODY_SIZE: usize = 1024 * 1024;
+
+// An HTTP/1.1 request: the method, the decoded path and query parameters,
+// the headers with lowercase names, and the body.
+#[derive(PartialEq, Debug)]
+pub struct Request {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+impl Request {
+    // Reads a request up to the end of its body, as given by its
+    // 'Content-Length' header. Errors are the response to send back.
+    pub fn read(reader: &mut impl BufRead) -> Result<Self, Response> {
+        let mut request_line: String = String::new();
+        reader
+            .read_line(&mut request_line)
+            .map_err(|_| Response::error(400, "Can not read the request"))?;
+        let mut parts = request_line.split_whitespace();
+        let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+            return Err(Response::error(400, "Malformed request line"));
+        };
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let mut headers: Vec<(String, String)> = vec![];
+        loop {
+            let mut header: String = String::new();
+            let read: usize = reader
+                .read_line(&mut header)
+                .map_err(|_| Response::error(400, "Can not read the headers"))?;
+            let header: &str = header.trim_end_matches(['\r', '\n']);
+            if read == 0 || header.is_empty() {
+                break;
+            }
+            let Some((name, value)) = header.split_once(':') else {
+                return Err(Response::error(400, "Malformed header"));
+            };
+            headers.push((name.trim().to_lowercase(), value.trim().to_owned()));
+        }
+        let mut request: Request = Request {
+            method: method.to_uppercase(),
+            path: percent_decode(path),
+            query: parse_query(query),
+            headers,
+            body: String::new(),
+        };
+        let length: usize = match request.get_header("content-length") {
+            Some(length) => length
+                .parse()
+                .map_err(|_| Response::error(400, "Invalid Content-Length"))?,
+            None => 0,
+        };
+        if length > MAX_BODY_SIZE {
+            return Err(Response::error(413, "The request body is too large"));
+        }
+        let mut body: Vec<u8> = vec![0; length];
+        reader
+            .read_exact(&mut body)
+            .map_err(|_| Response::error(400, "Can not read the request body"))?;
+        request.body = String::from_utf8(body)
+            .map_err(|_| Response::error(400, "The request body is not UTF-8"))?;
+        Ok(request)
+    }
+
+    pub fn get_method(&self) -> &str {
+        &self.method
+    }
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+    pub fn get_query(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+    pub fn get_header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    // The path split at '/', without empty segments (e.g.: '/tasks/3' ->
+    // ["tasks", "3"]).
+    pub fn get_segments(&self) -> Vec<&str> {
+        self.path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect()
+    }
+
+    // Parses the body as a JSON object.
+    pub fn get_json(&self) -> Result<JsonValue, Response> {
+        let json_value: JsonValue = json::parse(&self.body)
+            .map_err(|_| Response::error(400, "The request body is not valid JSON"))?;
+        if !json_value.is_object() {
+            return Err(Response::error(
+                400,
+                "The request body must be a JSON object",
+            ));
+        }
+        Ok(json_value)
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct Response {
+    status: u16,
+    content_type: String,
+    body: String,
+}
+impl Response {
+    pub fn new(status: u16, content_type: &str, body: String) -> Self {
+        Response {
+            status,
+            content_type: content_type.to_owned(),
+            body,
+        }
+    }
+    pub fn json(status: u16, json_value: &JsonValue) -> Self {
+        Response::new(status, "application/json", json_value.dump())
+    }
+    // An error as a JSON object (e.g.: '{"error": "No task found with id 3"}').
+    pub fn error(status: u16, message: &str) -> Self {
+        Response::json(status, &object! { "error" => message })
+    }
+    pub fn no_content() -> Self {
+        Response::new(204, "application/json", String::new())
+    }
+
+    pub fn get_status(&self) -> u16 {
+        self.status
+    }
+
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        let mut head: String = format!("HTTP/1.1 {} {}\r\n", self.status, reason(self.status));
+        if self.status != 204 {
+            let _ = write!(head, "Content-Type: {}\r\n", self.content_type);
+        }
+        let _ = write!(
+            head,
+            "Content-Length: {}\r\nConnection: close\r\n\r\n",
+            self.body.len()
+        );
+        writer.write_all(head.as_bytes())?;
+        writer.write_all(self.body.as_bytes())?;
+        writer.flush()
+    }
+}
+
+// Starts a stream of server-sent events, which stays open after the head.
+pub fn write_event_stream_head(writer: &mut impl Write) -> io::Result<()> {
+    writer.write_all(
+        b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\n\r\n",
+    )?;
+    writer.flush()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        _ => "Internal Server Error",
+    }
+}
+
+// Splits a query string into decoded name and value pairs.
+fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(name), percent_decode(value))
+        })
+        .collect()
+}
+
+// Decodes '%XX' escapes and '+' for spaces. Invalid escapes are kept as
+// they are.
+fn percent_decode(text: &str) -> String {
+    let bytes: &[u8] = text.as_bytes();
+    let mut decoded: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut index: usize = 0;
+    while index < bytes.len() {
+        let escaped: Option<u8> = match bytes[index] {
+            b'%' => text
+                .get(index + 1..index + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+        match (bytes[index], escaped) {
+            (_, Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+                continue;
+            }
+            (b'+', None) => decoded.push(b' '),
+            (byte, None) => decoded.push(byte),
+        }
+        index += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Read a request with a query string and a body
+    #[test]
+    fn test_read_request() {
+        let source: &str = "POST /tasks?filter=tag%3Awork+and+done&limit=5 HTTP/1.1\r\n\
+            Host: localhost\r\n\
+            Content-Length: 16\r\n\
+            \r\n\
+            {\"task\": \"Docs\"}";
+
+        let request: Request = Request::read(&mut source.as_bytes()).unwrap();
+
+        assert_eq!(request.get_method(), "POST");
+        assert_eq!(request.get_segments(), vec!["tasks"]);
+        assert_eq!(request.get_query("filter"), Some("tag:work and done"));
+        assert_eq!(request.get_query("limit"), Some("5"));
+        assert_eq!(request.get_header("host"), Some("localhost"));
+        assert_eq!(request.get_json().unwrap()["task"], "Docs");
+    }
+
+    // Reject bodies that are not JSON objects
+    #[test]
+    fn test_request_with_invalid_json_body() {
+        let source: &str = "PUT /tasks/1 HTTP/1.1\r\nContent-Length: 2\r\n\r\n[]";
+
+        let request: Request = Request::read(&mut source.as_bytes()).unwrap();
+
+        assert_eq!(request.get_json().unwrap_err().get_status(), 400);
+    }
+
+    // Reject a malformed request line
+    #[test]
+    fn test_read_malformed_request() {
+        let error: Response = Request::read(&mut "\r\n".as_bytes()).unwrap_err();
+        assert_eq!(error.get_status(), 400);
+    }
+
+    // Write a response with its status line and headers
+    #[test]
+    fn test_write_response() {
+        let mut output: Vec<u8> = vec![];
+
+        Response::error(404, "No task found with id 3")
+            .write_to(&mut output)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "HTTP/1.1 404 Not Found\r\n\
+            Content-Type: application/json\r\n\
+            Content-Length: 35\r\n\
+            Connection: close\r\n\
+            \r\n\
+            {\"error\":\"No task found with id 3\"}"
+        );
+    }
+}
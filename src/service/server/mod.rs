@@ -0,0 +1,516 @@
+pub mod events;
+pub mod http;
+
+use crate::model::{Priority, Task, TaskState};
+use crate::store::TaskStore;
+use crate::store::tokens::Tokens;
+use http::{Request, Response};
+
+use json::JsonValue;
+
+use std::io::{BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// The page of the web interface, built on the endpoints below.
+const INDEX_PAGE: &str = include_str!("index.html");
+// Who requests are audited for while the server has no tokens.
+const ANONYMOUS: &str = "anonymous";
+// How long a client may take to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+// How long a subscriber of the events may take to receive one.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+// How often the event streams get a comment, so that idle connections are
+// not closed along the way.
+const KEEP_ALIVE: Duration = Duration::from_secs(15);
+// How often the store is checked for the changes of other processes while
+// no request comes in.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+// Serves the tasks over HTTP until the process is stopped, with the web
+// interface at the root. Requests are read on threads of their own, so a
+// slow or idle connection does not hold up the others, and are handled one
+// at a time against the store, read again whenever a command changed it. The
+// changes of both are pushed to the subscribers of '/events'.
+pub fn run(store: &mut dyn TaskStore, bind: &str) {
+    let listener: TcpListener = TcpListener::bind(bind)
+        .unwrap_or_else(|error| panic!("Can not listen on '{bind}': {error}"));
+    println!("Serving the tasks on http://{bind}");
+    if Tokens::load().is_empty() {
+        println!(
+            "Warning: without tokens, every local user can change the tasks (see 'token create')"
+        );
+    }
+    let (sender, receiver) = mpsc::channel::<(Result<Request, Response>, TcpStream)>();
+    thread::spawn(move || accept(listener, sender));
+    let mut subscribers: Vec<TcpStream> = vec![];
+    let mut kept_alive: Instant = Instant::now();
+    loop {
+        match receiver.recv_timeout(POLL_INTERVAL) {
+            Ok((request, stream)) => respond(store, request, stream, &mut subscribers),
+            Err(RecvTimeoutError::Timeout) => refresh(store, &mut subscribers),
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        if kept_alive.elapsed() >= KEEP_ALIVE {
+            publish(&mut subscribers, ": keep-alive\n\n");
+            kept_alive = Instant::now();
+        }
+    }
+}
+
+// Reads the store again if another process changed it, and pushes the
+// changes to the subscribers.
+fn refresh(store: &mut dyn TaskStore, subscribers: &mut Vec<TcpStream>) {
+    let before: Vec<Task> = store.get_tasks();
+    store.reload();
+    for event in events::changes(&before, &store.get_tasks()) {
+        publish(subscribers, &event.format());
+    }
+}
+
+fn accept(listener: TcpListener, sender: Sender<(Result<Request, Response>, TcpStream)>) {
+    for stream in listener.incoming().flatten() {
+        let sender: Sender<(Result<Request, Response>, TcpStream)> = sender.clone();
+        thread::spawn(move || {
+            let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+            // Browsers open connections ahead of time that may never carry
+            // a request.
+            if !matches!(stream.peek(&mut [0]), Ok(1..)) {
+                return;
+            }
+            let request: Result<Request, Response> = Request::read(&mut BufReader::new(&stream));
+            let _ = sender.send((request, stream));
+        });
+    }
+}
+
+fn respond(
+    store: &mut dyn TaskStore,
+    request: Result<Request, Response>,
+    stream: TcpStream,
+    subscribers: &mut Vec<TcpStream>,
+) {
+    let request: Request = match request {
+        Ok(request) => request,
+        Err(error) => {
+            println!("- {}", error.get_status());
+            let _ = error.write_to(&mut &stream);
+            return;
+        }
+    };
+    let summary: String = format!("{} {}", request.get_method(), request.get_path());
+    let actor: String = match authorize(&request) {
+        Ok(actor) => actor,
+        Err(error) => {
+            println!("{summary} {}", error.get_status());
+            let _ = error.write_to(&mut &stream);
+            return;
+        }
+    };
+    let summary: String = format!("{actor} {summary}");
+    if request.get_method() == "GET" && request.get_segments() == ["events"] {
+        println!("{summary} 200");
+        subscribe(stream, subscribers);
+        return;
+    }
+    // Commands may have changed the tasks while the server is up.
+    refresh(store, subscribers);
+    let before: Option<Vec<Task>> = (request.get_method() != "GET").then(|| store.get_tasks());
+    store.set_actor(Some(actor));
+    // A failing endpoint answers with an error rather than stopping the
+    // server.
+    let response: Response = panic::catch_unwind(AssertUnwindSafe(|| route(store, &request)))
+        .unwrap_or_else(|payload| Err(Response::error(500, &super::panic_message(&*payload))))
+        .unwrap_or_else(|error| error);
+    store.set_actor(None);
+    if let Some(before) = before {
+        for event in events::changes(&before, &store.get_tasks()) {
+            publish(subscribers, &event.format());
+        }
+    }
+    println!("{summary} {}", response.get_status());
+    // The client may have gone already.
+    let _ = response.write_to(&mut &stream);
+}
+
+// The owner of the token of the request, given as a bearer token or as the
+// 'access_token' query parameter for clients that can not set headers. The
+// page of the web interface needs no token, nor does any request while the
+// server has no tokens. Read-only tokens can only read.
+fn authorize(request: &Request) -> Result<String, Response> {
+    let tokens: Tokens = Tokens::load();
+    if tokens.is_empty() || (request.get_method() == "GET" && request.get_segments().is_empty()) {
+        return Ok(ANONYMOUS.to_owned());
+    }
+    let secret: Option<&str> = request
+        .get_header("authorization")
+        .and_then(|authorization| authorization.strip_prefix("Bearer "))
+        .or_else(|| request.get_query("access_token"));
+    let token = secret
+        .and_then(|secret| tokens.authenticate(secret.trim()))
+        .ok_or_else(|| Response::error(401, "A valid token is required"))?;
+    if request.get_method() != "GET" && !token.can_write() {
+        return Err(Response::error(
+            403,
+            &format!("The token of '{}' is read-only", token.get_owner()),
+        ));
+    }
+    Ok(token.get_owner().to_owned())
+}
+
+// Keeps the connection open to push the events to.
+fn subscribe(stream: TcpStream, subscribers: &mut Vec<TcpStream>) {
+    let _ = stream.set_write_timeout(Some(WRITE_TIMEOUT));
+    if http::write_event_stream_head(&mut &stream).is_ok() {
+        subscribers.push(stream);
+    }
+}
+
+// Sends a frame of the event stream to every subscriber, dropping the ones
+// that went away.
+fn publish(subscribers: &mut Vec<TcpStream>, frame: &str) {
+    subscribers.retain(|stream| {
+        let mut writer: &TcpStream = stream;
+        writer
+            .write_all(frame.as_bytes())
+            .and_then(|_| writer.flush())
+            .is_ok()
+    });
+}
+
+// Dispatches a request to the endpoint of its method and path. Errors are
+// responses too, so endpoints can return them early.
+fn route(store: &mut dyn TaskStore, request: &Request) -> Result<Response, Response> {
+    match (request.get_method(), request.get_segments().as_slice()) {
+        ("GET", []) => Ok(Response::new(
+            200,
+            "text/html; charset=utf-8",
+            INDEX_PAGE.to_owned(),
+        )),
+        ("GET", ["tasks"]) => list_tasks(&*store, request),
+        ("POST", ["tasks"]) => create_task(store, request),
+        ("GET", ["tasks", id]) => {
+            let id: u8 = find_task(&*store, id)?;
+            Ok(task_response(&*store, id, 200))
+        }
+        ("PUT" | "PATCH", ["tasks", id]) => update_task(store, id, request),
+        ("DELETE", ["tasks", id]) => {
+            let id: u8 = find_task(&*store, id)?;
+            store.remove_tasks(&[id]);
+            Ok(Response::no_content())
+        }
+        ("PUT", ["tasks", id, "state"]) => {
+            let id: u8 = find_task(&*store, id)?;
+            let state: String = parse_state(&request.get_json()?["state"])?;
+            store.set_state(&[id], state);
+            Ok(task_response(&*store, id, 200))
+        }
+        (_, [] | ["events"] | ["tasks"] | ["tasks", _] | ["tasks", _, "state"]) => {
+            Err(Response::error(405, "Method not allowed"))
+        }
+        _ => Err(Response::error(404, "Not found")),
+    }
+}
+
+// Lists the tasks matching the 'filter' query parameter, ordered and paged
+// by the 'sort', 'limit' and 'offset' ones (e.g.:
+// '/tasks?filter=state:done&sort=priority:desc&limit=10').
+fn list_tasks(store: &dyn TaskStore, request: &Request) -> Result<Response, Response> {
+    let limit: Option<usize> = parse_count(request, "limit")?;
+    let offset: usize = parse_count(request, "offset")?.unwrap_or(0);
+    let tasks: Vec<Task> = super::query_tasks(
+        store,
+        request.get_query("filter"),
+        request.get_query("sort"),
+        limit,
+        offset,
+    )
+    .map_err(|error| Response::error(400, &error))?;
+    let tasks: Vec<JsonValue> = tasks.iter().map(JsonValue::from).collect();
+    Ok(Response::json(200, &JsonValue::Array(tasks)))
+}
+
+// Adds a task from a body with its 'task' text, and optionally its
+// 'priority', 'tags', 'milestone' and 'project'.
+fn create_task(store: &mut dyn TaskStore, request: &Request) -> Result<Response, Response> {
+    let body: JsonValue = request.get_json()?;
+    let text: String = parse_text(&body["task"])?;
+    let priority: Option<Priority> = parse_priority(&body)?;
+    let tags: Option<Vec<String>> = parse_tags(&body)?;
+    let milestone: Option<String> = parse_milestone(&*store, &body)?.flatten();
+    let project: Option<String> = optional_string(&body, "project")?;
+    if let Some(project) = &project
+        && store.get_project(project).is_none()
+    {
+        return Err(Response::error(
+            400,
+            &format!("No project found with name '{project}'"),
+        ));
+    }
+    let id: u8 = store
+        .create_task(text, milestone, project, &mut |task| {
+            if let Some(priority) = &priority {
+                task.set_priority(priority.clone());
+            }
+            for tag in tags.iter().flatten() {
+                task.add_tag(tag.clone());
+            }
+        })
+        .map_err(|error| Response::error(409, &error))?;
+    Ok(task_response(&*store, id, 201))
+}
+
+// Changes the fields given in the body: the 'task' text, 'state',
+// 'priority', 'tags' and 'milestone' (null to unplan the task). Every
+// field is checked before any is changed, and all of them are changed in one
+// operation.
+fn update_task(
+    store: &mut dyn TaskStore,
+    id: &str,
+    request: &Request,
+) -> Result<Response, Response> {
+    let id: u8 = find_task(&*store, id)?;
+    let body: JsonValue = request.get_json()?;
+    let text: Option<String> = match body.has_key("task") {
+        true => Some(parse_text(&body["task"])?),
+        false => None,
+    };
+    let state: Option<String> = match body.has_key("state") {
+        true => Some(parse_state(&body["state"])?),
+        false => None,
+    };
+    let priority: Option<Priority> = parse_priority(&body)?;
+    let tags: Option<Vec<String>> = parse_tags(&body)?;
+    let milestone: Option<Option<String>> = parse_milestone(&*store, &body)?;
+
+    store.edit_task(id, &mut |task| {
+        if let Some(text) = &text {
+            task.set_task(text.to_owned());
+        }
+        if let Some(state) = &state {
+            task.set_state(state.to_owned());
+        }
+        if let Some(priority) = &priority {
+            task.set_priority(priority.clone());
+        }
+        if let Some(tags) = &tags {
+            let current: Vec<String> = task.get_tags().clone();
+            for tag in current.iter().filter(|tag| !tags.contains(tag)) {
+                task.remove_tag(tag);
+            }
+            for tag in tags {
+                task.add_tag(tag.to_owned());
+            }
+        }
+        if let Some(milestone) = &milestone {
+            task.set_milestone(milestone.clone());
+        }
+    });
+    Ok(task_response(&*store, id, 200))
+}
+
+// The id of the task in the path, if there is such a task.
+fn find_task(store: &dyn TaskStore, id: &str) -> Result<u8, Response> {
+    id.parse()
+        .ok()
+        .filter(|id| store.get_task(*id).is_some())
+        .ok_or_else(|| Response::error(404, &format!("No task found with id '{id}'")))
+}
+
+fn task_response(store: &dyn TaskStore, id: u8, status: u16) -> Response {
+    match store.get_task(id) {
+        Some(task) => Response::json(status, &JsonValue::from(task)),
+        None => Response::error(404, &format!("No task found with id '{id}'")),
+    }
+}
+
+fn parse_count(request: &Request, name: &str) -> Result<Option<usize>, Response> {
+    request
+        .get_query(name)
+        .map(|count| {
+            count.parse().map_err(|_| {
+                Response::error(400, &format!("The '{name}' parameter must be a number"))
+            })
+        })
+        .transpose()
+}
+
+fn parse_text(json_value: &JsonValue) -> Result<String, Response> {
+    match json_value.as_str() {
+        Some(text) if !text.trim().is_empty() => Ok(text.to_owned()),
+        _ => Err(Response::error(
+            400,
+            "The 'task' field must be a non-empty string",
+        )),
+    }
+}
+
+fn parse_state(json_value: &JsonValue) -> Result<String, Response> {
+    let state: String = json_value
+        .as_str()
+        .ok_or_else(|| Response::error(400, "The 'state' field must be a string"))?
+        .to_owned();
+    TaskState::try_from(state.clone()).map_err(|error| Response::error(400, &error))?;
+    Ok(state)
+}
+
+fn parse_priority(body: &JsonValue) -> Result<Option<Priority>, Response> {
+    optional_string(body, "priority")?
+        .map(|priority| Priority::try_from(priority).map_err(|error| Response::error(400, &error)))
+        .transpose()
+}
+
+fn parse_tags(body: &JsonValue) -> Result<Option<Vec<String>>, Response> {
+    if !body.has_key("tags") {
+        return Ok(None);
+    }
+    let error = || Response::error(400, "The 'tags' field must be an array of strings");
+    if !body["tags"].is_array() {
+        return Err(error());
+    }
+    body["tags"]
+        .members()
+        .map(|tag| tag.as_str().map(str::to_owned).ok_or_else(error))
+        .collect::<Result<Vec<String>, Response>>()
+        .map(Some)
+}
+
+// The milestone to plan the task for, None when the field is missing and
+// Some(None) when it is null.
+fn parse_milestone(
+    store: &dyn TaskStore,
+    body: &JsonValue,
+) -> Result<Option<Option<String>>, Response> {
+    if !body.has_key("milestone") {
+        return Ok(None);
+    }
+    let milestone: Option<String> = optional_string(body, "milestone")?;
+    if let Some(milestone) = &milestone
+        && store.get_milestone(milestone).is_none()
+    {
+        return Err(Response::error(
+            400,
+            &format!("No milestone found with name '{milestone}'"),
+        ));
+    }
+    Ok(Some(milestone))
+}
+
+// A string field that may be missing or null.
+fn optional_string(body: &JsonValue, field: &str) -> Result<Option<String>, Response> {
+    let json_value: &JsonValue = &body[field];
+    if json_value.is_null() {
+        return Ok(None);
+    }
+    json_value
+        .as_str()
+        .map(|value| Some(value.to_owned()))
+        .ok_or_else(|| Response::error(400, &format!("The '{field}' field must be a string")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::json_store::JsonStore;
+    use crate::store::json_store::tests::empty_store;
+
+    fn request(method: &str, path: &str, body: &str) -> Request {
+        let text: String = format!(
+            "{method} {path} HTTP/1.1\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+        Request::read(&mut text.as_bytes()).unwrap()
+    }
+
+    fn status(store: &mut JsonStore, method: &str, path: &str, body: &str) -> u16 {
+        route(store, &request(method, path, body))
+            .unwrap_or_else(|error| error)
+            .get_status()
+    }
+
+    // Create a task and read it back
+    #[test]
+    fn test_route_creates_task() {
+        let (_home, mut store) = empty_store();
+
+        assert_eq!(
+            status(&mut store, "POST", "/tasks", r#"{"task": "Write docs"}"#),
+            201
+        );
+        assert_eq!(status(&mut store, "GET", "/tasks/1", ""), 200);
+    }
+
+    // Reject invalid fields before changing anything
+    #[test]
+    fn test_route_rejects_invalid_fields() {
+        let (_home, mut store) = empty_store();
+        status(&mut store, "POST", "/tasks", r#"{"task": "Write docs"}"#);
+
+        assert_eq!(status(&mut store, "POST", "/tasks", r#"{"task": ""}"#), 400);
+        assert_eq!(
+            status(
+                &mut store,
+                "PUT",
+                "/tasks/1",
+                r#"{"task": "Ship", "state": "later"}"#
+            ),
+            400
+        );
+        assert_eq!(store.get_task(1).unwrap().get_task(), "Write docs");
+    }
+
+    // Answer unknown tasks and paths with a 404
+    #[test]
+    fn test_route_not_found() {
+        let (_home, mut store) = empty_store();
+
+        assert_eq!(status(&mut store, "GET", "/tasks/7", ""), 404);
+        assert_eq!(status(&mut store, "GET", "/milestones", ""), 404);
+    }
+
+    // Answer known paths with an unsupported method with a 405
+    #[test]
+    fn test_route_method_not_allowed() {
+        let (_home, mut store) = empty_store();
+
+        assert_eq!(status(&mut store, "DELETE", "/tasks", ""), 405);
+        assert_eq!(status(&mut store, "POST", "/tasks/1/state", ""), 405);
+    }
+
+    // Set every field of a new task in one operation
+    #[test]
+    fn test_route_create_is_undone_at_once() {
+        let (_home, mut store) = empty_store();
+
+        let body: &str = r#"{"task": "Write docs", "priority": "high", "tags": ["docs", "v2"]}"#;
+        assert_eq!(status(&mut store, "POST", "/tasks", body), 201);
+        let task: &Task = store.get_task(1).unwrap();
+        assert_eq!(task.get_priority(), &Priority::High);
+        assert_eq!(
+            task.get_tags(),
+            &vec![String::from("docs"), String::from("v2")]
+        );
+
+        assert_eq!(store.undo(), Some("add #1".to_string()));
+        assert!(store.get_tasks().is_empty());
+    }
+
+    // Change every field of an update in one operation
+    #[test]
+    fn test_route_update_is_undone_at_once() {
+        let (_home, mut store) = empty_store();
+        status(&mut store, "POST", "/tasks", r#"{"task": "Write docs"}"#);
+
+        let body: &str = r#"{"task": "Ship docs", "state": "done", "tags": ["docs"]}"#;
+        assert_eq!(status(&mut store, "PUT", "/tasks/1", body), 200);
+        assert_eq!(store.undo(), Some("update #1".to_string()));
+
+        let task: &Task = store.get_task(1).unwrap();
+        assert_eq!(task.get_task(), "Write docs");
+        assert_eq!(task.get_state(), &TaskState::NotStarted);
+        assert!(task.get_tags().is_empty());
+    }
+}
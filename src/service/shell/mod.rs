@@ -0,0 +1,362 @@
+use super::TaskService;
+use super::terminal::{self, Key, RawMode};
+use crate::cli;
+use crate::config::Config;
+use crate::store::TaskStore;
+
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+const PROMPT: &str = "task-tracker> ";
+// The most lines kept in the history file.
+const HISTORY_SIZE: usize = 500;
+const KEYWORDS: &[&str] = &[
+    "not-started",
+    "in-progress",
+    "done",
+    "low",
+    "medium",
+    "high",
+    "--where",
+    "--dry-run",
+    "--project",
+    "--milestone",
+    "--sort",
+    "--limit",
+    "--offset",
+];
+
+// Runs the commands typed by the user against the open store, until 'exit'
+// or the end of the input. A failing command reports its error and the
+// shell carries on.
+pub fn run(store: &mut dyn TaskStore, config: &Config) {
+    let interactive: bool = io::stdin().is_terminal() && io::stdout().is_terminal();
+    let mut history: Vec<String> = if interactive { load_history() } else { vec![] };
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|info| {
+        eprintln!("{}", super::panic_message(info.payload()));
+    }));
+    loop {
+        let line: Option<String> = if interactive {
+            let words: Vec<String> = completion_words(&*store);
+            read_line(&history, &words)
+        } else {
+            read_plain_line()
+        };
+        let Some(line) = line else {
+            break;
+        };
+        let arguments: Vec<String> = match split_arguments(&line) {
+            Ok(arguments) => arguments,
+            Err(error) => {
+                println!("{error}");
+                continue;
+            }
+        };
+        if arguments.is_empty() {
+            continue;
+        }
+        if interactive && history.last() != Some(&line) {
+            history.push(line);
+            save_history(&history);
+        }
+        match arguments[0].as_str() {
+            "exit" | "quit" => break,
+            "help" => println!("{}", cli::HELP_TEXT),
+            "shell" => println!("Already in the shell"),
+            _ => {
+                let arguments: Vec<String> = std::iter::once("task-tracker".to_owned())
+                    .chain(arguments)
+                    .collect();
+                let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+                    let command: cli::Command = cli::Command::parse_from(&arguments);
+                    TaskService::new(&mut *store, config).execute(command);
+                }));
+            }
+        }
+    }
+    panic::set_hook(default_hook);
+}
+
+// Reads a line when the input is not a terminal, so commands can be piped
+// to the shell.
+fn read_plain_line() -> Option<String> {
+    let mut line: String = String::new();
+    match io::stdin().lock().read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(line.trim_end_matches(['\r', '\n']).to_owned()),
+    }
+}
+
+// Reads a line on the terminal with the cursor keys, the history on up and
+// down, and completion on tab. Returns None on Ctrl-D on an empty line.
+fn read_line(history: &[String], words: &[String]) -> Option<String> {
+    let Some(_raw_mode) = RawMode::enter() else {
+        print!("{PROMPT}");
+        let _ = io::stdout().flush();
+        return read_plain_line();
+    };
+    let mut input = io::stdin().lock();
+    let mut line: Line = Line::default();
+    let mut position: usize = history.len();
+    let mut draft: String = String::new();
+    redraw(&line);
+    loop {
+        let Some(key) = terminal::read_key(&mut input) else {
+            continue;
+        };
+        match key {
+            Key::Enter => {
+                print!("\r\n");
+                return Some(line.get_text());
+            }
+            Key::EndOfFile if line.characters.is_empty() => {
+                print!("\r\n");
+                return None;
+            }
+            Key::Interrupt => {
+                print!("^C\r\n");
+                line = Line::default();
+                position = history.len();
+            }
+            Key::Up if position > 0 => {
+                if position == history.len() {
+                    draft = line.get_text();
+                }
+                position -= 1;
+                line = Line::new(&history[position]);
+            }
+            Key::Down if position < history.len() => {
+                position += 1;
+                line = match history.get(position) {
+                    Some(entry) => Line::new(entry),
+                    None => Line::new(&draft),
+                };
+            }
+            Key::Tab => {
+                let matches: Vec<String> = line.complete(words);
+                if matches.len() > 1 {
+                    print!("\r\n{}\r\n", matches.join("  "));
+                }
+            }
+            key => line.apply(&key),
+        }
+        redraw(&line);
+    }
+}
+
+fn redraw(line: &Line) {
+    let column: usize = PROMPT.chars().count() + line.cursor;
+    print!("\r\x1b[K{PROMPT}{}\r\x1b[{column}C", line.get_text());
+    let _ = io::stdout().flush();
+}
+
+// The words offered by the completion: the commands, the states, the
+// priorities and the names in the store.
+fn completion_words(store: &dyn TaskStore) -> Vec<String> {
+    let mut words: Vec<String> = cli::COMMAND_NAMES
+        .iter()
+        .chain(KEYWORDS)
+        .map(|word| (*word).to_owned())
+        .collect();
+    for task in store.get_tasks() {
+        words.extend(task.get_tags().iter().cloned());
+    }
+    for view in store.get_views() {
+        words.push(format!("@{}", view.get_name()));
+    }
+    for milestone in store.get_milestones() {
+        words.push(milestone.get_name().to_owned());
+    }
+    for project in store.get_projects() {
+        words.push(project.get_name().to_owned());
+    }
+    words.sort();
+    words.dedup();
+    words
+}
+
+// Splits a line into arguments at whitespace, keeping quoted text together
+// (e.g.: add 'Buy eggs' -> ["add", "Buy eggs"]).
+fn split_arguments(line: &str) -> Result<Vec<String>, String> {
+    let mut arguments: Vec<String> = vec![];
+    let mut argument: Option<String> = None;
+    let mut quote: Option<char> = None;
+    let mut characters = line.chars();
+    while let Some(character) = characters.next() {
+        match (quote, character) {
+            (Some(open), _) if character == open => quote = None,
+            (Some('"') | None, '\\') => {
+                if let Some(escaped) = characters.next() {
+                    argument.get_or_insert_default().push(escaped);
+                }
+            }
+            (Some(_), _) => argument.get_or_insert_default().push(character),
+            (None, '\'' | '"') => {
+                quote = Some(character);
+                argument.get_or_insert_default();
+            }
+            (None, _) if character.is_whitespace() => arguments.extend(argument.take()),
+            (None, _) => argument.get_or_insert_default().push(character),
+        }
+    }
+    if quote.is_some() {
+        return Err("The command has an unclosed quote".to_owned());
+    }
+    arguments.extend(argument);
+    Ok(arguments)
+}
+
+// A line being typed, with the position of the cursor in characters.
+#[derive(Default)]
+struct Line {
+    characters: Vec<char>,
+    cursor: usize,
+}
+impl Line {
+    fn new(text: &str) -> Self {
+        let characters: Vec<char> = text.chars().collect();
+        let cursor: usize = characters.len();
+        Line { characters, cursor }
+    }
+    fn get_text(&self) -> String {
+        self.characters.iter().collect()
+    }
+
+    fn apply(&mut self, key: &Key) {
+        match key {
+            Key::Left => self.cursor = self.cursor.saturating_sub(1),
+            Key::Right => self.cursor = (self.cursor + 1).min(self.characters.len()),
+            Key::Home => self.cursor = 0,
+            Key::End => self.cursor = self.characters.len(),
+            Key::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.characters.remove(self.cursor);
+            }
+            Key::Char(character) => {
+                self.characters.insert(self.cursor, *character);
+                self.cursor += 1;
+            }
+            _ => {}
+        }
+    }
+
+    // Completes the word before the cursor: the first word among the
+    // commands, the others among the given words. Completes as far as the
+    // matches agree, and returns them.
+    fn complete(&mut self, words: &[String]) -> Vec<String> {
+        let start: usize = self.characters[..self.cursor]
+            .iter()
+            .rposition(|character| character.is_whitespace())
+            .map_or(0, |index| index + 1);
+        let first: bool = self.characters[..start]
+            .iter()
+            .all(|character| character.is_whitespace());
+        let prefix: String = self.characters[start..self.cursor].iter().collect();
+        let matches: Vec<String> = words
+            .iter()
+            .filter(|word| cli::COMMAND_NAMES.contains(&word.as_str()) == first)
+            .filter(|word| word.starts_with(&prefix))
+            .cloned()
+            .collect();
+        let completion: String = match matches.as_slice() {
+            [] => return matches,
+            [word] => format!("{word} "),
+            [word, others @ ..] => others.iter().fold(word.to_owned(), |common, other| {
+                common
+                    .chars()
+                    .zip(other.chars())
+                    .take_while(|(left, right)| left == right)
+                    .map(|(character, _)| character)
+                    .collect()
+            }),
+        };
+        for character in completion.chars().skip(prefix.chars().count()) {
+            self.apply(&Key::Char(character));
+        }
+        matches
+    }
+}
+
+fn get_history_file_path() -> PathBuf {
+    let home: PathBuf = match env::home_dir() {
+        Some(home_path) => home_path,
+        None => panic!("Can not read the home path"),
+    };
+    let path: &Path = Path::new("tasks.history");
+    home.join(path)
+}
+
+fn load_history() -> Vec<String> {
+    fs::read_to_string(get_history_file_path())
+        .map(|source| source.lines().map(str::to_owned).collect())
+        .unwrap_or_default()
+}
+
+fn save_history(history: &[String]) {
+    let start: usize = history.len().saturating_sub(HISTORY_SIZE);
+    let mut source: String = history[start..].join("\n");
+    source.push('\n');
+    // The shell works without a history.
+    let _ = fs::write(get_history_file_path(), source);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Split a line at whitespace, keeping quoted text together
+    #[test]
+    fn test_split_arguments_with_quotes() {
+        assert_eq!(
+            split_arguments(r#"add 'Buy eggs' --project "home stuff" a\ b ''"#),
+            Ok(vec![
+                "add".to_string(),
+                "Buy eggs".to_string(),
+                "--project".to_string(),
+                "home stuff".to_string(),
+                "a b".to_string(),
+                String::new(),
+            ])
+        );
+        assert!(split_arguments("add 'Buy eggs").is_err());
+    }
+
+    // Edit a line at the cursor
+    #[test]
+    fn test_line_edits_at_cursor() {
+        let mut line: Line = Line::new("ad");
+
+        line.apply(&Key::Left);
+        line.apply(&Key::Char('x'));
+        line.apply(&Key::End);
+        line.apply(&Key::Backspace);
+
+        assert_eq!(line.get_text(), "ax");
+    }
+
+    // Complete commands first, and other words after them
+    #[test]
+    fn test_line_completes_words() {
+        let words: Vec<String> = vec![
+            "tag".to_string(),
+            "trash".to_string(),
+            "tui".to_string(),
+            "done".to_string(),
+        ];
+        let mut line: Line = Line::new("tr");
+        assert_eq!(line.complete(&words), vec!["trash".to_string()]);
+        assert_eq!(line.get_text(), "trash ");
+
+        let mut line: Line = Line::new("mark 3 d");
+        line.complete(&words);
+        assert_eq!(line.get_text(), "mark 3 done ");
+
+        let mut line: Line = Line::new("t");
+        assert_eq!(line.complete(&words).len(), 3);
+        assert_eq!(line.get_text(), "t");
+    }
+}
@@ -0,0 +1,132 @@
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::process;
+
+// A key press, as far as the interactive commands care.
+#[derive(PartialEq, Debug)]
+pub enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    Enter,
+    Tab,
+    Escape,
+    Backspace,
+    Interrupt,
+    EndOfFile,
+    Char(char),
+    Unknown,
+}
+
+// Switches the terminal to raw mode, and back when dropped, including when
+// a command panics.
+pub struct RawMode {
+    saved: String,
+}
+impl RawMode {
+    pub fn enter() -> Option<Self> {
+        let saved: String = stty(&["-g"])?.trim().to_owned();
+        stty(&["raw", "-echo", "min", "0", "time", "1"])?;
+        Some(RawMode { saved })
+    }
+}
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = io::stdout().flush();
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+fn stty(arguments: &[&str]) -> Option<String> {
+    let terminal: File = File::open("/dev/tty").ok()?;
+    let output: process::Output = process::Command::new("stty")
+        .args(arguments)
+        .stdin(terminal)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+// Reads one key press. Returns None when no key was pressed for a moment,
+// so the caller can redraw after the terminal is resized.
+pub fn read_key(input: &mut impl Read) -> Option<Key> {
+    let mut byte: [u8; 1] = [0];
+    if input.read(&mut byte).ok()? == 0 {
+        return None;
+    }
+    let mut bytes: Vec<u8> = vec![byte[0]];
+    let length: usize = match byte[0] {
+        // Escape sequences are as long as what the terminal sent at once.
+        0x1b => 2,
+        0xc0..=0xdf => 1,
+        0xe0..=0xef => 2,
+        0xf0..=0xf7 => 3,
+        _ => 0,
+    };
+    while bytes.len() <= length && input.read(&mut byte).ok()? == 1 {
+        bytes.push(byte[0]);
+    }
+    Some(decode(&bytes))
+}
+
+// Decodes the bytes the terminal sends for one key press.
+fn decode(bytes: &[u8]) -> Key {
+    match bytes {
+        [0x1b] => Key::Escape,
+        [0x1b, b'[' | b'O', b'A'] => Key::Up,
+        [0x1b, b'[' | b'O', b'B'] => Key::Down,
+        [0x1b, b'[' | b'O', b'C'] => Key::Right,
+        [0x1b, b'[' | b'O', b'D'] => Key::Left,
+        [0x1b, b'[' | b'O', b'H'] | [0x01] => Key::Home,
+        [0x1b, b'[' | b'O', b'F'] | [0x05] => Key::End,
+        [b'\r'] | [b'\n'] => Key::Enter,
+        [b'\t'] => Key::Tab,
+        [0x7f] | [0x08] => Key::Backspace,
+        [0x03] => Key::Interrupt,
+        [0x04] => Key::EndOfFile,
+        _ => {
+            let mut characters = std::str::from_utf8(bytes).unwrap_or_default().chars();
+            match (characters.next(), characters.next()) {
+                (Some(character), None) if !character.is_control() => Key::Char(character),
+                _ => Key::Unknown,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Decode arrow keys, control keys and characters
+    #[test]
+    fn test_decode_keys() {
+        assert_eq!(decode(b"\x1b[A"), Key::Up);
+        assert_eq!(decode(b"\x1bOB"), Key::Down);
+        assert_eq!(decode(b"\x1b[D"), Key::Left);
+        assert_eq!(decode(b"\x01"), Key::Home);
+        assert_eq!(decode(b"\x1b"), Key::Escape);
+        assert_eq!(decode(b"\r"), Key::Enter);
+        assert_eq!(decode(b"\t"), Key::Tab);
+        assert_eq!(decode(b"\x7f"), Key::Backspace);
+        assert_eq!(decode(b"\x03"), Key::Interrupt);
+        assert_eq!(decode(b"\x04"), Key::EndOfFile);
+        assert_eq!(decode("é".as_bytes()), Key::Char('é'));
+        assert_eq!(decode(b"\x1b[Z"), Key::Unknown);
+    }
+
+    // A key press is read whole, escape sequences included
+    #[test]
+    fn test_read_key_reads_sequences() {
+        let mut input: &[u8] = b"\x1b[Bq";
+        assert_eq!(read_key(&mut input), Some(Key::Down));
+        assert_eq!(read_key(&mut input), Some(Key::Char('q')));
+        assert_eq!(read_key(&mut input), None);
+    }
+}
@@ -0,0 +1,392 @@
+use super::board;
+use super::terminal::{self, Key, RawMode};
+use crate::config::Config;
+use crate::model::filter::{self, Filter};
+use crate::model::sort::{ListOptions, SortKey};
+use crate::model::view::Column;
+use crate::model::{Task, TaskState};
+use crate::store::TaskStore;
+
+use std::io::{self, IsTerminal, Write};
+
+// The size used when the terminal does not report one.
+const DEFAULT_SIZE: (usize, usize) = (24, 80);
+const SEPARATOR: &str = " │ ";
+const HELP: &str = "j/k move  m mark  a add  e edit  d delete  / filter  b board  u undo  q quit";
+const REVERSE_START: &str = "\x1b[7m";
+const REVERSE_END: &str = "\x1b[0m";
+
+#[derive(PartialEq, Debug)]
+enum Mode {
+    List,
+    Board,
+}
+
+// What the status line is asking the user for.
+#[derive(PartialEq, Debug)]
+enum Prompt {
+    Filter,
+    Add(String),
+    Edit(u8, String),
+    Delete(u8),
+}
+
+// Runs the interface on the terminal until the user quits it.
+pub fn run(store: &mut dyn TaskStore, config: &Config) {
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        println!("The interactive interface needs a terminal");
+        return;
+    }
+    let Some(_raw_mode) = RawMode::enter() else {
+        println!("Can not switch the terminal to raw mode");
+        return;
+    };
+    let _screen: AlternateScreen = AlternateScreen::enter();
+    let mut app: App = App::new(store, config);
+    let mut input = io::stdin().lock();
+    while app.running {
+        let (rows, columns) = super::terminal_size().unwrap_or(DEFAULT_SIZE);
+        draw(&app.render(rows, columns));
+        if let Some(key) = terminal::read_key(&mut input) {
+            app.handle(key);
+        }
+    }
+}
+
+// Shows the interface on the alternate screen without a cursor, and brings
+// back the screen of the shell when dropped.
+struct AlternateScreen;
+impl AlternateScreen {
+    fn enter() -> Self {
+        print!("\x1b[?1049h\x1b[?25l");
+        let _ = io::stdout().flush();
+        AlternateScreen
+    }
+}
+impl Drop for AlternateScreen {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        let _ = io::stdout().flush();
+    }
+}
+
+// Redraws the whole screen, clearing what is left of each line.
+fn draw(lines: &[String]) {
+    let mut stdout = io::stdout().lock();
+    let _ = write!(stdout, "\x1b[H{}\x1b[K\x1b[J", lines.join("\x1b[K\r\n"));
+    let _ = stdout.flush();
+}
+
+// The filter typed so far: a filter query once it parses, and a title
+// search while it does not.
+fn parse_live_filter(text: &str) -> Option<Filter> {
+    let text: &str = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    Some(filter::query::parse_filter(text).unwrap_or_else(|_| Filter::Title(text.to_owned())))
+}
+
+// Applies a key to a line being typed. Returns Some(true) when the line is
+// submitted, Some(false) when it is cancelled and None while it is edited.
+fn edit_line(line: &mut String, key: &Key) -> Option<bool> {
+    match key {
+        Key::Enter => return Some(true),
+        Key::Escape | Key::Interrupt => return Some(false),
+        Key::Backspace => {
+            line.pop();
+        }
+        Key::Char(character) => line.push(*character),
+        _ => {}
+    }
+    None
+}
+
+// The next state in the workflow, going back to the start after done.
+fn next_state(state: &TaskState) -> TaskState {
+    match state {
+        TaskState::NotStarted => TaskState::InProgress,
+        TaskState::InProgress => TaskState::Done,
+        TaskState::Done => TaskState::NotStarted,
+    }
+}
+
+fn state_marker(state: &TaskState) -> &'static str {
+    match state {
+        TaskState::NotStarted => "[ ]",
+        TaskState::InProgress => "[~]",
+        TaskState::Done => "[x]",
+    }
+}
+
+struct App<'a> {
+    store: &'a mut dyn TaskStore,
+    config: &'a Config,
+    tasks: Vec<Task>,
+    selected: usize,
+    filter: String,
+    mode: Mode,
+    prompt: Option<Prompt>,
+    status: String,
+    running: bool,
+}
+
+impl<'a> App<'a> {
+    fn new(store: &'a mut dyn TaskStore, config: &'a Config) -> Self {
+        let mut app: App = App {
+            store,
+            config,
+            tasks: vec![],
+            selected: 0,
+            filter: String::new(),
+            mode: Mode::List,
+            prompt: None,
+            status: String::new(),
+            running: true,
+        };
+        app.refresh();
+        app
+    }
+
+    // Reloads the tasks matching the filter, keeping the selection in range.
+    fn refresh(&mut self) {
+        self.tasks = match parse_live_filter(&self.filter) {
+            Some(filter) => self.store.find_tasks(&filter),
+            None => self.store.get_tasks(),
+        };
+        self.selected = self.selected.min(self.tasks.len().saturating_sub(1));
+    }
+
+    fn selected_task(&self) -> Option<&Task> {
+        self.tasks.get(self.selected)
+    }
+
+    fn handle(&mut self, key: Key) {
+        if let Some(prompt) = self.prompt.take() {
+            self.answer(prompt, key);
+            return;
+        }
+        self.status.clear();
+        match key {
+            Key::Up | Key::Char('k') => self.selected = self.selected.saturating_sub(1),
+            Key::Down | Key::Char('j') if self.selected + 1 < self.tasks.len() => {
+                self.selected += 1
+            }
+            Key::Char('m') => self.mark(),
+            Key::Char('a') => self.prompt = Some(Prompt::Add(String::new())),
+            Key::Char('e') => {
+                if let Some(task) = self.selected_task() {
+                    self.prompt = Some(Prompt::Edit(task.get_id(), task.get_task().to_owned()));
+                }
+            }
+            Key::Char('d') => {
+                if let Some(task) = self.selected_task() {
+                    self.prompt = Some(Prompt::Delete(task.get_id()));
+                }
+            }
+            Key::Char('/') => self.prompt = Some(Prompt::Filter),
+            Key::Char('b') => {
+                self.mode = match self.mode {
+                    Mode::List => Mode::Board,
+                    Mode::Board => Mode::List,
+                }
+            }
+            Key::Char('u') => {
+                self.status = match self.store.undo() {
+                    Some(operation) => format!("Undid '{operation}'"),
+                    None => "Nothing to undo".to_owned(),
+                };
+                self.refresh();
+            }
+            Key::Char('r') => {
+                self.status = match self.store.redo() {
+                    Some(operation) => format!("Redid '{operation}'"),
+                    None => "Nothing to redo".to_owned(),
+                };
+                self.refresh();
+            }
+            Key::Escape if !self.filter.is_empty() => {
+                self.filter.clear();
+                self.refresh();
+            }
+            Key::Char('q') | Key::Interrupt | Key::EndOfFile => self.running = false,
+            _ => {}
+        }
+    }
+
+    // Applies a key to the open prompt, which stays open until it is
+    // submitted or cancelled.
+    fn answer(&mut self, prompt: Prompt, key: Key) {
+        match prompt {
+            Prompt::Filter => {
+                let mut filter: String = self.filter.clone();
+                match edit_line(&mut filter, &key) {
+                    Some(true) => {}
+                    Some(false) => self.filter.clear(),
+                    None => {
+                        self.filter = filter;
+                        self.prompt = Some(Prompt::Filter);
+                    }
+                }
+                self.refresh();
+            }
+            Prompt::Add(mut text) => match edit_line(&mut text, &key) {
+                Some(true) if !text.trim().is_empty() => {
+                    self.status = match self.store.add_task(text.trim().to_owned(), None, None) {
+                        Ok(id) => format!("Added task {id}"),
+                        Err(error) => error,
+                    };
+                    self.refresh();
+                }
+                Some(_) => self.status = "Nothing added".to_owned(),
+                None => self.prompt = Some(Prompt::Add(text)),
+            },
+            Prompt::Edit(id, mut text) => match edit_line(&mut text, &key) {
+                Some(true) if !text.trim().is_empty() => {
+                    if self.store.update_task(id, text.trim().to_owned()) {
+                        self.status = format!("Updated task {id}");
+                    }
+                    self.refresh();
+                }
+                Some(_) => self.status = "Nothing updated".to_owned(),
+                None => self.prompt = Some(Prompt::Edit(id, text)),
+            },
+            Prompt::Delete(id) => {
+                if key == Key::Char('y') && !self.store.remove_tasks(&[id]).is_empty() {
+                    self.status = format!("Deleted task {id}");
+                    self.refresh();
+                } else {
+                    self.status = "Nothing deleted".to_owned();
+                }
+            }
+        }
+    }
+
+    // Moves the selected task to the next state of the workflow.
+    fn mark(&mut self) {
+        let Some(task) = self.selected_task() else {
+            return;
+        };
+        let id: u8 = task.get_id();
+        let state: TaskState = next_state(task.get_state());
+        self.store.set_state(&[id], String::from(&state));
+        self.status = super::wip_warning(&*self.store, self.config, state.clone())
+            .unwrap_or_else(|| format!("Marked task {id} as {}", String::from(&state)));
+        self.refresh();
+    }
+
+    // Lays out a screen of the given size: a title bar, the tasks and a
+    // status line.
+    fn render(&self, rows: usize, columns: usize) -> Vec<String> {
+        let body_rows: usize = rows.saturating_sub(2);
+        let mut title: String = format!(" Tasks: {} shown", self.tasks.len());
+        if !self.filter.is_empty() {
+            title.push_str(&format!("  filter: {}", self.filter));
+        }
+        let title: String = board::truncate(&title, columns);
+        let mut lines: Vec<String> = vec![format!("{REVERSE_START}{title:<columns$}{REVERSE_END}")];
+        let mut body: Vec<String> = match self.mode {
+            Mode::List => self.render_list(body_rows, columns),
+            Mode::Board => self.render_board(columns),
+        };
+        body.resize(body_rows, String::new());
+        lines.extend(body);
+        lines.push(board::truncate(&self.status_line(), columns));
+        lines
+    }
+
+    // The tasks on the left, scrolled to the selection, and the details of
+    // the selected task on the right.
+    fn render_list(&self, rows: usize, columns: usize) -> Vec<String> {
+        let list_width: usize = (columns * 2 / 5).max(20);
+        let detail_width: usize = columns.saturating_sub(list_width + SEPARATOR.chars().count());
+        let offset: usize = (self.selected + 1).saturating_sub(rows);
+        let details: Vec<String> = match self.selected_task() {
+            Some(task) => super::format_highlighted_task(task, &[])
+                .lines()
+                .map(|line| board::truncate(line, detail_width))
+                .collect(),
+            None => vec!["No tasks".to_owned()],
+        };
+        (0..rows)
+            .map(|row| {
+                let index: usize = offset + row;
+                let entry: String = match self.tasks.get(index) {
+                    Some(task) => {
+                        let marker: &str = state_marker(task.get_state());
+                        let entry: String =
+                            format!("{marker} #{} {}", task.get_id(), task.get_task());
+                        format!("{:<list_width$}", board::truncate(&entry, list_width))
+                    }
+                    None => " ".repeat(list_width),
+                };
+                let entry: String = if index == self.selected && index < self.tasks.len() {
+                    format!("{REVERSE_START}{entry}{REVERSE_END}")
+                } else {
+                    entry
+                };
+                let detail: &str = details.get(row).map(String::as_str).unwrap_or_default();
+                format!("{entry}{SEPARATOR}{detail}")
+            })
+            .collect()
+    }
+
+    fn render_board(&self, columns: usize) -> Vec<String> {
+        let order: ListOptions = ListOptions::new(
+            vec![
+                SortKey::new(Column::Priority, true),
+                SortKey::new(Column::Id, false),
+            ],
+            None,
+            0,
+        );
+        let tasks: Vec<Task> = order.apply(self.tasks.clone());
+        let board_columns: Vec<board::BoardColumn> = super::board_columns(&tasks, self.config);
+        board::render(&board_columns, columns)
+            .lines()
+            .map(|line| board::truncate(line, columns))
+            .collect()
+    }
+
+    fn status_line(&self) -> String {
+        match &self.prompt {
+            Some(Prompt::Filter) => format!("Filter: {}_", self.filter),
+            Some(Prompt::Add(text)) => format!("Add: {text}_"),
+            Some(Prompt::Edit(id, text)) => format!("Edit #{id}: {text}_"),
+            Some(Prompt::Delete(id)) => format!("Delete task {id}? [y/N]"),
+            None if !self.status.is_empty() => self.status.clone(),
+            None => HELP.to_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The live filter searches titles until the query parses
+    #[test]
+    fn test_parse_live_filter() {
+        assert_eq!(parse_live_filter("  "), None);
+        assert_eq!(
+            parse_live_filter("tag:work"),
+            Some(Filter::Tag("work".to_string()))
+        );
+        assert_eq!(
+            parse_live_filter("login ("),
+            Some(Filter::Title("login (".to_string()))
+        );
+    }
+
+    // Edit a line until it is submitted
+    #[test]
+    fn test_edit_line() {
+        let mut line: String = "ab".to_string();
+
+        assert_eq!(edit_line(&mut line, &Key::Backspace), None);
+        assert_eq!(edit_line(&mut line, &Key::Char('c')), None);
+        assert_eq!(line, "ac");
+        assert_eq!(edit_line(&mut line, &Key::Enter), Some(true));
+        assert_eq!(edit_line(&mut line, &Key::Escape), Some(false));
+    }
+}
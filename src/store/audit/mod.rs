@@ -0,0 +1,86 @@
+use json::{JsonValue, object};
+use std::env;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+// Who mutations are recorded for when nobody else was given, e.g. on the
+// command line.
+pub const LOCAL_ACTOR: &str = "local";
+
+// One mutation of the store: when it ran, who ran it and what it was.
+#[derive(PartialEq, Debug, Clone)]
+pub struct AuditEntry {
+    at: u64,
+    actor: String,
+    operation: String,
+}
+
+impl AuditEntry {
+    pub fn new(at: u64, actor: String, operation: String) -> Self {
+        AuditEntry {
+            at,
+            actor,
+            operation,
+        }
+    }
+    pub fn get_at(&self) -> u64 {
+        self.at
+    }
+    pub fn get_actor(&self) -> &String {
+        &self.actor
+    }
+    pub fn get_operation(&self) -> &String {
+        &self.operation
+    }
+
+    // Appends the entry to the audit log, one JSON object per line, so the
+    // log is never read back or rewritten.
+    pub fn append(&self) {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(AuditEntry::get_file_path())
+            .expect("Can not open the audit log");
+        writeln!(file, "{}", JsonValue::from(self).dump()).expect("Can not write the audit log");
+    }
+
+    fn get_file_path() -> PathBuf {
+        let home: PathBuf = match env::home_dir() {
+            Some(home_path) => home_path,
+            None => panic!("Can not read the home path"),
+        };
+        let path: &Path = Path::new("tasks.audit.jsonl");
+        home.join(path)
+    }
+}
+
+impl From<&AuditEntry> for JsonValue {
+    fn from(entry: &AuditEntry) -> JsonValue {
+        object! {
+            "at" => entry.get_at(),
+            "actor" => entry.get_actor().to_owned(),
+            "operation" => entry.get_operation().to_owned()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Convert AuditEntry to a line of the audit log
+    #[test]
+    fn test_audit_entry_to_json() {
+        let entry: AuditEntry = AuditEntry::new(
+            1_790_000_000,
+            "alice".to_string(),
+            "mark #3 done".to_string(),
+        );
+
+        assert_eq!(
+            JsonValue::from(&entry).dump(),
+            "{\"at\":1790000000,\"actor\":\"alice\",\"operation\":\"mark #3 done\"}"
+        );
+    }
+}
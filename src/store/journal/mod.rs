@@ -0,0 +1,217 @@
+use json::{JsonValue, object};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// One recorded mutation: a human readable description of the operation and
+// the store contents as they were right before the operation ran.
+#[derive(Debug, Clone)]
+pub struct JournalEntry {
+    operation: String,
+    snapshot: JsonValue,
+}
+
+impl JournalEntry {
+    pub fn get_operation(&self) -> &String {
+        &self.operation
+    }
+
+    pub fn get_snapshot(&self) -> &JsonValue {
+        &self.snapshot
+    }
+}
+
+impl From<&JournalEntry> for JsonValue {
+    fn from(entry: &JournalEntry) -> JsonValue {
+        object! {
+            "operation" => entry.operation.to_owned(),
+            "snapshot" => entry.snapshot.clone()
+        }
+    }
+}
+
+impl TryFrom<&JsonValue> for JournalEntry {
+    type Error = String;
+    fn try_from(json_object: &JsonValue) -> Result<Self, Self::Error> {
+        let operation: String = json_object["operation"]
+            .as_str()
+            .ok_or("Can not parse journal operation")?
+            .to_owned();
+        let snapshot: JsonValue = json_object["snapshot"].clone();
+        Ok(JournalEntry {
+            operation,
+            snapshot,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Journal {
+    undo: Vec<JournalEntry>,
+    redo: Vec<JournalEntry>,
+    depth: usize,
+}
+
+impl Journal {
+    pub fn new(depth: usize) -> Self {
+        Journal {
+            undo: vec![],
+            redo: vec![],
+            depth,
+        }
+    }
+
+    pub fn load(depth: usize) -> Self {
+        let file_path: PathBuf = Journal::get_file_path();
+        let mut journal: Journal = Journal::new(depth);
+        if file_path.exists() {
+            let source: String = fs::read_to_string(file_path).unwrap();
+            let json_object: JsonValue = json::parse(&source).unwrap();
+            journal.undo = Journal::entries_from_json_array(&json_object["undo"]);
+            journal.redo = Journal::entries_from_json_array(&json_object["redo"]);
+            journal.truncate();
+        }
+        journal
+    }
+
+    pub fn get_depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn save(&self) {
+        let file_path: PathBuf = Journal::get_file_path();
+        let contents: String = object! {
+            "undo" => Journal::entries_to_json_array(&self.undo),
+            "redo" => Journal::entries_to_json_array(&self.redo)
+        }
+        .to_string();
+        fs::write(file_path, contents).unwrap()
+    }
+
+    // Records a new mutation. A fresh mutation invalidates everything that
+    // was undone before, so the redo history is dropped.
+    pub fn record(&mut self, operation: String, snapshot: JsonValue) {
+        self.undo.push(JournalEntry {
+            operation,
+            snapshot,
+        });
+        self.redo.clear();
+        self.truncate();
+    }
+
+    // Pops the last mutation and moves the current store contents onto the
+    // redo history. The returned entry holds the snapshot to restore.
+    pub fn undo(&mut self, current: JsonValue) -> Option<JournalEntry> {
+        let entry: JournalEntry = self.undo.pop()?;
+        self.redo.push(JournalEntry {
+            operation: entry.operation.clone(),
+            snapshot: current,
+        });
+        Some(entry)
+    }
+
+    pub fn redo(&mut self, current: JsonValue) -> Option<JournalEntry> {
+        let entry: JournalEntry = self.redo.pop()?;
+        self.undo.push(JournalEntry {
+            operation: entry.operation.clone(),
+            snapshot: current,
+        });
+        self.truncate();
+        Some(entry)
+    }
+
+    fn truncate(&mut self) {
+        if self.undo.len() > self.depth {
+            let overflow: usize = self.undo.len() - self.depth;
+            self.undo.drain(..overflow);
+        }
+        if self.redo.len() > self.depth {
+            let overflow: usize = self.redo.len() - self.depth;
+            self.redo.drain(..overflow);
+        }
+    }
+
+    fn entries_from_json_array(json_array: &JsonValue) -> Vec<JournalEntry> {
+        let mut entries: Vec<JournalEntry> = vec![];
+        for json_value in json_array.members() {
+            let entry: JournalEntry = JournalEntry::try_from(json_value).unwrap();
+            entries.push(entry);
+        }
+        entries
+    }
+
+    fn entries_to_json_array(entries: &[JournalEntry]) -> JsonValue {
+        let mut json_array: JsonValue = json::array![];
+        for entry in entries {
+            json_array.push(JsonValue::from(entry)).unwrap();
+        }
+        json_array
+    }
+
+    fn get_file_path() -> PathBuf {
+        let home: PathBuf = match env::home_dir() {
+            Some(home_path) => home_path,
+            None => panic!("Can not read the home path"),
+        };
+        let path: &Path = Path::new("tasks.journal.json");
+        home.join(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Undo returns the snapshot taken before the last operation
+    #[test]
+    fn test_undo_returns_last_recorded_snapshot() {
+        let mut journal: Journal = Journal::new(10);
+        journal.record("add #1".to_string(), json::array![]);
+
+        let entry: JournalEntry = journal.undo(json::array![1]).unwrap();
+        assert_eq!(entry.get_operation(), "add #1");
+        assert_eq!(entry.get_snapshot(), &json::array![]);
+    }
+
+    // Redo restores the state that was current before the undo
+    #[test]
+    fn test_redo_returns_state_before_undo() {
+        let mut journal: Journal = Journal::new(10);
+        journal.record("add #1".to_string(), json::array![]);
+        journal.undo(json::array![1]);
+
+        let entry: JournalEntry = journal.redo(json::array![]).unwrap();
+        assert_eq!(entry.get_snapshot(), &json::array![1]);
+        assert!(journal.redo(json::array![1]).is_none());
+    }
+
+    // Recording a new operation drops the redo history
+    #[test]
+    fn test_record_clears_redo_history() {
+        let mut journal: Journal = Journal::new(10);
+        journal.record("add #1".to_string(), json::array![]);
+        journal.undo(json::array![1]);
+        journal.record("add #1".to_string(), json::array![]);
+
+        assert!(journal.redo(json::array![1]).is_none());
+    }
+
+    // The journal keeps at most `depth` operations
+    #[test]
+    fn test_record_is_bounded_by_depth() {
+        let mut journal: Journal = Journal::new(2);
+        journal.record("add #1".to_string(), json::array![]);
+        journal.record("add #2".to_string(), json::array![1]);
+        journal.record("add #3".to_string(), json::array![1, 2]);
+
+        assert_eq!(
+            journal.undo(json::array![]).unwrap().get_operation(),
+            "add #3"
+        );
+        assert_eq!(
+            journal.undo(json::array![]).unwrap().get_operation(),
+            "add #2"
+        );
+        assert!(journal.undo(json::array![]).is_none());
+    }
+}
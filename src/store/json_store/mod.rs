@@ -1,126 +1,770 @@
-use super::TaskStore;
+use super::audit::{self, AuditEntry};
+use super::journal::{Journal, JournalEntry};
+use super::search_index::SearchIndex;
+use super::{TaskStore, format_ids};
 
-use crate::model::{Task, TaskState};
+use crate::config::Config;
+use crate::date;
+use crate::model::filter::Filter;
+use crate::model::import::ImportedTask;
+use crate::model::milestone::Milestone;
+use crate::model::project::Project;
+use crate::model::search::{SearchHit, SearchTerm};
+use crate::model::sync::Change;
+use crate::model::view::View;
+use crate::model::{Priority, Task, TaskState, TrashedTask};
 
-use json::JsonValue;
+use json::{JsonValue, object};
+use std::cell::OnceCell;
 use std::env;
-use std::fs;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 #[derive(Debug)]
 pub struct JsonStore {
     store: Vec<Task>,
+    trash: Vec<TrashedTask>,
+    views: Vec<View>,
+    milestones: Vec<Milestone>,
+    projects: Vec<Project>,
+    // The archive lives in its own file and is only read when an archive
+    // command needs it, so it does not slow down everyday commands.
+    archive: Option<Vec<Task>>,
     last_id: u8,
+    journal: Journal,
+    // Who the mutations are audited for.
+    actor: Option<String>,
+    // Read from its file on the first search or change, built from the tasks
+    // when the file is missing, then saved along with every change.
+    search_index: OnceCell<SearchIndex>,
+    // When the file was last read or saved, to notice the changes of other
+    // processes.
+    modified: Option<SystemTime>,
 }
 
 impl TaskStore for JsonStore {
-    fn get_tasks(&self, state_filter: String) -> Vec<Task> {
-        dbg!(&state_filter);
-        if state_filter == String::from("None") {
-            return self.store.clone();
-        } else {
-            let state_filer = TaskState::try_from(state_filter).unwrap();
-            let mut store: Vec<Task> = vec![];
-            for task in &self.store {
-                if task.get_state() == &state_filer {
-                    store.push(task.clone());
-                }
+    fn get_tasks(&self) -> Vec<Task> {
+        self.store.clone()
+    }
+
+    fn find_tasks(&self, filter: &Filter) -> Vec<Task> {
+        self.store
+            .iter()
+            .filter(|task| filter.matches(task))
+            .cloned()
+            .collect()
+    }
+
+    fn search(&self, terms: &[SearchTerm]) -> Vec<SearchHit> {
+        let index: &SearchIndex = self.search_index.get_or_init(|| {
+            JsonStore::read_search_index().unwrap_or_else(|| {
+                let index: SearchIndex = SearchIndex::build(&self.store);
+                JsonStore::write_search_index(&index);
+                index
+            })
+        });
+        let mut hits: Vec<SearchHit> = vec![];
+        // The index returns the most relevant tasks first.
+        for (id, _, matched_words) in index.search(terms) {
+            if let Some(task) = self.get_task(id) {
+                hits.push(SearchHit::new(task.clone(), matched_words));
             }
-            return store;
         }
+        hits
     }
 
     fn get_task(&self, id: u8) -> Option<&Task> {
         for task in &self.store {
             let task_id: u8 = task.get_id();
             if task_id == id {
-                return Some(&task);
+                return Some(task);
             }
         }
-        return None;
+        None
+    }
+
+    fn add_task(
+        &mut self,
+        task: String,
+        milestone: Option<String>,
+        project: Option<String>,
+    ) -> Result<u8, String> {
+        self.create_task(task, milestone, project, &mut |_| {})
     }
 
-    fn add_task(&mut self, task: String) -> u8 {
-        let id: u8 = self.last_id + 1;
-        let task: Task = Task::new(id, task, TaskState::NotStarted);
+    fn create_task(
+        &mut self,
+        task: String,
+        milestone: Option<String>,
+        project: Option<String>,
+        change: &mut dyn FnMut(&mut Task),
+    ) -> Result<u8, String> {
+        let snapshot: JsonValue = self.to_json_object();
+        let id: u8 = self.next_id()?;
+        let mut task: Task = Task::new(id, task, TaskState::NotStarted);
+        task.set_milestone(milestone);
+        if let Some(project) = project.and_then(|name| {
+            self.projects
+                .iter_mut()
+                .find(|project| project.get_name() == &name)
+        }) {
+            task.set_project(project.get_name().to_owned(), project.next_number());
+        }
+        change(&mut task);
         self.store.push(task);
         self.last_id += 1;
-        self.save();
-        return id;
+        self.reindex(&[id]);
+        self.commit(format!("add #{id}"), snapshot);
+        Ok(id)
+    }
+
+    fn import_tasks(
+        &mut self,
+        projects: Vec<Project>,
+        tasks: &[ImportedTask],
+    ) -> Result<Vec<u8>, String> {
+        let free: usize = usize::from(u8::MAX - self.last_id);
+        if tasks.len() > free {
+            return Err(format!(
+                "Can not import {} tasks, only {free} task ids are left",
+                tasks.len()
+            ));
+        }
+        let snapshot: JsonValue = self.to_json_object();
+        self.projects.extend(projects);
+        let mut ids: Vec<u8> = vec![];
+        for imported in tasks {
+            let id: u8 = self.next_id()?;
+            let mut task: Task = Task::new(id, imported.get_title().clone(), TaskState::NotStarted);
+            if let Some(project) = imported.get_project().and_then(|name| {
+                self.projects
+                    .iter_mut()
+                    .find(|project| project.get_name() == name)
+            }) {
+                task.set_project(project.get_name().to_owned(), project.next_number());
+            }
+            task.set_state(String::from(imported.get_state()));
+            task.set_priority(imported.get_priority().clone());
+            for tag in imported.get_tags() {
+                task.add_tag(tag.clone());
+            }
+            for note in imported.get_notes() {
+                task.add_note(note.clone());
+            }
+            self.store.push(task);
+            self.last_id = id;
+            ids.push(id);
+        }
+        self.reindex(&ids);
+        self.commit(format!("import {}", format_ids(&ids)), snapshot);
+        Ok(ids)
     }
 
-    fn set_state(&mut self, id: u8, state: String) -> bool {
-        let task = self.get_mut_task(id);
-        match task {
-            Some(task) => {
-                task.set_state(state.clone());
-                self.save();
-                true
+    fn sync_tasks(&mut self, changes: &[Change]) -> Result<Vec<u8>, String> {
+        let added: usize = changes
+            .iter()
+            .filter(|change| matches!(change, Change::AddTask { .. }))
+            .count();
+        let free: usize = usize::from(u8::MAX - self.last_id);
+        if added > free {
+            return Err(format!(
+                "Can not add {added} tasks from the file, only {free} task ids are left"
+            ));
+        }
+        let snapshot: JsonValue = self.to_json_object();
+        let deleted_at: u64 = date::now();
+        let mut ids: Vec<u8> = vec![];
+        let mut changed: Vec<u8> = vec![];
+        for change in changes {
+            match change {
+                Change::AddTask { title, done, .. } => {
+                    let id: u8 = self.next_id()?;
+                    let mut task: Task = Task::new(id, title.clone(), TaskState::NotStarted);
+                    if *done {
+                        task.set_state(String::from(&TaskState::Done));
+                    }
+                    self.store.push(task);
+                    self.last_id = id;
+                    ids.push(id);
+                    changed.push(id);
+                }
+                Change::MarkTask { id, done } => {
+                    let state: TaskState = match done {
+                        true => TaskState::Done,
+                        false => TaskState::NotStarted,
+                    };
+                    if let Some(task) = self.get_mut_task(*id) {
+                        task.set_state(String::from(&state));
+                        changed.push(*id);
+                    }
+                }
+                Change::RenameTask { id, title } => {
+                    if let Some(task) = self.get_mut_task(*id) {
+                        task.set_task(title.clone());
+                        changed.push(*id);
+                    }
+                }
+                Change::DeleteTask(id) => {
+                    if let Some(index) = self.store.iter().position(|task| task.get_id() == *id) {
+                        let task: Task = self.store.remove(index);
+                        self.trash.push(TrashedTask::new(task, deleted_at));
+                        changed.push(*id);
+                    }
+                }
+                _ => {}
             }
-            None => false,
         }
+        if !changed.is_empty() {
+            self.reindex(&changed);
+            self.commit(format!("sync {}", format_ids(&changed)), snapshot);
+        }
+        Ok(ids)
+    }
+
+    fn set_state(&mut self, ids: &[u8], state: String) -> Vec<u8> {
+        let operation: String = format!("mark {} {state}", format_ids(ids));
+        self.apply(ids, operation, |task| task.set_state(state.clone()))
     }
     fn update_task(&mut self, id: u8, updated_task: String) -> bool {
-        let task = self.get_mut_task(id);
-        match task {
-            Some(task) => {
-                task.set_task(updated_task.clone());
-                self.save();
-                true
+        let operation: String = format!("update #{id}");
+        let updated: Vec<u8> =
+            self.apply(&[id], operation, |task| task.set_task(updated_task.clone()));
+        !updated.is_empty()
+    }
+    fn edit_task(&mut self, id: u8, change: &mut dyn FnMut(&mut Task)) -> bool {
+        let operation: String = format!("update #{id}");
+        let edited: Vec<u8> = self.apply(&[id], operation, change);
+        !edited.is_empty()
+    }
+    fn set_priority(&mut self, ids: &[u8], priority: Priority) -> Vec<u8> {
+        let operation: String = format!("priority {} {}", format_ids(ids), String::from(&priority));
+        self.apply(ids, operation, |task| task.set_priority(priority.clone()))
+    }
+    fn tag_tasks(&mut self, ids: &[u8], tag: String) -> Vec<u8> {
+        let operation: String = format!("tag {} {tag}", format_ids(ids));
+        self.apply(ids, operation, |task| task.add_tag(tag.clone()))
+    }
+    fn add_note(&mut self, id: u8, note: String) -> bool {
+        let operation: String = format!("note #{id}");
+        let noted: Vec<u8> = self.apply(&[id], operation, |task| task.add_note(note.clone()));
+        !noted.is_empty()
+    }
+    fn untag_tasks(&mut self, ids: &[u8], tag: String) -> Vec<u8> {
+        let operation: String = format!("untag {} {tag}", format_ids(ids));
+        self.apply(ids, operation, |task| task.remove_tag(&tag))
+    }
+    fn set_milestone(&mut self, ids: &[u8], milestone: Option<String>) -> Vec<u8> {
+        let operation: String = match &milestone {
+            Some(milestone) => format!("milestone set {} {milestone}", format_ids(ids)),
+            None => format!("milestone unset {}", format_ids(ids)),
+        };
+        self.apply(ids, operation, |task| task.set_milestone(milestone.clone()))
+    }
+    fn remove_tasks(&mut self, ids: &[u8]) -> Vec<u8> {
+        let snapshot: JsonValue = self.to_json_object();
+        let deleted_at: u64 = date::now();
+        let mut removed: Vec<u8> = vec![];
+        for id in ids {
+            if let Some(index) = self.store.iter().position(|task| task.get_id() == *id) {
+                let task: Task = self.store.remove(index);
+                self.trash.push(TrashedTask::new(task, deleted_at));
+                removed.push(*id);
+            }
+        }
+        if !removed.is_empty() {
+            self.reindex(&removed);
+            self.commit(format!("delete {}", format_ids(&removed)), snapshot);
+        }
+        removed
+    }
+
+    fn get_trash(&self) -> Vec<TrashedTask> {
+        self.trash.clone()
+    }
+
+    fn restore_task(&mut self, id: u8) -> bool {
+        let snapshot: JsonValue = self.to_json_object();
+        for (index, trashed_task) in self.trash.iter().enumerate() {
+            if id == trashed_task.get_task().get_id() {
+                let task: Task = self.trash.remove(index).into_task();
+                // Keep the tasks ordered by id, as if the task was never deleted.
+                let position: usize = self.store.partition_point(|task| task.get_id() < id);
+                self.store.insert(position, task);
+                self.reindex(&[id]);
+                self.commit(format!("restore #{id}"), snapshot);
+                return true;
+            }
+        }
+        false
+    }
+
+    fn purge_trash(&mut self, older_than: Option<u64>) -> Vec<u8> {
+        let snapshot: JsonValue = self.to_json_object();
+        let now: u64 = date::now();
+        let mut purged: Vec<u8> = vec![];
+        self.trash.retain(|trashed_task| {
+            let age: u64 = now.saturating_sub(trashed_task.get_deleted_at());
+            let expired: bool = older_than.is_none_or(|older_than| age >= older_than);
+            if expired {
+                purged.push(trashed_task.get_task().get_id());
+            }
+            !expired
+        });
+        if !purged.is_empty() {
+            self.commit(String::from("purge"), snapshot);
+        }
+        purged
+    }
+
+    fn get_archived_tasks(&self) -> Vec<Task> {
+        match &self.archive {
+            Some(archive) => archive.clone(),
+            None => Self::read_archive(),
+        }
+    }
+
+    fn archive_tasks(&mut self, older_than: Option<u64>) -> Vec<u8> {
+        self.load_archive();
+        let snapshot: JsonValue = self.to_json_object();
+        let now: u64 = date::now();
+        let mut archived: Vec<Task> = vec![];
+        self.store.retain(|task| {
+            // Done tasks without a history have no known age, so only an
+            // archive without '--older-than' takes them.
+            let expired: bool = match (task.get_state(), older_than, task.get_done_at()) {
+                (TaskState::Done, None, _) => true,
+                (TaskState::Done, Some(older_than), Some(done_at)) => {
+                    now.saturating_sub(done_at) >= older_than
+                }
+                _ => false,
+            };
+            if expired {
+                archived.push(task.clone());
             }
-            None => false,
+            !expired
+        });
+        let ids: Vec<u8> = archived.iter().map(Task::get_id).collect();
+        if !ids.is_empty() {
+            self.archive.get_or_insert_default().append(&mut archived);
+            self.reindex(&ids);
+            self.commit(String::from("archive"), snapshot);
         }
+        ids
     }
-    fn remove_task(&mut self, id: u8) -> bool {
-        for (index, task) in self.store.iter_mut().enumerate() {
+
+    fn unarchive_task(&mut self, id: u8) -> bool {
+        self.load_archive();
+        let snapshot: JsonValue = self.to_json_object();
+        let archive: &mut Vec<Task> = self.archive.get_or_insert_default();
+        for (index, task) in archive.iter().enumerate() {
             if id == task.get_id() {
-                self.store.remove(index);
-                self.save();
+                let task: Task = archive.remove(index);
+                let position: usize = self.store.partition_point(|task| task.get_id() < id);
+                self.store.insert(position, task);
+                self.reindex(&[id]);
+                self.commit(format!("unarchive #{id}"), snapshot);
                 return true;
             }
         }
-        return false;
+        false
+    }
+
+    fn get_views(&self) -> Vec<View> {
+        self.views.clone()
+    }
+
+    fn get_view(&self, name: &str) -> Option<&View> {
+        self.views.iter().find(|view| view.get_name() == name)
+    }
+
+    fn save_view(&mut self, view: View) {
+        let snapshot: JsonValue = self.to_json_object();
+        let name: String = view.get_name().to_owned();
+        match self
+            .views
+            .iter_mut()
+            .find(|saved| saved.get_name() == &name)
+        {
+            Some(saved) => *saved = view,
+            None => self.views.push(view),
+        }
+        self.commit(format!("view save {name}"), snapshot);
+    }
+
+    fn remove_view(&mut self, name: &str) -> bool {
+        let snapshot: JsonValue = self.to_json_object();
+        let count: usize = self.views.len();
+        self.views.retain(|view| view.get_name() != name);
+        if self.views.len() == count {
+            return false;
+        }
+        self.commit(format!("view delete {name}"), snapshot);
+        true
+    }
+
+    fn get_milestones(&self) -> Vec<Milestone> {
+        self.milestones.clone()
+    }
+
+    fn get_milestone(&self, name: &str) -> Option<&Milestone> {
+        self.milestones
+            .iter()
+            .find(|milestone| milestone.get_name() == name)
+    }
+
+    fn save_milestone(&mut self, milestone: Milestone) {
+        let snapshot: JsonValue = self.to_json_object();
+        let name: String = milestone.get_name().to_owned();
+        match self
+            .milestones
+            .iter_mut()
+            .find(|saved| saved.get_name() == &name)
+        {
+            Some(saved) => *saved = milestone,
+            None => self.milestones.push(milestone),
+        }
+        self.commit(format!("milestone add {name}"), snapshot);
+    }
+
+    fn remove_milestone(&mut self, name: &str) -> bool {
+        let snapshot: JsonValue = self.to_json_object();
+        let count: usize = self.milestones.len();
+        self.milestones
+            .retain(|milestone| milestone.get_name() != name);
+        if self.milestones.len() == count {
+            return false;
+        }
+        for task in &mut self.store {
+            if task
+                .get_milestone()
+                .is_some_and(|milestone| milestone == name)
+            {
+                task.set_milestone(None);
+            }
+        }
+        self.commit(format!("milestone delete {name}"), snapshot);
+        true
+    }
+
+    fn get_projects(&self) -> Vec<Project> {
+        self.projects.clone()
+    }
+
+    fn get_project(&self, name: &str) -> Option<&Project> {
+        self.projects
+            .iter()
+            .find(|project| project.get_name() == name)
+    }
+
+    fn add_project(&mut self, project: Project) -> bool {
+        if self.get_project(project.get_name()).is_some() {
+            return false;
+        }
+        let snapshot: JsonValue = self.to_json_object();
+        let name: String = project.get_name().to_owned();
+        self.projects.push(project);
+        self.commit(format!("project add {name}"), snapshot);
+        true
+    }
+
+    fn set_actor(&mut self, actor: Option<String>) {
+        self.actor = actor;
+    }
+
+    // The archive is loaded first so the snapshot handed to the journal is
+    // complete, whichever operation it is going to be replayed against.
+    fn undo(&mut self) -> Option<String> {
+        self.load_archive();
+        let entry: JournalEntry = self.journal.undo(self.to_json_object())?;
+        self.restore(&entry);
+        self.audit(format!("undo '{}'", entry.get_operation()));
+        Some(entry.get_operation().to_owned())
+    }
+
+    fn redo(&mut self) -> Option<String> {
+        self.load_archive();
+        let entry: JournalEntry = self.journal.redo(self.to_json_object())?;
+        self.restore(&entry);
+        self.audit(format!("redo '{}'", entry.get_operation()));
+        Some(entry.get_operation().to_owned())
+    }
+
+    fn reload(&mut self) {
+        if JsonStore::get_modified_time() == self.modified {
+            return;
+        }
+        self.journal = Journal::load(self.journal.get_depth());
+        self.archive = None;
+        self.read();
     }
 }
 
 impl JsonStore {
-    pub fn load() -> Self {
+    pub fn load(config: &Config) -> Self {
+        let journal: Journal = Journal::load(config.get_undo_depth());
+        let mut json_store: JsonStore = JsonStore {
+            store: vec![],
+            trash: vec![],
+            views: vec![],
+            milestones: vec![],
+            projects: vec![],
+            archive: None,
+            last_id: 0,
+            journal,
+            actor: None,
+            search_index: OnceCell::new(),
+            modified: None,
+        };
+        json_store.read();
+        json_store
+    }
+
+    // The file is locked while it is read and saved, so that no process
+    // reads it halfway through the save of another one.
+    fn read(&mut self) {
+        let file_path: PathBuf = JsonStore::get_file_path();
+        let Ok(mut file) = File::open(file_path) else {
+            // Without a file, the store starts empty.
+            self.load_json_object(&JsonValue::new_array());
+            self.modified = None;
+            return;
+        };
+        file.lock_shared().unwrap();
+        let mut source: String = String::new();
+        file.read_to_string(&mut source).unwrap();
+        let json_object: JsonValue = json::parse(&source).unwrap();
+        self.load_json_object(&json_object);
+        self.modified = file
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok();
+    }
+
+    pub fn save(&mut self) {
         let file_path: PathBuf = JsonStore::get_file_path();
+        let mut json_object: JsonValue = self.to_json_object();
+        let archive: JsonValue = json_object.remove("archive");
+        let mut file: File = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(file_path)
+            .unwrap();
+        file.lock().unwrap();
+        file.set_len(0).unwrap();
+        file.write_all(json_object.to_string().as_bytes()).unwrap();
+        self.modified = file
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        if self.archive.is_some() {
+            let archive_file_path: PathBuf = JsonStore::get_archive_file_path();
+            fs::write(archive_file_path, archive.to_string()).unwrap();
+        }
+        if let Some(index) = self.search_index.get() {
+            JsonStore::write_search_index(index);
+        }
+    }
+
+    // Persists the store together with the journal entry of the operation
+    // that has just been applied.
+    fn commit(&mut self, operation: String, snapshot: JsonValue) {
+        self.audit(operation.clone());
+        self.journal.record(operation, snapshot);
+        self.save();
+        self.journal.save();
+    }
+
+    fn audit(&self, operation: String) {
+        let actor: String = self
+            .actor
+            .clone()
+            .unwrap_or_else(|| audit::LOCAL_ACTOR.to_owned());
+        AuditEntry::new(date::now(), actor, operation).append();
+    }
+
+    fn restore(&mut self, entry: &JournalEntry) {
+        self.load_json_object(entry.get_snapshot());
+        // A saved index would not match the restored tasks anymore.
+        if JsonStore::file_exists(&JsonStore::get_search_index_file_path()) {
+            self.search_index = OnceCell::from(SearchIndex::build(&self.store));
+        }
+        self.save();
+        self.journal.save();
+    }
+
+    // Keeps the search index in sync with the given tasks, if it was saved.
+    // Without a saved index, the next search builds it from every task.
+    fn reindex(&mut self, ids: &[u8]) {
+        if self.search_index.get().is_none()
+            && let Some(index) = JsonStore::read_search_index()
+        {
+            self.search_index = OnceCell::from(index);
+        }
+        let Some(index) = self.search_index.get_mut() else {
+            return;
+        };
+        for id in ids {
+            match self.store.iter().find(|task| task.get_id() == *id) {
+                Some(task) => index.insert(task),
+                None => index.remove(*id),
+            }
+        }
+    }
+
+    // Applies a change to every task with one of the given ids and persists
+    // them all at once. Returns the ids of the tasks that were found.
+    fn apply<F>(&mut self, ids: &[u8], operation: String, mut change: F) -> Vec<u8>
+    where
+        F: FnMut(&mut Task),
+    {
+        let snapshot: JsonValue = self.to_json_object();
+        let mut applied: Vec<u8> = vec![];
+        for id in ids {
+            if let Some(task) = self.get_mut_task(*id) {
+                change(task);
+                applied.push(*id);
+            }
+        }
+        if !applied.is_empty() {
+            self.reindex(&applied);
+            self.commit(operation, snapshot);
+        }
+        applied
+    }
+
+    // Ids are never reused, archived and deleted tasks keep theirs.
+    fn next_id(&self) -> Result<u8, String> {
+        self.last_id.checked_add(1).ok_or_else(|| {
+            format!(
+                "Every task id up to {} is taken, no more tasks can be added",
+                u8::MAX
+            )
+        })
+    }
+
+    fn get_mut_task(&mut self, id: u8) -> Option<&mut Task> {
+        self.store.iter_mut().find(|task| id == task.get_id())
+    }
+
+    fn load_archive(&mut self) {
+        if self.archive.is_none() {
+            self.archive = Some(Self::read_archive());
+        }
+    }
+
+    fn read_archive() -> Vec<Task> {
+        let file_path: PathBuf = JsonStore::get_archive_file_path();
         if JsonStore::file_exists(&file_path) {
             let source: String = fs::read_to_string(file_path).unwrap();
-            let json_object: JsonValue = json::parse(&source).unwrap();
-            Self::from_json_array(json_object)
+            let json_array: JsonValue = json::parse(&source).unwrap();
+            Self::from_json_array(&json_array)
         } else {
-            let store: Vec<Task> = vec![];
-            let last_id: u8 = 0;
-            return JsonStore { store, last_id };
+            vec![]
         }
     }
 
-    pub fn save(&self) {
-        let file_path: PathBuf = JsonStore::get_file_path();
-        let contents: String = self.to_json_array().to_string();
-        fs::write(file_path, contents).unwrap()
+    // Older stores were saved as a plain array of tasks, without a trash.
+    // Snapshots only contain the archive when it was loaded at the time.
+    fn load_json_object(&mut self, json_object: &JsonValue) {
+        if json_object.is_array() {
+            self.store = Self::from_json_array(json_object);
+            self.trash = vec![];
+            self.views = vec![];
+            self.milestones = vec![];
+            self.projects = vec![];
+        } else {
+            self.store = Self::from_json_array(&json_object["tasks"]);
+            self.trash = Self::trash_from_json_array(&json_object["trash"]);
+            self.views = Self::views_from_json_array(&json_object["views"]);
+            self.milestones = Self::milestones_from_json_array(&json_object["milestones"]);
+            self.projects = Self::projects_from_json_array(&json_object["projects"]);
+            if json_object.has_key("archive") {
+                self.archive = Some(Self::from_json_array(&json_object["archive"]));
+            }
+        }
+        self.search_index = OnceCell::new();
+        let stored_last_id: u8 = json_object["last_id"].as_u8().unwrap_or(0);
+        self.last_id = self.find_last_id().max(stored_last_id);
     }
 
-    fn get_mut_task(&mut self, id: u8) -> Option<&mut Task> {
-        for task in self.store.iter_mut() {
-            if id == task.get_id() {
-                return Some(task);
+    // The last id is stored as well, since archived tasks still own their id
+    // and the archive is not loaded to find the next free one.
+    fn to_json_object(&self) -> JsonValue {
+        let mut json_object: JsonValue = object! {
+            "last_id" => self.last_id,
+            "tasks" => self.to_json_array(),
+            "trash" => self.trash_to_json_array(),
+            "views" => self.views.iter().map(JsonValue::from).collect::<Vec<JsonValue>>(),
+            "milestones" => self.milestones.iter().map(JsonValue::from).collect::<Vec<JsonValue>>(),
+            "projects" => self.projects.iter().map(JsonValue::from).collect::<Vec<JsonValue>>()
+        };
+        if let Some(archive) = &self.archive {
+            let mut json_array: JsonValue = json::array![];
+            for task in archive {
+                json_array.push(JsonValue::from(task)).unwrap();
             }
+            json_object["archive"] = json_array;
         }
-        return None;
+        json_object
     }
 
-    fn from_json_array(json_array: JsonValue) -> JsonStore {
+    fn from_json_array(json_array: &JsonValue) -> Vec<Task> {
         let mut store: Vec<Task> = vec![];
         for json_value in json_array.members() {
             let task: Task = Task::try_from(json_value).unwrap();
             store.push(task);
         }
-        let length: usize = store.len();
-        let last_id: u8 = store[length - 1].get_id();
-        return JsonStore { store, last_id };
+        store
+    }
+
+    fn trash_from_json_array(json_array: &JsonValue) -> Vec<TrashedTask> {
+        let mut trash: Vec<TrashedTask> = vec![];
+        for json_value in json_array.members() {
+            let trashed_task: TrashedTask = TrashedTask::try_from(json_value).unwrap();
+            trash.push(trashed_task);
+        }
+        trash
+    }
+
+    fn views_from_json_array(json_array: &JsonValue) -> Vec<View> {
+        let mut views: Vec<View> = vec![];
+        for json_value in json_array.members() {
+            let view: View = View::try_from(json_value).unwrap();
+            views.push(view);
+        }
+        views
+    }
+
+    fn milestones_from_json_array(json_array: &JsonValue) -> Vec<Milestone> {
+        let mut milestones: Vec<Milestone> = vec![];
+        for json_value in json_array.members() {
+            let milestone: Milestone = Milestone::try_from(json_value).unwrap();
+            milestones.push(milestone);
+        }
+        milestones
+    }
+
+    fn projects_from_json_array(json_array: &JsonValue) -> Vec<Project> {
+        let mut projects: Vec<Project> = vec![];
+        for json_value in json_array.members() {
+            let project: Project = Project::try_from(json_value).unwrap();
+            projects.push(project);
+        }
+        projects
+    }
+
+    // Trashed tasks keep their id, so they are taken into account to avoid
+    // handing out an id that a later restore would collide with.
+    fn find_last_id(&self) -> u8 {
+        let task_ids = self.store.iter().map(Task::get_id);
+        let trash_ids = self
+            .trash
+            .iter()
+            .map(|trashed_task| trashed_task.get_task().get_id());
+        task_ids.chain(trash_ids).max().unwrap_or(0)
     }
 
     fn to_json_array(&self) -> JsonValue {
@@ -129,7 +773,15 @@ impl JsonStore {
             let task: JsonValue = JsonValue::from(task_object);
             json_array.push(task).unwrap();
         }
-        return json_array;
+        json_array
+    }
+
+    fn trash_to_json_array(&self) -> JsonValue {
+        let mut json_array: JsonValue = json::array![];
+        for trashed_task in &self.trash {
+            json_array.push(JsonValue::from(trashed_task)).unwrap();
+        }
+        json_array
     }
 
     fn get_file_path() -> PathBuf {
@@ -141,7 +793,67 @@ impl JsonStore {
         home.join(path)
     }
 
-    fn file_exists(file_path: &PathBuf) -> bool {
+    fn get_modified_time() -> Option<SystemTime> {
+        fs::metadata(JsonStore::get_file_path())
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
+    fn get_archive_file_path() -> PathBuf {
+        let home: PathBuf = match env::home_dir() {
+            Some(home_path) => home_path,
+            None => panic!("Can not read the home path"),
+        };
+        let path: &Path = Path::new("tasks.archive.json");
+        home.join(path)
+    }
+
+    // An index that can not be read is built again on the next search.
+    fn read_search_index() -> Option<SearchIndex> {
+        let source: String = fs::read_to_string(JsonStore::get_search_index_file_path()).ok()?;
+        let json_object: JsonValue = json::parse(&source).ok()?;
+        SearchIndex::try_from(&json_object).ok()
+    }
+
+    fn write_search_index(index: &SearchIndex) {
+        let file_path: PathBuf = JsonStore::get_search_index_file_path();
+        fs::write(file_path, JsonValue::from(index).to_string()).unwrap();
+    }
+
+    fn get_search_index_file_path() -> PathBuf {
+        let home: PathBuf = match env::home_dir() {
+            Some(home_path) => home_path,
+            None => panic!("Can not read the home path"),
+        };
+        let path: &Path = Path::new("tasks.index.json");
+        home.join(path)
+    }
+
+    fn file_exists(file_path: &Path) -> bool {
         file_path.exists()
     }
 }
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    use std::process;
+    use std::sync::{Mutex, MutexGuard, PoisonError};
+
+    // The store is saved in the home directory, so the tests using it take
+    // turns.
+    static HOME: Mutex<()> = Mutex::new(());
+
+    // An empty store, in a home directory of its own.
+    pub fn empty_store() -> (MutexGuard<'static, ()>, JsonStore) {
+        let guard: MutexGuard<'static, ()> = HOME.lock().unwrap_or_else(PoisonError::into_inner);
+        let home: PathBuf = env::temp_dir().join(format!("task-tracker-{}", process::id()));
+        let _ = fs::remove_dir_all(&home);
+        fs::create_dir_all(&home).unwrap();
+        // SAFETY: no other test reads the environment, and these ones hold
+        // the lock while they use it.
+        unsafe { env::set_var("HOME", &home) };
+        (guard, JsonStore::load(&Config::default()))
+    }
+}
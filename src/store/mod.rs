@@ -1,12 +1,87 @@
+pub mod audit;
+pub mod journal;
 pub mod json_store;
+pub mod search_index;
+pub mod sync_bases;
+pub mod tokens;
 
 use crate::model;
+use crate::model::filter::Filter;
 
 pub trait TaskStore {
-    fn get_tasks(&self, state_filter: String) -> Vec<model::Task>;
+    fn get_tasks(&self) -> Vec<model::Task>;
+    // The whole filter is handed to the store, so backends with indexes can
+    // translate it into their own lookups instead of scanning every task.
+    fn find_tasks(&self, filter: &Filter) -> Vec<model::Task>;
+    fn search(&self, terms: &[model::search::SearchTerm]) -> Vec<model::search::SearchHit>;
     fn get_task(&self, id: u8) -> Option<&model::Task>;
-    fn add_task(&mut self, task: String) -> u8;
-    fn set_state(&mut self, id: u8, state: String) -> bool;
+    // Tasks added to a project get the next number of the project as well.
+    // Fails once every task id is taken.
+    fn add_task(
+        &mut self,
+        task: String,
+        milestone: Option<String>,
+        project: Option<String>,
+    ) -> Result<u8, String>;
+    // Adds a task and sets its other fields as one operation.
+    fn create_task(
+        &mut self,
+        task: String,
+        milestone: Option<String>,
+        project: Option<String>,
+        change: &mut dyn FnMut(&mut model::Task),
+    ) -> Result<u8, String>;
+    // Adds the imported tasks and the new projects they belong to as one
+    // operation. Fails without adding any when too few task ids are left.
+    fn import_tasks(
+        &mut self,
+        projects: Vec<model::project::Project>,
+        tasks: &[model::import::ImportedTask],
+    ) -> Result<Vec<u8>, String>;
+    // Applies the changes a Markdown sync makes to the tasks as one
+    // operation, and returns the ids of the added tasks in order. Fails
+    // without changing any task when too few task ids are left.
+    fn sync_tasks(&mut self, changes: &[model::sync::Change]) -> Result<Vec<u8>, String>;
+    fn set_state(&mut self, ids: &[u8], state: String) -> Vec<u8>;
     fn update_task(&mut self, id: u8, updated_task: String) -> bool;
-    fn remove_task(&mut self, id: u8) -> bool;
+    // Changes several fields of a task as one operation, undone as a whole.
+    fn edit_task(&mut self, id: u8, change: &mut dyn FnMut(&mut model::Task)) -> bool;
+    fn set_priority(&mut self, ids: &[u8], priority: model::Priority) -> Vec<u8>;
+    fn tag_tasks(&mut self, ids: &[u8], tag: String) -> Vec<u8>;
+    fn add_note(&mut self, id: u8, note: String) -> bool;
+    fn untag_tasks(&mut self, ids: &[u8], tag: String) -> Vec<u8>;
+    fn set_milestone(&mut self, ids: &[u8], milestone: Option<String>) -> Vec<u8>;
+    fn remove_tasks(&mut self, ids: &[u8]) -> Vec<u8>;
+    fn get_trash(&self) -> Vec<model::TrashedTask>;
+    fn restore_task(&mut self, id: u8) -> bool;
+    fn purge_trash(&mut self, older_than: Option<u64>) -> Vec<u8>;
+    fn get_archived_tasks(&self) -> Vec<model::Task>;
+    fn archive_tasks(&mut self, older_than: Option<u64>) -> Vec<u8>;
+    fn unarchive_task(&mut self, id: u8) -> bool;
+    fn get_views(&self) -> Vec<model::view::View>;
+    fn get_view(&self, name: &str) -> Option<&model::view::View>;
+    fn save_view(&mut self, view: model::view::View);
+    fn remove_view(&mut self, name: &str) -> bool;
+    fn get_milestones(&self) -> Vec<model::milestone::Milestone>;
+    fn get_milestone(&self, name: &str) -> Option<&model::milestone::Milestone>;
+    fn save_milestone(&mut self, milestone: model::milestone::Milestone);
+    // Removes the milestone and unplans the tasks planned for it.
+    fn remove_milestone(&mut self, name: &str) -> bool;
+    fn get_projects(&self) -> Vec<model::project::Project>;
+    fn get_project(&self, name: &str) -> Option<&model::project::Project>;
+    fn add_project(&mut self, project: model::project::Project) -> bool;
+    // Who the following mutations are recorded for in the audit log (e.g.:
+    // the owner of an API token), or the local user when None.
+    fn set_actor(&mut self, actor: Option<String>);
+    fn undo(&mut self) -> Option<String>;
+    fn redo(&mut self) -> Option<String>;
+    // Reads the store again if another process changed it since it was last
+    // read or saved (e.g.: a command run while the server is up).
+    fn reload(&mut self);
+}
+
+// Formats a list of task ids for messages (e.g.: '#3, #5, #7').
+pub fn format_ids(ids: &[u8]) -> String {
+    let ids: Vec<String> = ids.iter().map(|id| format!("#{id}")).collect();
+    ids.join(", ")
 }
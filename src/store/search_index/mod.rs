@@ -0,0 +1,296 @@
+use crate::model::Task;
+use crate::model::search::{SearchTerm, tokenize};
+
+use json::JsonValue;
+use std::collections::{BTreeMap, HashMap};
+
+// Positions of consecutive fields are kept apart by this gap, so a phrase
+// never matches across the end of one field and the start of the next one.
+const FIELD_GAP: usize = 1;
+
+// An inverted index from words to the tasks containing them. It is saved
+// next to the store and updated task by task, so searching never has to
+// scan the whole store.
+#[derive(Debug, Default)]
+pub struct SearchIndex {
+    // word -> task id -> positions of the word in the task
+    postings: BTreeMap<String, BTreeMap<u8, Vec<usize>>>,
+    // task id -> distinct words of the task, to drop its postings on updates
+    documents: HashMap<u8, Vec<String>>,
+}
+
+// A task matching a search: its id, its relevance and the matched words.
+pub type Match = (u8, f64, Vec<String>);
+
+impl SearchIndex {
+    pub fn build(tasks: &[Task]) -> Self {
+        let mut index: SearchIndex = SearchIndex::default();
+        for task in tasks {
+            index.insert(task);
+        }
+        index
+    }
+
+    // Indexes the task text, notes and tags, replacing any previous version.
+    pub fn insert(&mut self, task: &Task) {
+        let id: u8 = task.get_id();
+        self.remove(id);
+
+        let mut fields: Vec<&String> = vec![task.get_task()];
+        fields.extend(task.get_notes());
+        fields.extend(task.get_tags());
+
+        let mut words: Vec<String> = vec![];
+        let mut position: usize = 0;
+        for field in fields {
+            for word in tokenize(field) {
+                let positions: &mut Vec<usize> = self
+                    .postings
+                    .entry(word.clone())
+                    .or_default()
+                    .entry(id)
+                    .or_default();
+                positions.push(position);
+                if !words.contains(&word) {
+                    words.push(word);
+                }
+                position += 1;
+            }
+            position += FIELD_GAP;
+        }
+        self.documents.insert(id, words);
+    }
+
+    pub fn remove(&mut self, id: u8) {
+        let Some(words) = self.documents.remove(&id) else {
+            return;
+        };
+        for word in words {
+            if let Some(tasks) = self.postings.get_mut(&word) {
+                tasks.remove(&id);
+                if tasks.is_empty() {
+                    self.postings.remove(&word);
+                }
+            }
+        }
+    }
+
+    // Finds the tasks containing every term, most relevant first. Each term
+    // adds its number of occurrences weighted by how rare the term is.
+    pub fn search(&self, terms: &[SearchTerm]) -> Vec<Match> {
+        let task_count: f64 = self.documents.len() as f64;
+        let mut results: Option<HashMap<u8, (f64, Vec<String>)>> = None;
+        for term in terms {
+            let occurrences: HashMap<u8, (usize, Vec<String>)> = self.find(term);
+            if occurrences.is_empty() {
+                return vec![];
+            }
+            let weight: f64 = match term {
+                SearchTerm::Phrase(words) => words.len() as f64,
+                _ => 1.0,
+            };
+            let rarity: f64 = (1.0 + task_count / occurrences.len() as f64).ln();
+            let mut scored: HashMap<u8, (f64, Vec<String>)> = HashMap::new();
+            for (id, (count, words)) in occurrences {
+                let score: f64 = count as f64 * rarity * weight;
+                match &results {
+                    Some(results) => {
+                        if let Some((previous_score, previous_words)) = results.get(&id) {
+                            let mut matched_words: Vec<String> = previous_words.clone();
+                            matched_words.extend(words);
+                            scored.insert(id, (previous_score + score, matched_words));
+                        }
+                    }
+                    None => {
+                        scored.insert(id, (score, words));
+                    }
+                }
+            }
+            results = Some(scored);
+        }
+
+        let mut matches: Vec<Match> = results
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(id, (score, words))| (id, score, words))
+            .collect();
+        matches.sort_by(|left, right| right.1.total_cmp(&left.1).then(left.0.cmp(&right.0)));
+        matches
+    }
+
+    // Counts the occurrences of a term in each task containing it.
+    fn find(&self, term: &SearchTerm) -> HashMap<u8, (usize, Vec<String>)> {
+        let mut occurrences: HashMap<u8, (usize, Vec<String>)> = HashMap::new();
+        match term {
+            SearchTerm::Word(word) => {
+                if let Some(tasks) = self.postings.get(word) {
+                    for (id, positions) in tasks {
+                        occurrences.insert(*id, (positions.len(), vec![word.clone()]));
+                    }
+                }
+            }
+            SearchTerm::Prefix(prefix) => {
+                let words = self
+                    .postings
+                    .range(prefix.clone()..)
+                    .take_while(|(word, _)| word.starts_with(prefix.as_str()));
+                for (word, tasks) in words {
+                    for (id, positions) in tasks {
+                        let (count, matched_words) = occurrences.entry(*id).or_default();
+                        *count += positions.len();
+                        matched_words.push(word.clone());
+                    }
+                }
+            }
+            SearchTerm::Phrase(words) => {
+                let Some(first_tasks) = words.first().and_then(|word| self.postings.get(word))
+                else {
+                    return occurrences;
+                };
+                for (id, first_positions) in first_tasks {
+                    let count: usize = first_positions
+                        .iter()
+                        .filter(|position| self.phrase_continues(words, *id, **position))
+                        .count();
+                    if count > 0 {
+                        occurrences.insert(*id, (count, words.clone()));
+                    }
+                }
+            }
+        }
+        occurrences
+    }
+
+    fn phrase_continues(&self, words: &[String], id: u8, start: usize) -> bool {
+        words.iter().enumerate().skip(1).all(|(offset, word)| {
+            self.postings
+                .get(word)
+                .and_then(|tasks| tasks.get(&id))
+                .is_some_and(|positions| positions.contains(&(start + offset)))
+        })
+    }
+}
+
+// Only the postings are saved, the words of each task are derived from them.
+impl From<&SearchIndex> for JsonValue {
+    fn from(index: &SearchIndex) -> JsonValue {
+        let mut json_object: JsonValue = JsonValue::new_object();
+        for (word, tasks) in &index.postings {
+            let mut json_tasks: JsonValue = JsonValue::new_object();
+            for (id, positions) in tasks {
+                json_tasks[id.to_string().as_str()] = JsonValue::from(positions.clone());
+            }
+            json_object[word.as_str()] = json_tasks;
+        }
+        json_object
+    }
+}
+
+impl TryFrom<&JsonValue> for SearchIndex {
+    type Error = String;
+    fn try_from(json_object: &JsonValue) -> Result<Self, Self::Error> {
+        if !json_object.is_object() {
+            return Err("Can not parse the search index".to_owned());
+        }
+        let mut index: SearchIndex = SearchIndex::default();
+        for (word, json_tasks) in json_object.entries() {
+            let mut tasks: BTreeMap<u8, Vec<usize>> = BTreeMap::new();
+            for (id, json_positions) in json_tasks.entries() {
+                let id: u8 = id
+                    .parse()
+                    .map_err(|_| format!("Can not parse the task id '{id}' of the search index"))?;
+                let positions: Vec<usize> = json_positions
+                    .members()
+                    .map(|position| position.as_usize().ok_or("Can not parse a word position"))
+                    .collect::<Result<Vec<usize>, &str>>()?;
+                index.documents.entry(id).or_default().push(word.to_owned());
+                tasks.insert(id, positions);
+            }
+            index.postings.insert(word.to_owned(), tasks);
+        }
+        Ok(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::TaskState;
+
+    fn task(id: u8, text: &str) -> Task {
+        Task::new(id, text.to_string(), TaskState::NotStarted)
+    }
+
+    fn ids(matches: &[Match]) -> Vec<u8> {
+        matches.iter().map(|(id, _, _)| *id).collect()
+    }
+
+    fn word(word: &str) -> SearchTerm {
+        SearchTerm::Word(word.to_string())
+    }
+
+    #[test]
+    fn test_search_requires_every_word() {
+        let index: SearchIndex =
+            SearchIndex::build(&[task(1, "fix login bug"), task(2, "login page")]);
+
+        assert_eq!(ids(&index.search(&[word("login"), word("bug")])), vec![1]);
+    }
+
+    #[test]
+    fn test_search_ranks_more_occurrences_first() {
+        let index: SearchIndex =
+            SearchIndex::build(&[task(1, "login page"), task(2, "login after login fails")]);
+
+        assert_eq!(ids(&index.search(&[word("login")])), vec![2, 1]);
+    }
+
+    #[test]
+    fn test_search_prefix() {
+        let index: SearchIndex = SearchIndex::build(&[task(1, "logging"), task(2, "catalog")]);
+
+        let matches: Vec<Match> = index.search(&[SearchTerm::Prefix("log".to_string())]);
+        assert_eq!(ids(&matches), vec![1]);
+        assert_eq!(matches[0].2, vec!["logging".to_string()]);
+    }
+
+    #[test]
+    fn test_search_phrase_requires_adjacent_words() {
+        let index: SearchIndex =
+            SearchIndex::build(&[task(1, "login bug"), task(2, "bug in login")]);
+
+        let phrase: SearchTerm = SearchTerm::Phrase(vec!["login".to_string(), "bug".to_string()]);
+        assert_eq!(ids(&index.search(&[phrase])), vec![1]);
+    }
+
+    #[test]
+    fn test_insert_replaces_previous_version() {
+        let mut index: SearchIndex = SearchIndex::build(&[task(1, "login bug")]);
+        index.insert(&task(1, "signup bug"));
+
+        assert!(index.search(&[word("login")]).is_empty());
+        assert_eq!(ids(&index.search(&[word("signup")])), vec![1]);
+    }
+
+    // Convert SearchIndex to JsonValue and back
+    #[test]
+    fn test_search_index_json_round_trip() {
+        let index: SearchIndex =
+            SearchIndex::build(&[task(1, "login bug"), task(2, "bug in login")]);
+
+        let mut loaded: SearchIndex = SearchIndex::try_from(&JsonValue::from(&index)).unwrap();
+
+        let phrase: SearchTerm = SearchTerm::Phrase(vec!["login".to_string(), "bug".to_string()]);
+        assert_eq!(ids(&loaded.search(&[phrase])), vec![1]);
+        loaded.remove(1);
+        assert_eq!(ids(&loaded.search(&[word("bug")])), vec![2]);
+    }
+
+    #[test]
+    fn test_remove_drops_task() {
+        let mut index: SearchIndex = SearchIndex::build(&[task(1, "login bug")]);
+        index.remove(1);
+
+        assert!(index.search(&[word("bug")]).is_empty());
+    }
+}
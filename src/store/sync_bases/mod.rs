@@ -0,0 +1,70 @@
+use crate::model::sync::SyncedItem;
+
+use json::JsonValue;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// The items of every synced file as they were after its last sync, by the
+// absolute path of the file. They live in their own file, so undoing a
+// change of the tasks does not make the next sync take it for an edit of
+// the file.
+#[derive(Debug, Default)]
+pub struct SyncBases {
+    files: Vec<(String, Vec<SyncedItem>)>,
+}
+
+impl SyncBases {
+    pub fn load() -> Self {
+        let file_path: PathBuf = SyncBases::get_file_path();
+        if !file_path.exists() {
+            return SyncBases::default();
+        }
+        let source: String = fs::read_to_string(file_path).unwrap();
+        let json_object: JsonValue = json::parse(&source).expect("Can not parse the sync file");
+        let files: Vec<(String, Vec<SyncedItem>)> = json_object
+            .entries()
+            .map(|(path, json_array)| {
+                let items: Vec<SyncedItem> = json_array
+                    .members()
+                    .map(|json_value| {
+                        SyncedItem::try_from(json_value).unwrap_or_else(|error| panic!("{error}"))
+                    })
+                    .collect();
+                (path.to_owned(), items)
+            })
+            .collect();
+        SyncBases { files }
+    }
+
+    pub fn save(&self) {
+        let mut json_object: JsonValue = JsonValue::new_object();
+        for (path, items) in &self.files {
+            json_object[path.as_str()] =
+                JsonValue::Array(items.iter().map(JsonValue::from).collect());
+        }
+        fs::write(SyncBases::get_file_path(), json_object.to_string()).unwrap();
+    }
+
+    pub fn get_items(&self, path: &str) -> Vec<SyncedItem> {
+        self.files
+            .iter()
+            .find(|(file, _)| file == path)
+            .map(|(_, items)| items.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn set_items(&mut self, path: String, items: Vec<SyncedItem>) {
+        self.files.retain(|(file, _)| *file != path);
+        self.files.push((path, items));
+    }
+
+    fn get_file_path() -> PathBuf {
+        let home: PathBuf = match env::home_dir() {
+            Some(home_path) => home_path,
+            None => panic!("Can not read the home path"),
+        };
+        let path: &Path = Path::new("tasks.sync.json");
+        home.join(path)
+    }
+}
@@ -0,0 +1,136 @@
+use crate::date;
+use crate::model::token::{Scope, Token};
+use crate::sha256;
+
+use json::JsonValue;
+use std::env;
+use std::fs::{self, File, OpenOptions, Permissions};
+use std::io::{Read, Write};
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+
+// The API tokens of the server. They live in their own file, readable only
+// by the user, so undoing a change of the tasks never brings back a revoked
+// token.
+#[derive(Debug, Default)]
+pub struct Tokens {
+    tokens: Vec<Token>,
+}
+
+impl Tokens {
+    pub fn load() -> Self {
+        let file_path: PathBuf = Tokens::get_file_path();
+        if !file_path.exists() {
+            return Tokens::default();
+        }
+        let source: String = fs::read_to_string(file_path).unwrap();
+        let json_array: JsonValue = json::parse(&source).expect("Can not parse the tokens file");
+        let tokens: Vec<Token> = json_array
+            .members()
+            .map(|json_value| Token::try_from(json_value).unwrap_or_else(|error| panic!("{error}")))
+            .collect();
+        Tokens { tokens }
+    }
+
+    // The file is created readable only by the user, and an older file is
+    // made so before the tokens are written to it.
+    pub fn save(&self) {
+        let file_path: PathBuf = Tokens::get_file_path();
+        let json_array: JsonValue =
+            JsonValue::Array(self.tokens.iter().map(JsonValue::from).collect());
+        let mut file: File = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(file_path)
+            .unwrap();
+        file.set_permissions(Permissions::from_mode(0o600)).unwrap();
+        file.write_all(json_array.to_string().as_bytes()).unwrap();
+    }
+
+    pub fn get_tokens(&self) -> &Vec<Token> {
+        &self.tokens
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    // Creates a token with a random secret, and returns the secret. Every
+    // owner has one token at most.
+    pub fn create(&mut self, owner: String, scope: Scope) -> Result<String, String> {
+        if self.tokens.iter().any(|token| token.get_owner() == &owner) {
+            return Err(format!(
+                "'{owner}' already has a token, revoke it to create a new one."
+            ));
+        }
+        let secret: String = format!("tt_{}", random_hex(16)?);
+        let secret_hash: String = sha256::hex_digest(&secret);
+        self.tokens
+            .push(Token::new(owner, secret_hash, scope, date::now()));
+        Ok(secret)
+    }
+
+    pub fn revoke(&mut self, owner: &str) -> bool {
+        let count: usize = self.tokens.len();
+        self.tokens.retain(|token| token.get_owner() != owner);
+        self.tokens.len() < count
+    }
+
+    // The token with the given secret, if it was not revoked.
+    pub fn authenticate(&self, secret: &str) -> Option<&Token> {
+        self.tokens.iter().find(|token| token.has_secret(secret))
+    }
+
+    fn get_file_path() -> PathBuf {
+        let home: PathBuf = match env::home_dir() {
+            Some(home_path) => home_path,
+            None => panic!("Can not read the home path"),
+        };
+        let path: &Path = Path::new("tasks.tokens.json");
+        home.join(path)
+    }
+}
+
+// Reads the given number of random bytes from the system, as hexadecimal.
+fn random_hex(bytes: usize) -> Result<String, String> {
+    let mut random: Vec<u8> = vec![0; bytes];
+    File::open("/dev/urandom")
+        .and_then(|mut source| source.read_exact(&mut random))
+        .map_err(|error| format!("Can not generate a token: {error}"))?;
+    Ok(random.iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Every owner has one token at most, until it is revoked
+    #[test]
+    fn test_create_and_revoke_tokens() {
+        let mut tokens: Tokens = Tokens::default();
+
+        let secret: String = tokens
+            .create("alice".to_string(), Scope::ReadWrite)
+            .unwrap();
+        assert!(tokens.create("alice".to_string(), Scope::Read).is_err());
+        assert_eq!(
+            tokens.authenticate(&secret).map(Token::get_owner),
+            Some(&"alice".to_string())
+        );
+
+        assert!(tokens.revoke("alice"));
+        assert!(!tokens.revoke("alice"));
+        assert_eq!(tokens.authenticate(&secret), None);
+    }
+
+    // Secrets are random
+    #[test]
+    fn test_random_hex() {
+        let first: String = random_hex(16).unwrap();
+
+        assert_eq!(first.len(), 32);
+        assert_ne!(first, random_hex(16).unwrap());
+    }
+}